@@ -608,6 +608,25 @@ impl Default for Power {
         Power::None
     }
 }
+impl Power {
+    /// Get the base power of a move, if it has a fixed one.
+    /// For multi-hit moves, this is the power of a single hit.
+    pub fn base_power(&self) -> Option<u8> {
+        match self {
+            Power::Base(p) | Power::BaseWithRecoil(p, _) | Power::BaseWithMercy(p) |
+            Power::BaseWithCharge(p, _) | Power::BaseWithCrash(p) | Power::BaseWithFaint(p) |
+            Power::BaseWithDrain(p) | Power::BaseWithTurnMultiplier(p) | Power::BaseAfterNTurns(p, _) => Some(*p),
+            Power::MultiHit(MultiHitFlavor::Variable(p)) | Power::MultiHit(MultiHitFlavor::Fixed(p, _)) |
+            Power::MultiHit(MultiHitFlavor::Accumulating(p, _, _)) => Some(*p),
+            _ => None
+        }
+    }
+
+    /// Check if a move deals a set amount of damage, rather than using the damage formula
+    pub fn is_fixed_damage(&self) -> bool {
+        matches!(self, Power::Exact(_) | Power::Percentage(_) | Power::OneHitKnockout)
+    }
+}
 
 /// The potential types of Multi Hit
 #[derive(Debug, Copy, Clone, Deserialize)]
@@ -627,7 +646,7 @@ pub enum DamageType {
 }
 
 /// Represents the stats that are maintained in battle
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum BattleStat {
     Attack,
     Defense,
@@ -640,7 +659,7 @@ pub enum BattleStat {
 }
 
 /// Represents a persistent status ailment
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum NonVolatileBattleAilment {
     Paralysis,
     Sleep,
@@ -650,7 +669,7 @@ pub enum NonVolatileBattleAilment {
 }
 
 /// Represents a weather condition in battle
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum Weather {
     HarshSun,
     Rain,
@@ -678,7 +697,7 @@ pub enum Target {
 }
 
 /// Represents if a stat change affects the user, or the target
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum StatChangeTarget {
     User,
     Target
@@ -739,6 +758,7 @@ pub enum Effect {
     Encore,
     EntryHazard(EntryHazardType),
     ClearHazards,
+    TrickRoom,
     Predicated(EffectPredicate, Box<Effect>, Box<Effect>),
     Custom
 }
@@ -748,13 +768,13 @@ pub enum EffectPredicate {
     Sunny
 }
 
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum PoisonType {
     Poison,
     BadlyPoisoned
 }
 
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum ScreenType {
     LightScreen,
     Reflect
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::item::{Item, KeyItem, Pocket};
+use crate::pokemon::Pokemon;
+
+/// The most of any one item the bag can hold
+pub const MAX_ITEM_QUANTITY: u16 = 999;
+
+/// One item in a pocket, and how many of it the player has
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BagSlot {
+    pub item: Item,
+    pub quantity: u16
+}
+
+/// The ways a pocket can be sorted
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BagSort {
+    /// By item number, which keeps TMs and HMs in numeric order
+    Number,
+    /// By quantity, most first
+    Quantity
+}
+
+/// Reasons an operation on the bag could not be done
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BagError {
+    /// The bag does not have enough of the item
+    NotEnough(Item),
+    /// Key items, TMs, and HMs cannot be thrown away
+    CannotToss(Item),
+    /// The item cannot be held by a Pokemon
+    CannotHold(Item),
+    /// The Pokemon is not holding anything to take
+    NothingHeld,
+    /// The bag has no room for more of the item
+    Full(Item)
+}
+impl Display for BagError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BagError::NotEnough(i) => write!(f, "Not enough {:?} in the bag", i),
+            BagError::CannotToss(i) => write!(f, "{:?} cannot be tossed", i),
+            BagError::CannotHold(i) => write!(f, "{:?} cannot be held", i),
+            BagError::NothingHeld => write!(f, "The Pokemon is not holding an item"),
+            BagError::Full(i) => write!(f, "The bag has no room for more {:?}", i)
+        }
+    }
+}
+impl Error for BagError {}
+
+/// The player's bag, sorting every item into its pocket
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Bag {
+    pockets: HashMap<Pocket, Vec<BagSlot>>,
+    /// Key items registered for quick use
+    registered: Vec<KeyItem>
+}
+impl Bag {
+    /// Get the most of an item the bag can hold. Reusable items can only be held once.
+    pub fn max_quantity(item: &Item) -> u16 {
+        if item.is_reusable() { 1 } else { MAX_ITEM_QUANTITY }
+    }
+
+    /// Get the items in a pocket, in their current order
+    pub fn pocket(&self, pocket: Pocket) -> &[BagSlot] {
+        self.pockets.get(&pocket).map_or(&[], |p| p.as_slice())
+    }
+
+    /// Count how many of an item are in the bag
+    pub fn count(&self, item: &Item) -> u16 {
+        self.slot(item).map_or(0, |s| s.quantity)
+    }
+
+    /// Check if at least one of an item is in the bag
+    pub fn contains(&self, item: &Item) -> bool {
+        self.count(item) > 0
+    }
+
+    fn slot(&self, item: &Item) -> Option<&BagSlot> {
+        self.pocket(item.pocket()).iter().find(|s| s.item == *item)
+    }
+
+    /// Add some of an item to the bag, stopping once the bag holds as many as it can.
+    /// New items are placed at the end of their pocket.
+    /// Returns how many were actually added.
+    pub fn add(&mut self, item: Item, quantity: u16) -> u16 {
+        let max = Bag::max_quantity(&item);
+        let pocket = self.pockets.entry(item.pocket()).or_default();
+        match pocket.iter_mut().find(|s| s.item == item) {
+            Some(slot) => {
+                let added = quantity.min(max - slot.quantity);
+                slot.quantity += added;
+                added
+            },
+            None => {
+                let added = quantity.min(max);
+                if added > 0 {
+                    pocket.push(BagSlot { item, quantity: added });
+                }
+                added
+            }
+        }
+    }
+
+    /// Add all of an item to the bag, or none at all if it would not fit
+    pub fn try_add(&mut self, item: Item, quantity: u16) -> Result<(), BagError> {
+        if self.count(&item).saturating_add(quantity) > Bag::max_quantity(&item) {
+            return Err(BagError::Full(item));
+        }
+        self.add(item, quantity);
+        Ok(())
+    }
+
+    /// Remove some of an item from the bag. Nothing is removed if there are not enough.
+    /// An item's slot is removed once none are left.
+    pub fn remove(&mut self, item: &Item, quantity: u16) -> Result<(), BagError> {
+        let pocket = self.pockets.entry(item.pocket()).or_default();
+        let idx = match pocket.iter().position(|s| s.item == *item) {
+            Some(idx) if pocket[idx].quantity >= quantity => idx,
+            _ => return Err(BagError::NotEnough(item.clone()))
+        };
+        pocket[idx].quantity -= quantity;
+        if pocket[idx].quantity == 0 {
+            pocket.remove(idx);
+            if let Item::KeyItem(k) = item {
+                self.registered.retain(|r| r != k);
+            }
+        }
+        Ok(())
+    }
+
+    /// Use an item from the bag. One is consumed, unless the item is reusable.
+    /// This only handles the bag; the item's effect is up to the caller.
+    pub fn use_item(&mut self, item: &Item) -> Result<(), BagError> {
+        if item.is_reusable() {
+            if self.contains(item) { Ok(()) } else { Err(BagError::NotEnough(item.clone())) }
+        } else {
+            self.remove(item, 1)
+        }
+    }
+
+    /// Throw away some of an item
+    pub fn toss(&mut self, item: &Item, quantity: u16) -> Result<(), BagError> {
+        if item.is_reusable() {
+            return Err(BagError::CannotToss(item.clone()));
+        }
+        self.remove(item, quantity)
+    }
+
+    /// Give an item from the bag to a Pokemon to hold.
+    /// Anything it was already holding is put back in the bag, and returned.
+    pub fn give(&mut self, item: &Item, pokemon: &mut Pokemon) -> Result<Option<Item>, BagError> {
+        if !item.is_holdable() {
+            return Err(BagError::CannotHold(item.clone()));
+        }
+        if let Some(held) = &pokemon.held_item {
+            if held != item && self.count(held) >= Bag::max_quantity(held) {
+                return Err(BagError::Full(held.clone()));
+            }
+        }
+        self.remove(item, 1)?;
+        let previous = pokemon.held_item.replace(item.clone());
+        if let Some(previous) = &previous {
+            self.add(previous.clone(), 1);
+        }
+        Ok(previous)
+    }
+
+    /// Take a Pokemon's held item, and put it in the bag
+    pub fn take(&mut self, pokemon: &mut Pokemon) -> Result<Item, BagError> {
+        let item = pokemon.held_item.clone().ok_or(BagError::NothingHeld)?;
+        self.try_add(item.clone(), 1)?;
+        pokemon.held_item = None;
+        Ok(item)
+    }
+
+    /// Sort the items in a pocket
+    pub fn sort(&mut self, pocket: Pocket, order: BagSort) {
+        match order {
+            BagSort::Number => self.sort_by_key(pocket, |s| s.item.clone()),
+            BagSort::Quantity => self.sort_by_key(pocket, |s| std::cmp::Reverse(s.quantity))
+        }
+    }
+
+    /// Sort the items in a pocket by any key, such as their translated names
+    pub fn sort_by_key<K: Ord, F: FnMut(&BagSlot) -> K>(&mut self, pocket: Pocket, f: F) {
+        if let Some(p) = self.pockets.get_mut(&pocket) {
+            p.sort_by_key(f);
+        }
+    }
+
+    /// Move an item within its pocket, so the player can order it themselves
+    pub fn move_slot(&mut self, pocket: Pocket, from: usize, to: usize) {
+        if let Some(p) = self.pockets.get_mut(&pocket) {
+            if from < p.len() && to < p.len() {
+                let slot = p.remove(from);
+                p.insert(to, slot);
+            }
+        }
+    }
+
+    /// Register a key item for quick use. The item must be in the bag.
+    pub fn register(&mut self, item: KeyItem) -> Result<(), BagError> {
+        if !self.contains(&Item::KeyItem(item)) {
+            return Err(BagError::NotEnough(Item::KeyItem(item)));
+        }
+        if !self.registered.contains(&item) {
+            self.registered.push(item);
+        }
+        Ok(())
+    }
+
+    /// Stop a key item from being registered
+    pub fn unregister(&mut self, item: KeyItem) {
+        self.registered.retain(|r| *r != item);
+    }
+
+    /// Get the registered key items, in the order they were registered
+    pub fn registered(&self) -> &[KeyItem] {
+        &self.registered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::pikachu;
+    use crate::item::{Berry, Pokeball, TM};
+
+    use super::*;
+
+    #[test]
+    fn quantities_and_pockets() {
+        let mut bag = Bag::default();
+        assert_eq!(bag.add(Item::Potion, 5), 5);
+        assert_eq!(bag.add(Item::Potion, 1000), 994);
+        assert_eq!(bag.count(&Item::Potion), MAX_ITEM_QUANTITY);
+        assert_eq!(bag.try_add(Item::Potion, 1), Err(BagError::Full(Item::Potion)));
+        assert_eq!(bag.try_add(Item::Potion, u16::MAX), Err(BagError::Full(Item::Potion)));
+        assert_eq!(bag.add(Item::TM(TM::TM01), 3), 1);
+        assert_eq!(bag.add(Item::Ball(Pokeball::PokeBall), 10), 10);
+
+        assert_eq!(bag.pocket(Pocket::Medicine).len(), 1);
+        assert_eq!(bag.pocket(Pocket::TMsAndHMs), &[BagSlot { item: Item::TM(TM::TM01), quantity: 1 }]);
+        assert!(bag.pocket(Pocket::Berries).is_empty());
+
+        assert_eq!(bag.toss(&Item::Potion, 999), Ok(()));
+        assert!(bag.pocket(Pocket::Medicine).is_empty());
+        assert_eq!(bag.toss(&Item::TM(TM::TM01), 1), Err(BagError::CannotToss(Item::TM(TM::TM01))));
+        assert_eq!(bag.use_item(&Item::TM(TM::TM01)), Ok(()));
+        assert_eq!(bag.use_item(&Item::Ball(Pokeball::PokeBall)), Ok(()));
+        assert_eq!(bag.count(&Item::Ball(Pokeball::PokeBall)), 9);
+        assert_eq!(bag.remove(&Item::Ball(Pokeball::PokeBall), 10), Err(BagError::NotEnough(Item::Ball(Pokeball::PokeBall))));
+
+        let yaml = serde_yaml::to_string(&bag).unwrap();
+        let loaded: Bag = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(loaded.count(&Item::Ball(Pokeball::PokeBall)), 9);
+    }
+
+    #[test]
+    fn sorting_and_registering() {
+        let mut bag = Bag::default();
+        bag.add(Item::TM(TM::TM30), 1);
+        bag.add(Item::TM(TM::TM02), 1);
+        bag.add(Item::Berry(Berry::SitrusBerry), 2);
+        bag.add(Item::Berry(Berry::OranBerry), 7);
+
+        bag.sort(Pocket::TMsAndHMs, BagSort::Number);
+        assert_eq!(bag.pocket(Pocket::TMsAndHMs)[0].item, Item::TM(TM::TM02));
+        bag.sort(Pocket::Berries, BagSort::Quantity);
+        assert_eq!(bag.pocket(Pocket::Berries)[0].item, Item::Berry(Berry::OranBerry));
+        bag.move_slot(Pocket::Berries, 1, 0);
+        assert_eq!(bag.pocket(Pocket::Berries)[0].item, Item::Berry(Berry::SitrusBerry));
+
+        assert_eq!(bag.register(KeyItem::Gracidea), Err(BagError::NotEnough(Item::KeyItem(KeyItem::Gracidea))));
+        bag.add(Item::KeyItem(KeyItem::Gracidea), 1);
+        assert_eq!(bag.register(KeyItem::Gracidea), Ok(()));
+        assert_eq!(bag.registered(), &[KeyItem::Gracidea]);
+    }
+
+    #[test]
+    fn give_and_take() {
+        let mut bag = Bag::default();
+        let mut pokemon = pikachu(5, &[]).pokemon;
+        bag.add(Item::Leftovers, 1);
+        bag.add(Item::LightBall, 1);
+
+        assert_eq!(bag.give(&Item::Leftovers, &mut pokemon), Ok(None));
+        assert_eq!(bag.give(&Item::LightBall, &mut pokemon), Ok(Some(Item::Leftovers)));
+        assert_eq!(bag.count(&Item::Leftovers), 1);
+        assert_eq!(bag.count(&Item::LightBall), 0);
+        assert_eq!(bag.give(&Item::KeyItem(KeyItem::Gracidea), &mut pokemon), Err(BagError::CannotHold(Item::KeyItem(KeyItem::Gracidea))));
+
+        assert_eq!(bag.take(&mut pokemon), Ok(Item::LightBall));
+        assert_eq!(bag.take(&mut pokemon), Err(BagError::NothingHeld));
+        assert_eq!(bag.count(&Item::LightBall), 1);
+    }
+}
@@ -0,0 +1,163 @@
+use rand::Rng;
+
+use crate::abilities::Ability;
+use crate::attack::{Accuracy, BattleStat, DamageType, Move, MoveData, Power, Weather};
+use crate::battle::{Battler, StatStages};
+use crate::item::Item;
+
+/// The base chance, out of 100, of a one-hit knockout move hitting a target of the same level
+const OHKO_BASE_ACCURACY: u32 = 30;
+
+/// The odds of a move hitting its target
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HitChance {
+    /// The move bypasses the accuracy check entirely
+    Always,
+    /// The move cannot hit this target, such as a one-hit knockout move against a higher level
+    Never,
+    /// The chance, out of 100, that the move hits. This may exceed 100, in which case it always hits.
+    Percent(u32)
+}
+impl HitChance {
+    /// Roll whether the move hits
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        match self {
+            HitChance::Always => true,
+            HitChance::Never => false,
+            HitChance::Percent(p) => rng.gen_range(0..100) < *p
+        }
+    }
+}
+
+/// Get the multiplier for a combined accuracy and evasion stage, as a fraction.
+/// Each positive stage adds a third, and each negative stage divides by the same amount.
+fn stage_multiplier(stage: i8) -> (u32, u32) {
+    let stage = stage.clamp(StatStages::MIN, StatStages::MAX);
+    if stage >= 0 {
+        (3 + u32::from(stage.unsigned_abs()), 3)
+    } else {
+        (3, 3 + u32::from(stage.unsigned_abs()))
+    }
+}
+
+/// Get the combined accuracy and evasion stage used when the attacker targets the defender.
+/// Unaware ignores the opposing Pokemon's stages, and an identified Pokemon cannot benefit from raised evasion.
+fn combined_stage(attacker: &Battler, defender: &Battler) -> i8 {
+    let accuracy = if defender.ability == Ability::Unaware { 0 } else { attacker.stages.get(BattleStat::Accuracy) };
+    let evasion = match defender.stages.get(BattleStat::Evasion) {
+        _ if attacker.ability == Ability::Unaware => 0,
+        e if e > 0 && defender.identified => 0,
+        e => e
+    };
+    accuracy - evasion
+}
+
+/// Get the odds of a move hitting, after stages, abilities, held items, and weather are considered.
+/// `defender_moved` is whether the defender has already acted this turn, which powers up the Zoom Lens.
+pub fn hit_chance(attacker: &Battler, defender: &Battler, data: &MoveData, weather: Option<Weather>, defender_moved: bool) -> HitChance {
+    let bypasses = attacker.locked_on || attacker.ability == Ability::NoGuard || defender.ability == Ability::NoGuard;
+
+    // One-hit knockout moves ignore stages and modifiers, and depend only on the difference in level
+    if let Power::OneHitKnockout = data.power {
+        return if defender.ability == Ability::Sturdy || attacker.pokemon.level < defender.pokemon.level {
+            HitChance::Never
+        } else if bypasses {
+            HitChance::Always
+        } else {
+            HitChance::Percent(OHKO_BASE_ACCURACY + u32::from(attacker.pokemon.level - defender.pokemon.level))
+        };
+    }
+
+    if bypasses {
+        return HitChance::Always;
+    }
+    let base = match (data.id, weather) {
+        (Move::Thunder, Some(Weather::Rain)) | (Move::Hurricane, Some(Weather::Rain)) => return HitChance::Always,
+        (Move::Blizzard, Some(Weather::Hail)) => return HitChance::Always,
+        (Move::Thunder, Some(Weather::HarshSun)) | (Move::Hurricane, Some(Weather::HarshSun)) => 50,
+        (_, _) => match data.accuracy {
+            Accuracy::Percentage(p) => u32::from(p),
+            Accuracy::AlwaysHits | Accuracy::Variable => return HitChance::Always
+        }
+    };
+
+    let (numerator, denominator) = stage_multiplier(combined_stage(attacker, defender));
+    let mut chance = base * numerator / denominator;
+
+    let mut modify = |applies: bool, numerator: u32, denominator: u32| {
+        if applies {
+            chance = chance * numerator / denominator;
+        }
+    };
+    modify(attacker.ability == Ability::CompoundEyes, 13, 10);
+    modify(attacker.ability == Ability::Hustle && data.damage_type == DamageType::Physical, 4, 5);
+    modify(attacker.ability == Ability::VictoryStar, 11, 10);
+    modify(defender.ability == Ability::SandVeil && weather == Some(Weather::Sandstorm), 4, 5);
+    modify(defender.ability == Ability::SnowCloak && weather == Some(Weather::Hail), 4, 5);
+    modify(attacker.pokemon.held_item == Some(Item::WideLens), 11, 10);
+    modify(attacker.pokemon.held_item == Some(Item::ZoomLens) && defender_moved, 6, 5);
+    modify(matches!(defender.pokemon.held_item, Some(Item::BrightPowder) | Some(Item::LaxIncense)), 9, 10);
+
+    HitChance::Percent(chance)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::{battler, move_data};
+    use crate::core::OneOrTwo;
+    use crate::species::Species;
+    use crate::types::Type;
+
+    use super::*;
+
+    fn mon(level: u8) -> Battler {
+        battler(Species::Rattata, OneOrTwo::One(Type::Normal), Ability::RunAway, [50; 6], level, &[Move::Tackle])
+    }
+
+    #[test]
+    fn accuracy_modifiers() {
+        let mut attacker = mon(50);
+        let mut defender = mon(50);
+        let data = move_data(Move::Tackle, Type::Normal, 35, 90, DamageType::Physical);
+
+        assert_eq!(hit_chance(&attacker, &defender, &data, None, false), HitChance::Percent(90));
+        defender.stages.change(BattleStat::Evasion, 1);
+        assert_eq!(hit_chance(&attacker, &defender, &data, None, false), HitChance::Percent(67));
+        defender.identified = true;
+        assert_eq!(hit_chance(&attacker, &defender, &data, None, false), HitChance::Percent(90));
+
+        attacker.stages.change(BattleStat::Accuracy, 2);
+        attacker.pokemon.held_item = Some(Item::WideLens);
+        assert_eq!(hit_chance(&attacker, &defender, &data, None, false), HitChance::Percent(165));
+        attacker.pokemon.held_item = Some(Item::ZoomLens);
+        assert_eq!(hit_chance(&attacker, &defender, &data, None, true), HitChance::Percent(180));
+
+        attacker.ability = Ability::Hustle;
+        attacker.stages = Default::default();
+        attacker.pokemon.held_item = None;
+        defender.pokemon.held_item = Some(Item::BrightPowder);
+        assert_eq!(hit_chance(&attacker, &defender, &data, None, false), HitChance::Percent(64));
+
+        attacker.locked_on = true;
+        assert_eq!(hit_chance(&attacker, &defender, &data, None, false), HitChance::Always);
+    }
+
+    #[test]
+    fn weather_and_ohko() {
+        let attacker = mon(60);
+        let mut defender = mon(50);
+
+        let thunder = move_data(Move::Thunder, Type::Electric, 120, 70, DamageType::Special);
+        assert_eq!(hit_chance(&attacker, &defender, &thunder, Some(Weather::Rain), false), HitChance::Always);
+        assert_eq!(hit_chance(&attacker, &defender, &thunder, Some(Weather::HarshSun), false), HitChance::Percent(50));
+        let blizzard = move_data(Move::Blizzard, Type::Ice, 120, 70, DamageType::Special);
+        assert_eq!(hit_chance(&attacker, &defender, &blizzard, Some(Weather::Hail), false), HitChance::Always);
+
+        let mut fissure = move_data(Move::Fissure, Type::Ground, 0, 30, DamageType::Physical);
+        fissure.power = Power::OneHitKnockout;
+        assert_eq!(hit_chance(&attacker, &defender, &fissure, None, false), HitChance::Percent(40));
+        assert_eq!(hit_chance(&defender, &attacker, &fissure, None, false), HitChance::Never);
+        defender.ability = Ability::Sturdy;
+        assert_eq!(hit_chance(&attacker, &defender, &fissure, None, false), HitChance::Never);
+    }
+}
@@ -0,0 +1,435 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Ability;
+use crate::attack::{Accuracy, BattleStat, DamageType, Effect, Move, MoveData, MultiHitFlavor, NonVolatileBattleAilment, Power, StatChangeTarget, Weather};
+use crate::battle::damage::{self, AttackProfile, Combatant, MAX_DAMAGE_ROLL, MIN_DAMAGE_ROLL};
+use crate::battle::order;
+use crate::battle::{Action, Battle, Battler, MoveDataLookup, SideId, StatStages};
+use crate::core::OneOrTwo;
+use crate::item::Item;
+use crate::pokemon::{NatureBoost, PokemonStatusCondition, StatSlot};
+use crate::species::Species;
+use crate::types::Type;
+
+/// The IV assumed for every stat of an opposing Pokemon
+const ASSUMED_IV: u8 = 15;
+/// The power assumed for an opposing Pokemon's unrevealed same-type attacks
+const ASSUMED_STAB_POWER: u16 = 80;
+/// Options scoring within this amount of the best are considered equally good
+const SCORE_TOLERANCE: f64 = 0.01;
+/// The number of pixels in an HP bar. The AI only sees an opposing Pokemon's HP to this precision.
+const HP_BAR_PIXELS: u16 = 48;
+/// Trainers heal their active Pokemon once its HP falls to this fraction, or lower
+const HEAL_THRESHOLD: f64 = 0.25;
+/// The items trainers heal with, from the most to the least powerful
+const HEALING_ITEMS: [Item; 5] = [Item::FullRestore, Item::MaxPotion, Item::HyperPotion, Item::SuperPotion, Item::Potion];
+
+/// Get how full an HP bar is. Any HP remaining fills at least one pixel.
+fn hp_bar_fraction(current: u16, max: u16) -> f64 {
+    let max = u32::from(max.max(1));
+    let pixels = (u32::from(current) * u32::from(HP_BAR_PIXELS)).div_ceil(max);
+    f64::from(pixels.min(u32::from(HP_BAR_PIXELS))) / f64::from(HP_BAR_PIXELS)
+}
+
+/// Represents how skilled a trainer is at battling
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AiTier {
+    /// Uses any move with PP remaining. Never switches voluntarily.
+    #[default]
+    Random,
+    /// Uses whichever move is expected to do the most damage to the opposing Pokemon
+    Greedy,
+    /// Weighs the threat the opposing Pokemon poses, and may inflict status, boost stats,
+    /// or switch to a better matchup instead of attacking
+    Lookahead
+}
+/// Everything the AI is allowed to know about the opposing Pokemon.
+/// Species, level, types, status, and stat stages are visible to anyone watching. HP is only
+/// known as the fraction shown on the HP bar, and moves are only known once they are used.
+/// Stats are estimated from the species' base stats, assuming an average specimen.
+#[derive(Debug, Clone)]
+pub struct FoeView {
+    pub species: Species,
+    pub level: u8,
+    pub types: OneOrTwo<Type>,
+    pub base_stats: [u8; 6],
+    pub stages: StatStages,
+    pub status: PokemonStatusCondition,
+    pub hp_fraction: f64,
+    pub revealed_moves: Vec<Move>
+}
+impl From<&Battler> for FoeView {
+    fn from(b: &Battler) -> Self {
+        FoeView {
+            species: b.pokemon.species,
+            level: b.pokemon.level,
+            types: b.types,
+            base_stats: b.base_stats,
+            stages: b.stages,
+            status: b.pokemon.status.clone(),
+            hp_fraction: hp_bar_fraction(b.pokemon.current_hp, b.pokemon.hp.value),
+            revealed_moves: b.revealed_moves.clone()
+        }
+    }
+}
+impl FoeView {
+    /// Estimate the speed of the opposing Pokemon, after its stat stages and status are applied.
+    /// Its held item is not known, so it is assumed to hold nothing.
+    pub fn effective_speed(&self) -> u16 {
+        order::modified_speed(self.stat(BattleStat::Speed), None, self.species, self.status.paralysis)
+    }
+}
+impl Combatant for FoeView {
+    fn level(&self) -> u8 { self.level }
+    fn types(&self) -> OneOrTwo<Type> { self.types }
+    fn ability(&self) -> Option<Ability> { None }
+    fn raw_stat(&self, stat: BattleStat) -> u16 {
+        let base = match stat {
+            BattleStat::Attack => self.base_stats[1],
+            BattleStat::Defense => self.base_stats[2],
+            BattleStat::SpecialAttack => self.base_stats[3],
+            BattleStat::SpecialDefense => self.base_stats[4],
+            BattleStat::Speed => self.base_stats[5],
+            BattleStat::Accuracy | BattleStat::Evasion | BattleStat::CriticalHitRatio => return 0
+        };
+        StatSlot::stat(base, self.level, ASSUMED_IV, 0, NatureBoost::Neutral).value
+    }
+    fn stage(&self, stat: BattleStat) -> i8 { self.stages.get(stat) }
+    fn current_hp(&self) -> u16 {
+        (f64::from(self.max_hp()) * self.hp_fraction).round() as u16
+    }
+    fn max_hp(&self) -> u16 {
+        StatSlot::hp(self.base_stats[0], self.level, ASSUMED_IV, 0).value
+    }
+    fn is_burned(&self) -> bool { self.status.burn }
+}
+
+impl AiTier {
+    /// Choose the action a side takes this turn.
+    /// All randomness is drawn from the provided RNG, so a seeded RNG always yields the same choices.
+    pub fn choose_action<R: Rng + ?Sized>(&self, battle: &Battle, side: SideId, moves: &MoveDataLookup, rng: &mut R) -> Action {
+        let own = battle.side(side);
+        let active = own.active();
+        let foe = FoeView::from(battle.side(side.opposite()).active());
+
+        let usable = active.usable_moves();
+        if usable.is_empty() {
+            return Action::Struggle;
+        }
+        let trick_room = battle.trick_room > 0;
+
+        match self {
+            AiTier::Random => Action::Fight(*usable.choose(rng).unwrap()),
+            AiTier::Greedy => {
+                let scored = usable.iter()
+                    .map(|m| (Action::Fight(*m), moves.get(m).map_or(0.0, |d| damage_fraction(active, &foe, d, battle.weather))))
+                    .collect::<Vec<_>>();
+                pick_best(scored, rng)
+            },
+            AiTier::Lookahead => {
+                let scored = usable.iter()
+                    .map(|m| (Action::Fight(*m), moves.get(m).map_or(0.0, |d| score_move(active, &foe, d, battle.weather, trick_room))))
+                    .collect::<Vec<_>>();
+                let best_move = scored.iter()
+                    .map(|(_, score)| *score)
+                    .fold(0.0, f64::max);
+
+                // Only consider leaving the field if the active Pokemon is in real danger
+                let threat = threat_to(active, &foe, moves, battle.weather);
+                let outpaced = !moves_before(active, &foe, trick_room);
+                if threat >= 1.0 && outpaced && best_move < 1.0 {
+                    let current = matchup(active, &foe, moves, battle.weather);
+                    let candidate = own.switch_candidates().into_iter()
+                        .map(|slot| (slot, &own.party[slot]))
+                        .filter(|(_, b)| threat_to(b, &foe, moves, battle.weather) < 0.5)
+                        .map(|(slot, b)| (Action::Switch(slot), matchup(b, &foe, moves, battle.weather)))
+                        .filter(|(_, score)| *score > current + 0.3)
+                        .collect::<Vec<_>>();
+                    if !candidate.is_empty() {
+                        return pick_best(candidate, rng);
+                    }
+                }
+                pick_best(scored, rng)
+            }
+        }
+    }
+
+    /// Choose which party member to send out after the active Pokemon faints.
+    /// Returns None if there is nobody left to send out.
+    pub fn choose_replacement<R: Rng + ?Sized>(&self, battle: &Battle, side: SideId, moves: &MoveDataLookup, rng: &mut R) -> Option<usize> {
+        let own = battle.side(side);
+        let foe = FoeView::from(battle.side(side.opposite()).active());
+        let candidates = own.switch_candidates();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let scored = candidates.into_iter()
+            .map(|slot| {
+                let b = &own.party[slot];
+                let score = match self {
+                    AiTier::Random => 0.0,
+                    AiTier::Greedy => best_damage_fraction(b, &foe, moves, battle.weather),
+                    AiTier::Lookahead => matchup(b, &foe, moves, battle.weather)
+                };
+                (slot, score)
+            })
+            .collect::<Vec<_>>();
+        Some(pick_best(scored, rng))
+    }
+
+    /// Choose the action a trainer takes this turn. Once their active Pokemon is low on HP, the trainer
+    /// heals it with the best healing item they have left, which is removed from their items.
+    /// Otherwise, the trainer acts as `choose_action` would.
+    pub fn choose_trainer_action<R: Rng + ?Sized>(&self, battle: &Battle, side: SideId, items: &mut Vec<Item>, moves: &MoveDataLookup, rng: &mut R) -> Action {
+        let own = battle.side(side);
+        let active = own.active();
+        let hp_fraction = f64::from(active.pokemon.current_hp) / f64::from(active.pokemon.hp.value.max(1));
+        if !active.is_fainted() && hp_fraction <= HEAL_THRESHOLD {
+            if let Some(idx) = HEALING_ITEMS.iter().find_map(|item| items.iter().position(|i| i == item)) {
+                return Action::UseItem(items.remove(idx), own.active);
+            }
+        }
+        self.choose_action(battle, side, moves, rng)
+    }
+}
+
+/// Pick the highest-scoring option. Ties are broken randomly.
+fn pick_best<T: Clone, R: Rng + ?Sized>(options: Vec<(T, f64)>, rng: &mut R) -> T {
+    let best = options.iter()
+        .map(|(_, score)| *score)
+        .fold(f64::MIN, f64::max);
+    let best = options.into_iter()
+        .filter(|(_, score)| best - *score <= SCORE_TOLERANCE)
+        .map(|(option, _)| option)
+        .collect::<Vec<_>>();
+    best.choose(rng).cloned().expect("At least one option should be available")
+}
+
+/// The average number of times a move hits
+fn expected_hits(data: &MoveData) -> f64 {
+    match data.power {
+        Power::MultiHit(MultiHitFlavor::Variable(_)) => 3.0,
+        Power::MultiHit(MultiHitFlavor::Fixed(_, hits)) => f64::from(hits),
+        Power::MultiHit(MultiHitFlavor::Accumulating(..)) => 3.0,
+        _ => 1.0
+    }
+}
+
+/// The chance a move hits, ignoring accuracy and evasion stages
+fn hit_chance(data: &MoveData) -> f64 {
+    match data.accuracy {
+        Accuracy::Percentage(p) => f64::from(p.min(100)) / 100.0,
+        Accuracy::AlwaysHits | Accuracy::Variable => 1.0
+    }
+}
+
+/// The expected damage of a move, as a fraction of the defender's remaining HP.
+/// Capped at 1, as any damage past a knockout is wasted.
+fn damage_fraction<A: Combatant + ?Sized, D: Combatant + ?Sized>(attacker: &A, defender: &D, data: &MoveData, weather: Option<Weather>) -> f64 {
+    let low = damage::calculate_move(attacker, defender, data, weather, false, MIN_DAMAGE_ROLL).amount;
+    let high = damage::calculate_move(attacker, defender, data, weather, false, MAX_DAMAGE_ROLL).amount;
+    let average = (f64::from(low) + f64::from(high)) / 2.0 * expected_hits(data) * hit_chance(data);
+    (average / f64::from(defender.current_hp().max(1))).min(1.0)
+}
+
+/// The best expected damage fraction a battler can deal with its moves
+fn best_damage_fraction(b: &Battler, foe: &FoeView, moves: &MoveDataLookup, weather: Option<Weather>) -> f64 {
+    b.usable_moves().iter()
+        .flat_map(|m| moves.get(m))
+        .map(|d| damage_fraction(b, foe, d, weather))
+        .fold(0.0, f64::max)
+}
+
+/// How much of a battler's remaining HP the opposing Pokemon is expected to take in one attack.
+/// Revealed moves are used if possible. Otherwise, the foe is assumed to have a strong attack of each of its types.
+fn threat_to(b: &Battler, foe: &FoeView, moves: &MoveDataLookup, weather: Option<Weather>) -> f64 {
+    let revealed = foe.revealed_moves.iter()
+        .flat_map(|m| moves.get(m))
+        .map(|d| damage_fraction(foe, b, d, weather))
+        .fold(0.0, f64::max);
+
+    let damage_type = if foe.stat(BattleStat::Attack) >= foe.stat(BattleStat::SpecialAttack) {
+        DamageType::Physical
+    } else {
+        DamageType::Special
+    };
+    let types = match foe.types {
+        OneOrTwo::One(t) => vec![t],
+        OneOrTwo::Two(a, b) => vec![a, b]
+    };
+    let assumed = types.into_iter()
+        .map(|_type| {
+            let profile = AttackProfile { _type, power: ASSUMED_STAB_POWER, damage_type, typeless: false };
+            let low = damage::calculate(foe, b, profile, weather, false, MIN_DAMAGE_ROLL).amount;
+            let high = damage::calculate(foe, b, profile, weather, false, MAX_DAMAGE_ROLL).amount;
+            ((f64::from(low) + f64::from(high)) / 2.0 / f64::from(b.pokemon.current_hp.max(1))).min(1.0)
+        })
+        .fold(0.0, f64::max);
+
+    revealed.max(assumed)
+}
+
+/// How favorable it is for a battler to face the opposing Pokemon.
+/// Positive means the battler deals more than it takes.
+fn matchup(b: &Battler, foe: &FoeView, moves: &MoveDataLookup, weather: Option<Weather>) -> f64 {
+    best_damage_fraction(b, foe, moves, weather) - threat_to(b, foe, moves, weather)
+}
+
+/// Check if a Pokemon of some type can never have the provided ailment
+fn is_immune_to_ailment(types: &OneOrTwo<Type>, ailment: &NonVolatileBattleAilment) -> bool {
+    match ailment {
+        NonVolatileBattleAilment::Burn => types.has_type(&Type::Fire),
+        NonVolatileBattleAilment::Freeze => types.has_type(&Type::Ice),
+        NonVolatileBattleAilment::Poison(_) => types.has_type(&Type::Poison) || types.has_type(&Type::Steel),
+        NonVolatileBattleAilment::Paralysis | NonVolatileBattleAilment::Sleep => false
+    }
+}
+
+/// Check if a battler is expected to move before the opposing Pokemon, if both use moves of the same priority.
+/// Speed ties are assumed to go to the foe.
+fn moves_before(b: &Battler, foe: &FoeView, trick_room: bool) -> bool {
+    let (own, theirs) = (order::effective_speed(b), foe.effective_speed());
+    if trick_room { own < theirs } else { own > theirs }
+}
+
+/// Score a move for the lookahead AI.
+/// Damaging moves are scored by the fraction of HP they remove, with a bonus for a knockout before the foe can act.
+/// Status moves are scored by how useful their effects are right now.
+fn score_move(active: &Battler, foe: &FoeView, data: &MoveData, weather: Option<Weather>, trick_room: bool) -> f64 {
+    if data.damage_type != DamageType::Status {
+        let fraction = damage_fraction(active, foe, data, weather);
+        let moves_first = data.priority > 0 || moves_before(active, foe, trick_room);
+        let guaranteed_ko = damage::calculate_move(active, foe, data, weather, false, MIN_DAMAGE_ROLL).amount >= foe.current_hp();
+        return if guaranteed_ko && moves_first { fraction + 1.0 } else { fraction };
+    }
+
+    let hp_fraction = f64::from(active.pokemon.current_hp) / f64::from(active.pokemon.hp.value.max(1));
+    data.effects.iter()
+        .map(|effect| match effect {
+            Effect::NonVolatileStatus(ailment, _, StatChangeTarget::Target)
+                if !foe.status.has_status_condition() && !is_immune_to_ailment(&foe.types, ailment) => {
+                let value = match ailment {
+                    NonVolatileBattleAilment::Sleep => 0.5,
+                    // Slowing the foe down only helps outside of Trick Room
+                    NonVolatileBattleAilment::Paralysis if !trick_room && !moves_before(active, foe, false) => 0.45,
+                    _ => 0.35
+                };
+                value * hit_chance(data)
+            },
+            Effect::StatChange(stat, stages, _, StatChangeTarget::User)
+                if *stages > 0 && hp_fraction > 0.7 && active.stages.get(*stat) < 2 => 0.25,
+            _ => 0.0
+        })
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use crate::battle::replay::BattleRng;
+    use crate::battle::testing::{battler, move_data, pikachu};
+    use crate::battle::Side;
+
+    use super::*;
+
+    fn moves() -> MoveDataLookup {
+        vec![
+            move_data(Move::Tackle, Type::Normal, 40, 100, DamageType::Physical),
+            move_data(Move::Scratch, Type::Normal, 40, 100, DamageType::Physical),
+            move_data(Move::ThunderShock, Type::Electric, 40, 100, DamageType::Special)
+        ].into_iter().map(|m| (m.id, m)).collect()
+    }
+
+    /// A Pikachu facing a Squirtle that its Thunder Shock can just knock out
+    fn battle(moves: &MoveDataLookup) -> Battle {
+        let pikachu = pikachu(50, &[Move::Tackle, Move::Scratch, Move::ThunderShock]);
+        let mut squirtle = battler(Species::Squirtle, OneOrTwo::One(Type::Water), Ability::Torrent, [44, 48, 65, 50, 64, 43], 50, &[Move::Tackle]);
+        squirtle.pokemon.current_hp = damage::calculate_move(&pikachu, &squirtle, &moves[&Move::ThunderShock], None, false, MIN_DAMAGE_ROLL).amount;
+        Battle::new(Side::new(vec![pikachu]), Side::new(vec![squirtle]))
+    }
+
+    fn choose(tier: AiTier, battle: &Battle, moves: &MoveDataLookup, seed: u64) -> Action {
+        tier.choose_action(battle, SideId::Player, moves, &mut BattleRng::seed_from_u64(seed))
+    }
+
+    #[test]
+    fn same_seed_same_choice() {
+        let moves = moves();
+        let battle = battle(&moves);
+        for tier in [AiTier::Random, AiTier::Greedy, AiTier::Lookahead] {
+            for seed in 0..20 {
+                assert_eq!(choose(tier, &battle, &moves, seed), choose(tier, &battle, &moves, seed), "{:?} with seed {}", tier, seed);
+            }
+        }
+
+        let random = (0..20)
+            .map(|seed| choose(AiTier::Random, &battle, &moves, seed))
+            .collect::<Vec<_>>();
+        assert!(random.iter().any(|a| *a != random[0]));
+    }
+
+    #[test]
+    fn greedy_takes_the_knockout() {
+        let moves = moves();
+        let battle = battle(&moves);
+        for seed in 0..20 {
+            assert_eq!(choose(AiTier::Greedy, &battle, &moves, seed), Action::Fight(Move::ThunderShock));
+            assert_eq!(choose(AiTier::Lookahead, &battle, &moves, seed), Action::Fight(Move::ThunderShock));
+        }
+    }
+
+    #[test]
+    fn speed_comparison() {
+        let moves = moves();
+        let mut battle = battle(&moves);
+        let foe = |battle: &Battle| FoeView::from(battle.side(SideId::Opponent).active());
+        assert!(moves_before(battle.player.active(), &foe(&battle), false));
+        assert!(!moves_before(battle.player.active(), &foe(&battle), true));
+
+        battle.player.active_mut().pokemon.status.paralysis = true;
+        assert!(!moves_before(battle.player.active(), &foe(&battle), false));
+        assert!(moves_before(battle.player.active(), &foe(&battle), true));
+
+        battle.player.active_mut().pokemon.held_item = Some(Item::ChoiceScarf);
+        battle.player.active_mut().stages.change(BattleStat::Speed, 2);
+        assert!(moves_before(battle.player.active(), &foe(&battle), false));
+
+        battle.opponent.active_mut().pokemon.status.paralysis = true;
+        assert_eq!(foe(&battle).effective_speed(), foe(&battle).stat(BattleStat::Speed) / 4);
+    }
+
+    #[test]
+    fn foe_hp_is_rounded_to_the_hp_bar() {
+        assert_eq!(hp_bar_fraction(0, 300), 0.0);
+        assert_eq!(hp_bar_fraction(1, 300), 1.0 / 48.0);
+        assert_eq!(hp_bar_fraction(150, 300), 0.5);
+        assert_eq!(hp_bar_fraction(152, 300), 25.0 / 48.0);
+        assert_eq!(hp_bar_fraction(300, 300), 1.0);
+
+        let moves = moves();
+        let battle = battle(&moves);
+        let foe = FoeView::from(battle.side(SideId::Opponent).active());
+        assert_eq!((foe.hp_fraction * f64::from(HP_BAR_PIXELS)).fract(), 0.0);
+    }
+
+    #[test]
+    fn trainers_heal_when_low() {
+        let moves = moves();
+        let mut battle = battle(&moves);
+        let mut items = vec![Item::Potion, Item::Leftovers, Item::HyperPotion];
+        let choose = |battle: &Battle, items: &mut Vec<Item>| {
+            AiTier::Greedy.choose_trainer_action(battle, SideId::Opponent, items, &moves, &mut BattleRng::seed_from_u64(0))
+        };
+        assert_eq!(choose(&battle, &mut items), Action::Fight(Move::Tackle));
+
+        battle.opponent.active_mut().pokemon.current_hp = 1;
+        assert_eq!(choose(&battle, &mut items), Action::UseItem(Item::HyperPotion, 0));
+        assert_eq!(choose(&battle, &mut items), Action::UseItem(Item::Potion, 0));
+        assert_eq!(choose(&battle, &mut items), Action::Fight(Move::Tackle));
+        assert_eq!(items, vec![Item::Leftovers]);
+    }
+}
@@ -0,0 +1,96 @@
+use rand::Rng;
+
+use crate::abilities::Ability;
+use crate::attack::{BattleStat, MoveData};
+use crate::battle::Battler;
+use crate::item::Item;
+use crate::species::Species;
+
+/// The chance of a critical hit at each stage, as a fraction.
+/// Any stage past the last uses the last chance.
+const CRITICAL_HIT_CHANCES: [(u32, u32); 5] = [(1, 16), (1, 8), (1, 4), (1, 3), (1, 2)];
+
+/// Get the critical hit stage of a move.
+/// This combines the move's own crit rate, the attacker's critical hit ratio stage (such as from Focus Energy),
+/// its held item, and its ability.
+pub fn stage(attacker: &Battler, data: &MoveData) -> u8 {
+    let item = match (&attacker.pokemon.held_item, attacker.pokemon.species) {
+        (Some(Item::ScopeLens), _) | (Some(Item::RazorClaw), _) => 1,
+        (Some(Item::Leek), Species::Farfetchd) | (Some(Item::LuckyPunch), Species::Chansey) => 2,
+        _ => 0
+    };
+    let ability = if attacker.ability == Ability::SuperLuck { 1 } else { 0 };
+    let ratio = attacker.stages.get(BattleStat::CriticalHitRatio).max(0).unsigned_abs();
+
+    data.crit_rate
+        .saturating_add(ratio)
+        .saturating_add(item)
+        .saturating_add(ability)
+}
+
+/// Get the chance of a critical hit at a stage, as a fraction
+pub fn chance(stage: u8) -> (u32, u32) {
+    let index = usize::from(stage).min(CRITICAL_HIT_CHANCES.len() - 1);
+    CRITICAL_HIT_CHANCES[index]
+}
+
+/// Check if the defender can be hit by a critical hit at all
+pub fn can_be_critical(defender: &Battler) -> bool {
+    !matches!(defender.ability, Ability::BattleArmor | Ability::ShellArmor)
+}
+
+/// Roll whether a move lands a critical hit
+pub fn roll<R: Rng + ?Sized>(attacker: &Battler, defender: &Battler, data: &MoveData, rng: &mut R) -> bool {
+    if !can_be_critical(defender) {
+        return false;
+    }
+    let (numerator, denominator) = chance(stage(attacker, data));
+    rng.gen_ratio(numerator, denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attack::{DamageType, Move};
+    use crate::battle::damage;
+    use crate::battle::testing::{battler, move_data};
+    use crate::core::OneOrTwo;
+    use crate::types::Type;
+
+    use super::*;
+
+    #[test]
+    fn critical_stages() {
+        let mut attacker = battler(Species::Farfetchd, OneOrTwo::Two(Type::Normal, Type::Flying), Ability::KeenEye,
+                                   [52, 65, 55, 58, 62, 60], 50, &[Move::Slash]);
+        let mut slash = move_data(Move::Slash, Type::Normal, 70, 100, DamageType::Physical);
+        slash.crit_rate = 1;
+
+        assert_eq!(stage(&attacker, &slash), 1);
+        attacker.pokemon.held_item = Some(Item::Leek);
+        assert_eq!(stage(&attacker, &slash), 3);
+        attacker.stages.change(BattleStat::CriticalHitRatio, 2);
+        attacker.ability = Ability::SuperLuck;
+        assert_eq!(stage(&attacker, &slash), 6);
+        assert_eq!(chance(6), (1, 2));
+        assert_eq!(chance(0), (1, 16));
+
+        attacker.pokemon.species = Species::Chansey;
+        assert_eq!(stage(&attacker, &slash), 4);
+
+        let mut defender = attacker.clone();
+        defender.ability = Ability::ShellArmor;
+        assert!(!can_be_critical(&defender));
+    }
+
+    #[test]
+    fn critical_hits_ignore_stages() {
+        let attacker = battler(Species::Rattata, OneOrTwo::One(Type::Normal), Ability::RunAway, [30, 56, 35, 25, 35, 72], 50, &[Move::Tackle]);
+        let mut defender = attacker.clone();
+        let tackle = move_data(Move::Tackle, Type::Normal, 50, 100, DamageType::Physical);
+
+        let unboosted = damage::calculate_move(&attacker, &defender, &tackle, None, true, 100);
+        defender.stages.change(BattleStat::Defense, 6);
+        assert_eq!(damage::calculate_move(&attacker, &defender, &tackle, None, true, 100), unboosted);
+        assert!(damage::calculate_move(&attacker, &defender, &tackle, None, false, 100).amount < unboosted.amount);
+    }
+}
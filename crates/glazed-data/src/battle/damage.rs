@@ -0,0 +1,261 @@
+use rand::Rng;
+
+use crate::abilities::Ability;
+use crate::attack::{BattleStat, DamageType, Move, MoveData, Power, Weather};
+use crate::battle::{Battler, StatStages};
+use crate::core::OneOrTwo;
+use crate::friendship;
+use crate::types::{Effectiveness, Type};
+
+/// The lowest random roll applied to damage, as a percentage
+pub const MIN_DAMAGE_ROLL: u8 = 85;
+/// The highest random roll applied to damage, as a percentage
+pub const MAX_DAMAGE_ROLL: u8 = 100;
+
+/// Anything the damage calculator can read battle values from.
+/// Implemented by battlers themselves, and by views which only expose what
+/// an opponent is able to see.
+pub trait Combatant {
+    fn level(&self) -> u8;
+    fn types(&self) -> OneOrTwo<Type>;
+    /// The ability of this combatant, if it is known
+    fn ability(&self) -> Option<Ability>;
+    /// The value of a stat, before stat stages are applied
+    fn raw_stat(&self, stat: BattleStat) -> u16;
+    /// The stat stage of a stat
+    fn stage(&self, stat: BattleStat) -> i8;
+    /// The value of a stat, with stat stages applied
+    fn stat(&self, stat: BattleStat) -> u16 {
+        StatStages::apply(self.raw_stat(stat), self.stage(stat))
+    }
+    fn current_hp(&self) -> u16;
+    fn max_hp(&self) -> u16;
+    fn is_burned(&self) -> bool;
+    /// The type and power of this combatant's Hidden Power, if it is known
+    fn hidden_power(&self) -> Option<(Type, u16)> {
+        None
+    }
+    /// The friendship of this combatant, if it is known
+    fn friendship(&self) -> Option<u8> {
+        None
+    }
+}
+
+impl Combatant for Battler {
+    fn level(&self) -> u8 { self.pokemon.level }
+    fn types(&self) -> OneOrTwo<Type> { self.types }
+    fn ability(&self) -> Option<Ability> { Some(self.ability) }
+    fn raw_stat(&self, stat: BattleStat) -> u16 { Battler::raw_stat(self, stat) }
+    fn stage(&self, stat: BattleStat) -> i8 { self.stages.get(stat) }
+    fn current_hp(&self) -> u16 { self.pokemon.current_hp }
+    fn max_hp(&self) -> u16 { self.pokemon.hp.value }
+    fn is_burned(&self) -> bool { self.pokemon.status.burn }
+    fn hidden_power(&self) -> Option<(Type, u16)> {
+        Some((self.pokemon.get_hidden_power_type(), self.pokemon.get_hidden_power_power()))
+    }
+    fn friendship(&self) -> Option<u8> { Some(self.pokemon.friendship) }
+}
+
+/// The result of a damage calculation
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Damage {
+    pub amount: u16,
+    pub effectiveness: Effectiveness
+}
+
+/// Everything about an attack which affects the damage formula
+#[derive(Debug, Copy, Clone)]
+pub struct AttackProfile {
+    pub _type: Type,
+    pub power: u16,
+    pub damage_type: DamageType,
+    /// Typeless attacks, like Struggle, ignore their type: they are always neutral, and never get STAB
+    pub typeless: bool
+}
+impl AttackProfile {
+    /// Determine the type and power of a move, as used by a specific attacker.
+    /// Returns None if the move's power cannot be determined ahead of time.
+    pub fn of<C: Combatant + ?Sized>(attacker: &C, data: &MoveData) -> Option<AttackProfile> {
+        let (_type, power) = match (data.id, attacker.hidden_power(), attacker.friendship()) {
+            (Move::HiddenPower, Some((t, p)), _) => (t, p),
+            (Move::Return, _, Some(f)) => (data._type, friendship::return_power(f)),
+            (Move::Frustration, _, Some(f)) => (data._type, friendship::frustration_power(f)),
+            _ => (data._type, u16::from(data.power.base_power()?))
+        };
+        Some(AttackProfile { _type, power, damage_type: data.damage_type, typeless: false })
+    }
+}
+
+/// Determine how effective an attack of a given type is against a defender
+pub fn effectiveness<D: Combatant + ?Sized>(defender: &D, attack_type: Type) -> Effectiveness {
+    match (defender.ability(), attack_type) {
+        (Some(Ability::Levitate), Type::Ground) => Effectiveness::Immune,
+        _ => defender.types().defending_against(&attack_type)
+    }
+}
+
+/// Calculate the damage one attack does, using a specific random roll.
+/// The roll is a percentage between MIN_DAMAGE_ROLL and MAX_DAMAGE_ROLL.
+pub fn calculate<A, D>(attacker: &A, defender: &D, profile: AttackProfile, weather: Option<Weather>, critical: bool, roll: u8) -> Damage
+    where A: Combatant + ?Sized, D: Combatant + ?Sized {
+    let effectiveness = if profile.typeless { Effectiveness::NORMAL } else { effectiveness(defender, profile._type) };
+    if effectiveness.is_immune() || profile.damage_type == DamageType::Status || profile.power == 0 {
+        return Damage { amount: 0, effectiveness };
+    }
+
+    let (attack_stat, defense_stat) = match profile.damage_type {
+        DamageType::Physical => (BattleStat::Attack, BattleStat::Defense),
+        _ => (BattleStat::SpecialAttack, BattleStat::SpecialDefense)
+    };
+    // Critical hits ignore the attacker's lowered attack, and the defender's raised defense
+    let (attack, defense) = if critical {
+        (StatStages::apply(attacker.raw_stat(attack_stat), attacker.stage(attack_stat).max(0)),
+         StatStages::apply(defender.raw_stat(defense_stat), defender.stage(defense_stat).min(0)))
+    } else {
+        (attacker.stat(attack_stat), defender.stat(defense_stat))
+    };
+    let attack = u32::from(attack);
+    let defense = u32::from(defense).max(1);
+    let level = u32::from(attacker.level());
+
+    let base = ((2 * level / 5 + 2) * u32::from(profile.power) * attack / defense) / 50 + 2;
+
+    let damage = match (weather, profile._type) {
+        (Some(Weather::Rain), Type::Water) | (Some(Weather::HarshSun), Type::Fire) => base * 3 / 2,
+        (Some(Weather::Rain), Type::Fire) | (Some(Weather::HarshSun), Type::Water) => base / 2,
+        _ => base
+    };
+    let damage = match (critical, attacker.ability()) {
+        (true, Some(Ability::Sniper)) => damage * 3,
+        (true, _) => damage * 2,
+        (false, _) => damage
+    };
+    let damage = damage * u32::from(roll.clamp(MIN_DAMAGE_ROLL, MAX_DAMAGE_ROLL)) / 100;
+    let damage = if !profile.typeless && attacker.types().is_stab(&profile._type) {
+        match attacker.ability() {
+            Some(Ability::Adaptability) => damage * 2,
+            _ => damage * 3 / 2
+        }
+    } else {
+        damage
+    };
+    let damage = match effectiveness {
+        Effectiveness::Effect(power) if power > 0 => damage << power,
+        Effectiveness::Effect(power) => damage >> -power,
+        Effectiveness::Immune => 0
+    };
+    let damage = if profile.damage_type == DamageType::Physical && attacker.is_burned() && attacker.ability() != Some(Ability::Guts) {
+        damage / 2
+    } else {
+        damage
+    };
+
+    Damage {
+        amount: damage.clamp(1, u32::from(u16::MAX)) as u16,
+        effectiveness
+    }
+}
+
+/// Calculate the damage of a move, using a specific random roll.
+/// Moves which deal a set amount of damage are handled here, as they ignore the damage formula.
+pub fn calculate_move<A, D>(attacker: &A, defender: &D, data: &MoveData, weather: Option<Weather>, critical: bool, roll: u8) -> Damage
+    where A: Combatant + ?Sized, D: Combatant + ?Sized {
+    let effectiveness = effectiveness(defender, data._type);
+    let fixed = match data.power {
+        Power::Exact(amount) => Some(u16::from(amount)),
+        Power::Percentage((numerator, denominator)) => {
+            let hp = u32::from(defender.current_hp());
+            Some((hp * u32::from(numerator) / u32::from(denominator.max(1))).max(1) as u16)
+        },
+        Power::OneHitKnockout => Some(defender.current_hp()),
+        _ => None
+    };
+
+    if data.damage_type == DamageType::Status {
+        return Damage { amount: 0, effectiveness };
+    }
+
+    match fixed {
+        Some(_) if effectiveness.is_immune() => Damage { amount: 0, effectiveness },
+        Some(amount) => Damage { amount, effectiveness: effectiveness.dont_care() },
+        None => match AttackProfile::of(attacker, data) {
+            Some(profile) => calculate(attacker, defender, profile, weather, critical, roll),
+            None => Damage { amount: 0, effectiveness }
+        }
+    }
+}
+
+/// Calculate the damage of a move, using a random roll
+pub fn roll_move<A, D, R>(attacker: &A, defender: &D, data: &MoveData, weather: Option<Weather>, critical: bool, rng: &mut R) -> Damage
+    where A: Combatant + ?Sized, D: Combatant + ?Sized, R: Rng + ?Sized {
+    let roll = rng.gen_range(MIN_DAMAGE_ROLL..=MAX_DAMAGE_ROLL);
+    calculate_move(attacker, defender, data, weather, critical, roll)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use crate::battle::replay::BattleRng;
+    use crate::battle::testing::{battler, move_data};
+    use crate::species::Species;
+
+    use super::*;
+
+    fn mon(types: OneOrTwo<Type>) -> Battler {
+        battler(Species::Rattata, types, Ability::RunAway, [50, 50, 50, 50, 50, 50], 50, &[])
+    }
+
+    fn hit(attacker: &Battler, defender: &Battler, _type: Type, roll: u8) -> Damage {
+        calculate(attacker, defender, AttackProfile { _type, power: 60, damage_type: DamageType::Special, typeless: false }, None, false, roll)
+    }
+
+    #[test]
+    fn stab() {
+        let normal = mon(OneOrTwo::One(Type::Normal));
+        let fire = mon(OneOrTwo::One(Type::Fire));
+        let target = mon(OneOrTwo::One(Type::Normal));
+        let plain = hit(&normal, &target, Type::Fire, MAX_DAMAGE_ROLL).amount;
+        assert_eq!(hit(&fire, &target, Type::Fire, MAX_DAMAGE_ROLL).amount, plain * 3 / 2);
+    }
+
+    #[test]
+    fn type_effectiveness() {
+        let attacker = mon(OneOrTwo::One(Type::Normal));
+        let plain = hit(&attacker, &mon(OneOrTwo::One(Type::Normal)), Type::Water, MAX_DAMAGE_ROLL);
+        assert_eq!(plain.effectiveness, Effectiveness::NORMAL);
+
+        let double = hit(&attacker, &mon(OneOrTwo::One(Type::Fire)), Type::Water, MAX_DAMAGE_ROLL);
+        assert_eq!(double, Damage { amount: plain.amount * 2, effectiveness: Effectiveness::DOUBLE });
+
+        let quadruple = hit(&attacker, &mon(OneOrTwo::Two(Type::Fire, Type::Rock)), Type::Water, MAX_DAMAGE_ROLL);
+        assert_eq!(quadruple, Damage { amount: plain.amount * 4, effectiveness: Effectiveness::Effect(2) });
+
+        let half = hit(&attacker, &mon(OneOrTwo::One(Type::Grass)), Type::Water, MAX_DAMAGE_ROLL);
+        assert_eq!(half, Damage { amount: plain.amount / 2, effectiveness: Effectiveness::HALF });
+
+        let immune = hit(&attacker, &mon(OneOrTwo::One(Type::Ghost)), Type::Normal, MAX_DAMAGE_ROLL);
+        assert_eq!(immune, Damage { amount: 0, effectiveness: Effectiveness::Immune });
+    }
+
+    #[test]
+    fn random_roll_bounds() {
+        let attacker = mon(OneOrTwo::One(Type::Normal));
+        let defender = mon(OneOrTwo::One(Type::Normal));
+        let data = move_data(Move::Tackle, Type::Normal, 40, 100, DamageType::Physical);
+        let low = calculate_move(&attacker, &defender, &data, None, false, MIN_DAMAGE_ROLL).amount;
+        let high = calculate_move(&attacker, &defender, &data, None, false, MAX_DAMAGE_ROLL).amount;
+        assert!(low < high);
+
+        // Rolls outside the range are clamped to it
+        assert_eq!(calculate_move(&attacker, &defender, &data, None, false, 0).amount, low);
+        assert_eq!(calculate_move(&attacker, &defender, &data, None, false, u8::MAX).amount, high);
+
+        let mut rng = BattleRng::seed_from_u64(0);
+        let rolled = (0..200)
+            .map(|_| roll_move(&attacker, &defender, &data, None, false, &mut rng).amount)
+            .collect::<Vec<_>>();
+        assert!(rolled.iter().all(|amount| (low..=high).contains(amount)));
+        assert!(rolled.contains(&low) && rolled.contains(&high));
+    }
+}
@@ -0,0 +1,602 @@
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::attack::{BattleStat, DamageType, Effect, Move, MoveData, MultiHitFlavor, NonVolatileBattleAilment, Power, ScreenType, StatChangeTarget, Target, Weather};
+use crate::battle::damage::{self, AttackProfile};
+use crate::battle::{accuracy, critical, order};
+use crate::forms::{self, FormTrigger};
+use crate::pokemon::{self, SpeciesData};
+use crate::species::Species;
+use crate::battle::{Action, Battle, MoveDataLookup, SideId};
+use crate::item::Item;
+use crate::types::{Effectiveness, Type};
+
+/// The number of turns weather lasts, when summoned by a move
+pub const WEATHER_DURATION: u8 = 5;
+/// The number of turns Reflect and Light Screen last
+pub const SCREEN_DURATION: u8 = 5;
+/// The number of turns Reflect and Light Screen last, when the user holds a Light Clay
+pub const LIGHT_CLAY_SCREEN_DURATION: u8 = 8;
+/// The number of turns Trick Room lasts
+pub const TRICK_ROOM_DURATION: u8 = 5;
+/// The base power of Struggle
+const STRUGGLE_POWER: u16 = 50;
+
+/// The reason a Pokemon was unable to use its move
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImmobileReason {
+    Asleep,
+    Frozen,
+    Paralyzed,
+    Flinched
+}
+
+/// Something which happened during a turn.
+/// These are produced in the order they occurred, so the UI can play them back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BattleEvent {
+    MoveUsed { side: SideId, attack: Move },
+    Struggled(SideId),
+    /// The move has no battle data, so it did nothing
+    MoveFailed(SideId),
+    Missed(SideId),
+    Immobilized { side: SideId, reason: ImmobileReason },
+    WokeUp(SideId),
+    Thawed(SideId),
+    /// Damage from an attack
+    Damaged { side: SideId, amount: u16, effectiveness: Effectiveness, critical: bool },
+    /// Damage from anything other than an attack, such as recoil, status, or weather
+    Hurt { side: SideId, amount: u16 },
+    Healed { side: SideId, amount: u16 },
+    StatChanged { side: SideId, stat: BattleStat, delta: i8 },
+    StatusInflicted { side: SideId, ailment: NonVolatileBattleAilment },
+    WeatherChanged(Option<Weather>),
+    /// A screen went up, or wore off, on this side
+    ScreenChanged { side: SideId, screen: ScreenType, active: bool },
+    /// The Pokemon used Lock-On, so its next move will hit
+    LockedOn(SideId),
+    /// The Pokemon was identified by Foresight, so raised evasion no longer helps it
+    Identified(SideId),
+    /// The Pokemon changed into another form
+    FormChanged { side: SideId, form: Species },
+    /// Trick Room started, or ended
+    TrickRoom(bool),
+    Switched { side: SideId, slot: usize },
+    /// An item was used. Its effects are applied by whoever owns the bag.
+    ItemUsed { side: SideId, item: Item, slot: usize },
+    Fainted { side: SideId, slot: usize },
+    Fled(SideId)
+}
+
+/// Tracks what has happened so far in the current turn
+#[derive(Debug, Default)]
+struct TurnState {
+    /// Sides which will flinch if they have not yet moved
+    flinched: Vec<SideId>,
+    /// Sides which have attempted a move
+    acted: Vec<SideId>
+}
+
+impl Battle {
+    /// Play out one turn, given the action each side chose.
+    /// All randomness is drawn from the provided RNG, so a battle played with the same seed
+    /// and the same actions always plays out identically.
+    pub fn play_turn<R: Rng + ?Sized>(&mut self, player: Action, opponent: Action, moves: &MoveDataLookup, rng: &mut R) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        let mut state = TurnState::default();
+        self.record_matchup();
+
+        let actions = vec![(SideId::Player, player), (SideId::Opponent, opponent)];
+        for (side, action) in order::resolve(self, actions, moves, rng) {
+            if self.is_over() {
+                break;
+            }
+            match action {
+                Action::Fight(attack) => {
+                    if self.side(side).active().is_fainted() { continue; }
+                    self.use_move(side, Some(attack), moves, &mut state, &mut events, rng);
+                },
+                Action::Struggle => {
+                    if self.side(side).active().is_fainted() { continue; }
+                    self.use_move(side, None, moves, &mut state, &mut events, rng);
+                },
+                Action::Switch(slot) => events.extend(self.replace(side, slot)),
+                Action::UseItem(item, slot) => events.push(BattleEvent::ItemUsed { side, item, slot }),
+                Action::Run => {
+                    self.fled = Some(side);
+                    events.push(BattleEvent::Fled(side));
+                }
+            }
+        }
+
+        if !self.is_over() {
+            self.end_of_turn(&mut events);
+        }
+        self.turn += 1;
+        events
+    }
+
+    /// Send out the party member in the slot, replacing the active Pokemon.
+    /// Does nothing if that party member cannot battle.
+    pub fn replace(&mut self, side: SideId, slot: usize) -> Vec<BattleEvent> {
+        let s = self.side_mut(side);
+        if !s.can_switch_to(slot) {
+            return Vec::new();
+        }
+        s.active_mut().switch_out();
+        s.active = slot;
+        self.record_matchup();
+        vec![BattleEvent::Switched { side, slot }]
+    }
+
+    /// Note that the player's active Pokemon has been sent out against the opponent's,
+    /// so it will share in the EVs if the opponent's faints
+    fn record_matchup(&mut self) {
+        let slot = self.player.active;
+        let foe = self.opponent.active_mut();
+        if !foe.faced_by.contains(&slot) {
+            foe.faced_by.push(slot);
+        }
+    }
+
+    /// Give the EVs of the opponent's fainted Pokemon to every Pokemon which battled it, and is still standing
+    fn award_evs(&mut self) {
+        let foe = self.opponent.active();
+        let yields = foe.ev_yield;
+        for slot in foe.faced_by.clone() {
+            match self.player.party.get_mut(slot) {
+                Some(b) if !b.is_fainted() => { b.pokemon.gain_evs(yields); },
+                _ => {}
+            }
+        }
+    }
+
+    /// Change the form of any active Pokemon whose form depends on what happened this turn,
+    /// such as Castform in new weather, or Darmanitan at low HP.
+    /// Forms without species data are skipped.
+    pub fn update_forms(&mut self, turn: &[BattleEvent], species: &HashMap<Species, SpeciesData>) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        for side in [SideId::Player, SideId::Opponent] {
+            let mut triggers = vec![FormTrigger::Weather(self.weather), FormTrigger::EndOfTurn];
+            for event in turn {
+                match event {
+                    BattleEvent::MoveUsed { side: s, attack: Move::RelicSong } if *s == side => triggers.push(FormTrigger::RelicSong),
+                    BattleEvent::StatusInflicted { side: s, ailment: NonVolatileBattleAilment::Freeze } if *s == side => triggers.push(FormTrigger::Frozen),
+                    _ => {}
+                }
+            }
+
+            for trigger in triggers {
+                let battler = self.side(side).active();
+                if battler.is_fainted() { break; }
+                let form = match forms::resulting_form(&battler.pokemon, battler.ability, trigger) {
+                    Some(f) => f,
+                    None => continue
+                };
+                if let Some(data) = pokemon::species_data(species, form) {
+                    self.side_mut(side).active_mut().change_form(form, data);
+                    events.push(BattleEvent::FormChanged { side, form });
+                }
+            }
+        }
+        events
+    }
+
+    /// Check if a side's active Pokemon fainted, and there is someone to replace it
+    pub fn needs_replacement(&self, side: SideId) -> bool {
+        let s = self.side(side);
+        s.active().is_fainted() && !s.switch_candidates().is_empty()
+    }
+
+    /// Check if the active Pokemon can act this turn, updating its sleep and freeze status
+    fn can_move<R: Rng + ?Sized>(&mut self, side: SideId, state: &TurnState, events: &mut Vec<BattleEvent>, rng: &mut R) -> bool {
+        let status = &mut self.side_mut(side).active_mut().pokemon.status;
+        if status.sleep > 0 {
+            status.sleep -= 1;
+            if status.sleep > 0 {
+                events.push(BattleEvent::Immobilized { side, reason: ImmobileReason::Asleep });
+                return false;
+            }
+            events.push(BattleEvent::WokeUp(side));
+        }
+        if status.freeze {
+            if rng.gen_ratio(1, 5) {
+                status.freeze = false;
+                events.push(BattleEvent::Thawed(side));
+            } else {
+                events.push(BattleEvent::Immobilized { side, reason: ImmobileReason::Frozen });
+                return false;
+            }
+        }
+        if state.flinched.contains(&side) {
+            events.push(BattleEvent::Immobilized { side, reason: ImmobileReason::Flinched });
+            return false;
+        }
+        if status.paralysis && rng.gen_ratio(1, 4) {
+            events.push(BattleEvent::Immobilized { side, reason: ImmobileReason::Paralyzed });
+            return false;
+        }
+        true
+    }
+
+    /// Use a move. A move of None means Struggle.
+    fn use_move<R: Rng + ?Sized>(&mut self, side: SideId, attack: Option<Move>, moves: &MoveDataLookup, state: &mut TurnState, events: &mut Vec<BattleEvent>, rng: &mut R) {
+        let target_moved = state.acted.contains(&side.opposite());
+        state.acted.push(side);
+        if !self.can_move(side, state, events, rng) {
+            return;
+        }
+        let target = side.opposite();
+
+        let attack = match attack {
+            Some(a) => a,
+            None => {
+                events.push(BattleEvent::Struggled(side));
+                let profile = AttackProfile { _type: Type::Normal, power: STRUGGLE_POWER, damage_type: DamageType::Physical, typeless: true };
+                let roll = rng.gen_range(damage::MIN_DAMAGE_ROLL..=damage::MAX_DAMAGE_ROLL);
+                let result = damage::calculate(self.side(side).active(), self.side(target).active(), profile, self.weather, false, roll);
+                self.deal_damage(target, result.amount, result.effectiveness, false, events);
+                let recoil = self.side(side).active().pokemon.hp.value / 4;
+                self.hurt(side, recoil.max(1), events);
+                return;
+            }
+        };
+
+        let user = self.side_mut(side).active_mut();
+        if let Some(slot) = user.pokemon.get_move_slot_mut(attack) {
+            slot.pp = slot.pp.saturating_sub(1);
+        }
+        user.reveal_move(attack);
+        events.push(BattleEvent::MoveUsed { side, attack });
+
+        let data = match moves.get(&attack) {
+            Some(d) => d,
+            None => {
+                events.push(BattleEvent::MoveFailed(side));
+                return;
+            }
+        };
+
+        let targets_self = matches!(data.target, Target::User | Target::UserAndAlly | Target::UserOrAlly | Target::Ally);
+        let hit = targets_self || accuracy::hit_chance(self.side(side).active(), self.side(target).active(), data, self.weather, target_moved).roll(rng);
+        // Lock-On only guarantees the move directly after it
+        self.side_mut(side).active_mut().locked_on = false;
+        if !hit {
+            events.push(BattleEvent::Missed(side));
+            if let Power::BaseWithCrash(_) = data.power {
+                let crash = self.side(side).active().pokemon.hp.value / 2;
+                self.hurt(side, crash, events);
+            }
+            return;
+        }
+
+        let mut immune = false;
+        if data.damage_type != DamageType::Status {
+            let mut dealt = 0u16;
+            for _ in 0..Self::roll_hits(data, rng) {
+                let critical = critical::roll(self.side(side).active(), self.side(target).active(), data, rng);
+                let result = damage::roll_move(self.side(side).active(), self.side(target).active(), data, self.weather, critical, rng);
+                immune = result.effectiveness.is_immune();
+                if immune { break; }
+                // Critical hits break through screens, and damage set by the move ignores them
+                let amount = if !critical && !data.power.is_fixed_damage() && self.side(target).is_screened(data.damage_type) {
+                    (result.amount / 2).max(1)
+                } else {
+                    result.amount
+                };
+                dealt = dealt.saturating_add(self.deal_damage(target, amount, result.effectiveness, critical, events));
+                if self.side(target).active().is_fainted() { break; }
+            }
+
+            match data.power {
+                Power::BaseWithRecoil(_, (numerator, denominator)) if dealt > 0 => {
+                    let recoil = u32::from(dealt) * u32::from(numerator) / u32::from(denominator.max(1));
+                    self.hurt(side, (recoil as u16).max(1), events);
+                },
+                Power::BaseWithDrain(_) if dealt > 0 => self.heal(side, (dealt / 2).max(1), events),
+                Power::BaseWithFaint(_) => {
+                    let hp = self.side(side).active().pokemon.current_hp;
+                    self.hurt(side, hp, events);
+                }
+                _ => {}
+            }
+        }
+
+        if !immune {
+            for effect in data.effects.iter() {
+                self.apply_effect(side, effect, state, events, rng);
+            }
+        }
+
+        self.check_faint(target, events);
+        self.check_faint(side, events);
+    }
+
+    /// Roll how many times a move strikes
+    fn roll_hits<R: Rng + ?Sized>(data: &MoveData, rng: &mut R) -> u8 {
+        match data.power {
+            Power::MultiHit(MultiHitFlavor::Variable(_)) => match rng.gen_range(0..6) {
+                0 | 1 => 2,
+                2 | 3 => 3,
+                4 => 4,
+                _ => 5
+            },
+            Power::MultiHit(MultiHitFlavor::Fixed(_, hits)) => hits,
+            Power::MultiHit(MultiHitFlavor::Accumulating(..)) => 3,
+            _ => 1
+        }
+    }
+
+    /// Roll a percentage chance. A chance of 0 is treated as always happening.
+    fn roll_chance<R: Rng + ?Sized>(chance: u8, rng: &mut R) -> bool {
+        chance == 0 || chance >= 100 || rng.gen_range(0..100) < chance
+    }
+
+    fn apply_effect<R: Rng + ?Sized>(&mut self, side: SideId, effect: &Effect, state: &mut TurnState, events: &mut Vec<BattleEvent>, rng: &mut R) {
+        let affected = |target: &StatChangeTarget| match target {
+            StatChangeTarget::User => side,
+            StatChangeTarget::Target => side.opposite()
+        };
+        match effect {
+            Effect::StatChange(stat, delta, chance, target) => {
+                let affected = affected(target);
+                if self.side(affected).active().is_fainted() || !Self::roll_chance(*chance, rng) { return; }
+                let delta = self.side_mut(affected).active_mut().stages.change(*stat, *delta);
+                events.push(BattleEvent::StatChanged { side: affected, stat: *stat, delta });
+            },
+            Effect::NonVolatileStatus(ailment, chance, target) => {
+                let affected = affected(target);
+                if self.side(affected).active().is_fainted() || !Self::roll_chance(*chance, rng) { return; }
+                if self.inflict(affected, *ailment, rng) {
+                    events.push(BattleEvent::StatusInflicted { side: affected, ailment: *ailment });
+                }
+            },
+            Effect::Flinch(chance) => {
+                if Self::roll_chance(*chance, rng) {
+                    state.flinched.push(side.opposite());
+                }
+            },
+            Effect::Heal(percent) => {
+                let max = u32::from(self.side(side).active().pokemon.hp.value);
+                let amount = (max * u32::from(*percent) / 100) as u16;
+                self.heal(side, amount, events);
+            },
+            Effect::ChangeWeather(weather) if self.weather != Some(*weather) => {
+                self.weather = Some(*weather);
+                self.weather_turns = WEATHER_DURATION;
+                events.push(BattleEvent::WeatherChanged(Some(*weather)));
+            },
+            Effect::Screen(screen) if *self.side_mut(side).screen_mut(*screen) == 0 => {
+                let duration = match self.side(side).active().pokemon.held_item {
+                    Some(Item::LightClay) => LIGHT_CLAY_SCREEN_DURATION,
+                    _ => SCREEN_DURATION
+                };
+                *self.side_mut(side).screen_mut(*screen) = duration;
+                events.push(BattleEvent::ScreenChanged { side, screen: *screen, active: true });
+            },
+            Effect::LockOn => {
+                self.side_mut(side).active_mut().locked_on = true;
+                events.push(BattleEvent::LockedOn(side));
+            },
+            Effect::Foresight => {
+                self.side_mut(side.opposite()).active_mut().identified = true;
+                events.push(BattleEvent::Identified(side.opposite()));
+            },
+            Effect::TrickRoom => {
+                // Using Trick Room while it is active ends it early
+                self.trick_room = if self.trick_room > 0 { 0 } else { TRICK_ROOM_DURATION };
+                events.push(BattleEvent::TrickRoom(self.trick_room > 0));
+            },
+            _ => {}
+        }
+    }
+
+    /// Attempt to give the active Pokemon a status condition.
+    /// Fails if it already has one, or its type makes it immune.
+    fn inflict<R: Rng + ?Sized>(&mut self, side: SideId, ailment: NonVolatileBattleAilment, rng: &mut R) -> bool {
+        let battler = self.side_mut(side).active_mut();
+        let types = battler.types;
+        let status = &mut battler.pokemon.status;
+        if status.has_status_condition() {
+            return false;
+        }
+        match ailment {
+            NonVolatileBattleAilment::Burn if !types.has_type(&Type::Fire) => status.burn = true,
+            NonVolatileBattleAilment::Freeze if !types.has_type(&Type::Ice) => status.freeze = true,
+            NonVolatileBattleAilment::Paralysis => status.paralysis = true,
+            NonVolatileBattleAilment::Sleep => status.sleep = rng.gen_range(2..=4),
+            NonVolatileBattleAilment::Poison(_) if !types.has_type(&Type::Poison) && !types.has_type(&Type::Steel) => status.poison = true,
+            _ => return false
+        }
+        true
+    }
+
+    /// Deal damage from an attack. Returns the amount of HP actually lost.
+    fn deal_damage(&mut self, side: SideId, amount: u16, effectiveness: Effectiveness, critical: bool, events: &mut Vec<BattleEvent>) -> u16 {
+        let (start, end) = self.side_mut(side).active_mut().pokemon.subtract_hp(amount);
+        events.push(BattleEvent::Damaged { side, amount: start - end, effectiveness, critical });
+        start - end
+    }
+
+    /// Deal damage from something other than an attack
+    fn hurt(&mut self, side: SideId, amount: u16, events: &mut Vec<BattleEvent>) {
+        let (start, end) = self.side_mut(side).active_mut().pokemon.subtract_hp(amount);
+        if start != end {
+            events.push(BattleEvent::Hurt { side, amount: start - end });
+        }
+    }
+
+    fn heal(&mut self, side: SideId, amount: u16, events: &mut Vec<BattleEvent>) {
+        let (start, end) = self.side_mut(side).active_mut().pokemon.add_hp(amount);
+        if start != end {
+            events.push(BattleEvent::Healed { side, amount: end - start });
+        }
+    }
+
+    fn check_faint(&mut self, side: SideId, events: &mut Vec<BattleEvent>) {
+        let s = self.side(side);
+        let fainted = BattleEvent::Fainted { side, slot: s.active };
+        if s.active().is_fainted() && !events.contains(&fainted) {
+            events.push(fainted);
+            if side == SideId::Opponent {
+                self.award_evs();
+            }
+        }
+    }
+
+    /// Apply weather and status damage, and count down the weather
+    fn end_of_turn(&mut self, events: &mut Vec<BattleEvent>) {
+        for side in [SideId::Player, SideId::Opponent] {
+            let battler = self.side(side).active();
+            if battler.is_fainted() { continue; }
+            let max = battler.pokemon.hp.value;
+
+            let weather_immune = match self.weather {
+                Some(Weather::Sandstorm) => [Type::Rock, Type::Ground, Type::Steel].iter().any(|t| battler.types.has_type(t)),
+                Some(Weather::Hail) => battler.types.has_type(&Type::Ice),
+                _ => true
+            };
+            if !weather_immune {
+                self.hurt(side, (max / 16).max(1), events);
+            }
+
+            let status = &self.side(side).active().pokemon.status;
+            if status.burn || status.poison {
+                self.hurt(side, (max / 8).max(1), events);
+            }
+            self.check_faint(side, events);
+        }
+
+        if self.weather.is_some() && self.weather_turns > 0 {
+            self.weather_turns -= 1;
+            if self.weather_turns == 0 {
+                self.weather = None;
+                events.push(BattleEvent::WeatherChanged(None));
+            }
+        }
+
+        for side in [SideId::Player, SideId::Opponent] {
+            for screen in [ScreenType::Reflect, ScreenType::LightScreen] {
+                let turns = self.side_mut(side).screen_mut(screen);
+                if *turns > 0 {
+                    *turns -= 1;
+                    if *turns == 0 {
+                        events.push(BattleEvent::ScreenChanged { side, screen, active: false });
+                    }
+                }
+            }
+        }
+
+        if self.trick_room > 0 {
+            self.trick_room -= 1;
+            if self.trick_room == 0 {
+                events.push(BattleEvent::TrickRoom(false));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use crate::abilities::Ability;
+    use crate::battle::replay::BattleRng;
+    use crate::battle::testing::{battler, move_data};
+    use crate::battle::Side;
+    use crate::core::OneOrTwo;
+
+    use super::*;
+
+    fn moves() -> MoveDataLookup {
+        let mut seismic_toss = move_data(Move::SeismicToss, Type::Fighting, 0, 100, DamageType::Physical);
+        // Deals damage equal to the user's level
+        seismic_toss.power = Power::Exact(50);
+        let mut super_fang = move_data(Move::SuperFang, Type::Normal, 0, 100, DamageType::Physical);
+        super_fang.power = Power::Percentage((1, 2));
+        let mut splash = move_data(Move::Splash, Type::Normal, 0, 100, DamageType::Status);
+        splash.target = Target::User;
+        vec![seismic_toss, super_fang, splash].into_iter().map(|m| (m.id, m)).collect()
+    }
+
+    /// Have the player use a move on an opponent protected by Reflect, and get the damage dealt
+    fn damage_through_reflect(attack: Move) -> (u16, u16) {
+        let moves = moves();
+        let user = battler(Species::Machop, OneOrTwo::One(Type::Fighting), Ability::Guts, [70, 80, 50, 35, 35, 35], 50, &[Move::SeismicToss, Move::SuperFang]);
+        let target = battler(Species::Snorlax, OneOrTwo::One(Type::Normal), Ability::Immunity, [160, 110, 65, 65, 110, 30], 50, &[Move::Splash]);
+        let mut battle = Battle::new(Side::new(vec![user]), Side::new(vec![target]));
+        battle.opponent.reflect = SCREEN_DURATION;
+        let start = battle.opponent.active().pokemon.current_hp;
+
+        let events = battle.play_turn(Action::Fight(attack), Action::Fight(Move::Splash), &moves, &mut BattleRng::seed_from_u64(0));
+        let dealt = events.iter()
+            .find_map(|e| match e {
+                BattleEvent::Damaged { side: SideId::Opponent, amount, .. } => Some(*amount),
+                _ => None
+            })
+            .expect("The move should hit");
+        (start, dealt)
+    }
+
+    #[test]
+    fn fainted_foes_give_evs() {
+        let mut moves = moves();
+        moves.insert(Move::Tackle, move_data(Move::Tackle, Type::Normal, 250, 100, DamageType::Physical));
+        let mon = |species| battler(species, OneOrTwo::One(Type::Normal), Ability::RunAway, [50; 6], 50, &[Move::Tackle, Move::Splash]);
+        let mut foe = mon(Species::Rattata);
+        foe.ev_yield = [0, 0, 0, 0, 0, 1];
+        let mut battle = Battle::new(Side::new(vec![mon(Species::Rattata), mon(Species::Raticate), mon(Species::Pidgey)]), Side::new(vec![foe.clone(), foe]));
+        battle.player.party[2].pokemon.speed.add_evs(1, 255);
+        battle.player.party[1].pokemon.held_item = Some(Item::MachoBrace);
+        let mut rng = BattleRng::seed_from_u64(0);
+
+        // Both Pokemon which were sent out share the EVs, but only once the foe faints
+        battle.play_turn(Action::Fight(Move::Splash), Action::Fight(Move::Splash), &moves, &mut rng);
+        battle.replace(SideId::Player, 1);
+        assert_eq!(battle.player.party[0].pokemon.speed.ev(), 0);
+        battle.play_turn(Action::Fight(Move::Tackle), Action::Fight(Move::Splash), &moves, &mut rng);
+        assert!(battle.opponent.active().is_fainted());
+        assert_eq!(battle.player.party[0].pokemon.speed.ev(), 1);
+        assert_eq!(battle.player.party[1].pokemon.speed.ev(), 2);
+        assert_eq!(battle.player.party[2].pokemon.speed.ev(), 1);
+
+        // Pokemon which fainted before the foe did gain nothing
+        battle.replace(SideId::Opponent, 1);
+        battle.replace(SideId::Player, 0);
+        battle.replace(SideId::Player, 1);
+        battle.player.party[0].pokemon.current_hp = 0;
+        battle.play_turn(Action::Fight(Move::Tackle), Action::Fight(Move::Splash), &moves, &mut rng);
+        assert_eq!(battle.player.party[0].pokemon.speed.ev(), 1);
+        assert_eq!(battle.player.party[1].pokemon.speed.ev(), 4);
+    }
+
+    #[test]
+    fn screens_ignore_fixed_damage() {
+        assert_eq!(damage_through_reflect(Move::SeismicToss).1, 50);
+        let (start, dealt) = damage_through_reflect(Move::SuperFang);
+        assert_eq!(dealt, start / 2);
+    }
+
+    #[test]
+    fn struggle_is_typeless() {
+        let struggle = |types| {
+            let user = battler(Species::Rattata, types, Ability::RunAway, [50; 6], 50, &[Move::Splash]);
+            let target = battler(Species::Gastly, OneOrTwo::One(Type::Ghost), Ability::Levitate, [50; 6], 50, &[Move::Splash]);
+            let mut battle = Battle::new(Side::new(vec![user]), Side::new(vec![target]));
+            battle.play_turn(Action::Struggle, Action::Fight(Move::Splash), &moves(), &mut BattleRng::seed_from_u64(0))
+                .into_iter()
+                .find_map(|e| match e {
+                    BattleEvent::Damaged { side: SideId::Opponent, amount, effectiveness, .. } => Some((amount, effectiveness)),
+                    _ => None
+                })
+                .expect("Struggle should hit")
+        };
+
+        // Ghosts are not immune, and Normal types get no STAB
+        let (amount, effectiveness) = struggle(OneOrTwo::One(Type::Normal));
+        assert!(amount > 0);
+        assert_eq!(effectiveness, Effectiveness::NORMAL);
+        assert_eq!(struggle(OneOrTwo::One(Type::Fighting)), (amount, effectiveness));
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::Ability;
+use crate::attack::{BattleStat, DamageType, Move, MoveData, ScreenType, Weather};
+use crate::core::OneOrTwo;
+use crate::item::Item;
+use crate::pokemon::{Pokemon, SpeciesData};
+use crate::species::Species;
+use crate::types::Type;
+
+pub mod accuracy;
+pub mod ai;
+pub mod critical;
+pub mod damage;
+pub mod engine;
+pub mod order;
+pub mod replay;
+#[cfg(test)]
+pub(crate) mod testing;
+
+/// Lookup of a move's battle data
+pub type MoveDataLookup = HashMap<Move, MoveData>;
+
+/// Identifies one of the two sides of a battle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SideId {
+    Player,
+    Opponent
+}
+impl SideId {
+    /// Get the side opposing this one
+    pub fn opposite(&self) -> SideId {
+        match self {
+            SideId::Player => SideId::Opponent,
+            SideId::Opponent => SideId::Player
+        }
+    }
+}
+
+/// Represents a choice one side makes at the start of a turn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// Use one of the active Pokemon's moves
+    Fight(Move),
+    /// Use Struggle, as no moves have PP remaining
+    Struggle,
+    /// Swap the active Pokemon with the party member in this slot
+    Switch(usize),
+    /// Use an item from the bag on the party member in this slot
+    UseItem(Item, usize),
+    /// Attempt to flee from the battle
+    Run
+}
+
+/// Represents the stat stages of a Pokemon in battle.
+/// Stages start at 0, and can be raised up to +6 or lowered down to -6.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatStages {
+    pub attack: i8,
+    pub defense: i8,
+    pub special_attack: i8,
+    pub special_defense: i8,
+    pub speed: i8,
+    pub accuracy: i8,
+    pub evasion: i8,
+    pub critical_hit_ratio: i8
+}
+impl StatStages {
+    pub const MAX: i8 = 6;
+    pub const MIN: i8 = -6;
+
+    /// Get the current stage of a stat
+    pub fn get(&self, stat: BattleStat) -> i8 {
+        match stat {
+            BattleStat::Attack => self.attack,
+            BattleStat::Defense => self.defense,
+            BattleStat::SpecialAttack => self.special_attack,
+            BattleStat::SpecialDefense => self.special_defense,
+            BattleStat::Speed => self.speed,
+            BattleStat::Accuracy => self.accuracy,
+            BattleStat::Evasion => self.evasion,
+            BattleStat::CriticalHitRatio => self.critical_hit_ratio
+        }
+    }
+
+    /// Raise or lower a stat stage, stopping at the limits.
+    /// Returns the amount the stage actually changed by.
+    pub fn change(&mut self, stat: BattleStat, delta: i8) -> i8 {
+        let min = match stat {
+            BattleStat::CriticalHitRatio => 0,
+            _ => StatStages::MIN
+        };
+        let stage = match stat {
+            BattleStat::Attack => &mut self.attack,
+            BattleStat::Defense => &mut self.defense,
+            BattleStat::SpecialAttack => &mut self.special_attack,
+            BattleStat::SpecialDefense => &mut self.special_defense,
+            BattleStat::Speed => &mut self.speed,
+            BattleStat::Accuracy => &mut self.accuracy,
+            BattleStat::Evasion => &mut self.evasion,
+            BattleStat::CriticalHitRatio => &mut self.critical_hit_ratio
+        };
+        let start = *stage;
+        *stage = stage.saturating_add(delta).clamp(min, StatStages::MAX);
+        *stage - start
+    }
+
+    /// Apply a stage to a stat value.
+    /// Each positive stage adds 50% of the stat, while each negative stage
+    /// lowers it by the same fraction (+1 = 3/2, -1 = 2/3, and so on).
+    pub fn apply(value: u16, stage: i8) -> u16 {
+        let stage = i32::from(stage.clamp(StatStages::MIN, StatStages::MAX));
+        let (numerator, denominator) = if stage >= 0 {
+            (2 + stage, 2)
+        } else {
+            (2, 2 - stage)
+        };
+        (i32::from(value) * numerator / denominator) as u16
+    }
+}
+
+/// Represents a Pokemon while it takes part in a battle.
+/// Species data which does not change mid-battle is cached here, so the battle
+/// does not need to consult any lookups.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Battler {
+    pub pokemon: Pokemon,
+    pub types: OneOrTwo<Type>,
+    pub ability: Ability,
+    /// Base stats, in order: HP, Attack, Defense, Special Attack, Special Defense, Speed
+    pub base_stats: [u8; 6],
+    #[serde(default)]
+    pub stages: StatStages,
+    /// Moves this Pokemon has used, which the opposing side now knows about
+    #[serde(default)]
+    pub revealed_moves: Vec<Move>,
+    /// If this Pokemon used Lock-On, guaranteeing its next move hits
+    #[serde(default)]
+    pub locked_on: bool,
+    /// If this Pokemon was identified by Foresight, preventing it from benefiting from raised evasion
+    #[serde(default)]
+    pub identified: bool,
+    /// The EVs gained by each opposing Pokemon which battled this one, once it faints
+    #[serde(default)]
+    pub ev_yield: [u8; 6],
+    /// The opposing party slots which have been sent out against this Pokemon
+    #[serde(default)]
+    pub faced_by: Vec<usize>
+}
+impl Battler {
+    pub fn new(pokemon: Pokemon, data: &SpeciesData) -> Battler {
+        Battler {
+            types: data._type,
+            ability: data.get_ability(pokemon.ability),
+            base_stats: data.stats.base_stats(),
+            stages: StatStages::default(),
+            revealed_moves: Vec::new(),
+            locked_on: false,
+            identified: false,
+            ev_yield: data.stats.ev_yields(),
+            faced_by: Vec::new(),
+            pokemon
+        }
+    }
+
+    /// Get the value of a stat, with stat stages applied
+    pub fn stat(&self, stat: BattleStat) -> u16 {
+        StatStages::apply(self.raw_stat(stat), self.stages.get(stat))
+    }
+
+    /// Get the value of a stat, before stat stages are applied
+    pub fn raw_stat(&self, stat: BattleStat) -> u16 {
+        match stat {
+            BattleStat::Attack => self.pokemon.attack.value,
+            BattleStat::Defense => self.pokemon.defense.value,
+            BattleStat::SpecialAttack => self.pokemon.special_attack.value,
+            BattleStat::SpecialDefense => self.pokemon.special_defense.value,
+            BattleStat::Speed => self.pokemon.speed.value,
+            BattleStat::Accuracy | BattleStat::Evasion | BattleStat::CriticalHitRatio => 0
+        }
+    }
+
+    /// Get all moves which have PP remaining
+    pub fn usable_moves(&self) -> Vec<Move> {
+        [self.pokemon.move_1, self.pokemon.move_2, self.pokemon.move_3, self.pokemon.move_4].iter()
+            .flatten()
+            .filter(|slot| slot.pp > 0)
+            .map(|slot| slot.attack)
+            .collect()
+    }
+
+    /// Change this Pokemon into another form, taking on that form's types and stats.
+    /// The ability is kept, as abilities like Zen Mode cause the change in the first place.
+    pub fn change_form(&mut self, form: Species, data: &SpeciesData) {
+        self.pokemon.change_form(form, data);
+        self.types = data._type;
+        self.base_stats = data.stats.base_stats();
+    }
+
+    /// Note that this Pokemon used a move, revealing it to the opponent
+    pub fn reveal_move(&mut self, attack: Move) {
+        if !self.revealed_moves.contains(&attack) {
+            self.revealed_moves.push(attack);
+        }
+    }
+
+    /// Clear everything that does not persist when this Pokemon leaves the field
+    pub fn switch_out(&mut self) {
+        self.stages = StatStages::default();
+        self.locked_on = false;
+        self.identified = false;
+    }
+
+    pub fn is_fainted(&self) -> bool {
+        self.pokemon.is_fainted()
+    }
+}
+
+/// Represents one side of a battle: a party, and the Pokemon currently sent out
+#[derive(Debug, Clone)]
+pub struct Side {
+    pub party: Vec<Battler>,
+    pub active: usize,
+    /// The number of turns remaining before Reflect wears off
+    pub reflect: u8,
+    /// The number of turns remaining before Light Screen wears off
+    pub light_screen: u8
+}
+impl Side {
+    /// Create a side. The first Pokemon able to battle is sent out first.
+    pub fn new(party: Vec<Battler>) -> Side {
+        let active = party.iter()
+            .position(|b| !b.is_fainted())
+            .unwrap_or(0);
+        Side { party, active, reflect: 0, light_screen: 0 }
+    }
+
+    pub fn active(&self) -> &Battler {
+        &self.party[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Battler {
+        &mut self.party[self.active]
+    }
+
+    /// Check if the party member in the slot could be switched in
+    pub fn can_switch_to(&self, slot: usize) -> bool {
+        slot != self.active && self.party.get(slot).is_some_and(|b| !b.is_fainted())
+    }
+
+    /// Get every party slot which could be switched in
+    pub fn switch_candidates(&self) -> Vec<usize> {
+        (0..self.party.len())
+            .filter(|slot| self.can_switch_to(*slot))
+            .collect()
+    }
+
+    /// Get the turns remaining of a screen
+    pub fn screen_mut(&mut self, screen: ScreenType) -> &mut u8 {
+        match screen {
+            ScreenType::Reflect => &mut self.reflect,
+            ScreenType::LightScreen => &mut self.light_screen
+        }
+    }
+
+    /// Check if a screen is weakening attacks of this damage type against this side
+    pub fn is_screened(&self, damage_type: DamageType) -> bool {
+        match damage_type {
+            DamageType::Physical => self.reflect > 0,
+            DamageType::Special => self.light_screen > 0,
+            DamageType::Status => false
+        }
+    }
+
+    /// Check if every Pokemon on this side has fainted
+    pub fn is_defeated(&self) -> bool {
+        self.party.iter().all(|b| b.is_fainted())
+    }
+}
+
+/// Represents how a battle ended
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BattleOutcome {
+    /// This side knocked out every opposing Pokemon
+    Victory(SideId),
+    /// This side ran from the battle
+    Fled(SideId)
+}
+
+/// Represents the entire state of a single battle
+#[derive(Debug, Clone)]
+pub struct Battle {
+    pub player: Side,
+    pub opponent: Side,
+    pub weather: Option<Weather>,
+    /// The number of turns remaining before the weather clears
+    pub weather_turns: u8,
+    /// The number of turns remaining before Trick Room ends
+    pub trick_room: u8,
+    pub turn: u16,
+    pub fled: Option<SideId>
+}
+impl Battle {
+    pub fn new(player: Side, opponent: Side) -> Battle {
+        Battle {
+            player,
+            opponent,
+            weather: None,
+            weather_turns: 0,
+            trick_room: 0,
+            turn: 0,
+            fled: None
+        }
+    }
+
+    pub fn side(&self, id: SideId) -> &Side {
+        match id {
+            SideId::Player => &self.player,
+            SideId::Opponent => &self.opponent
+        }
+    }
+
+    pub fn side_mut(&mut self, id: SideId) -> &mut Side {
+        match id {
+            SideId::Player => &mut self.player,
+            SideId::Opponent => &mut self.opponent
+        }
+    }
+
+    /// Get how the battle ended, if it is over
+    pub fn outcome(&self) -> Option<BattleOutcome> {
+        if let Some(side) = self.fled {
+            Some(BattleOutcome::Fled(side))
+        } else if self.opponent.is_defeated() {
+            Some(BattleOutcome::Victory(SideId::Player))
+        } else if self.player.is_defeated() {
+            Some(BattleOutcome::Victory(SideId::Opponent))
+        } else {
+            None
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome().is_some()
+    }
+}
@@ -0,0 +1,169 @@
+use std::cmp::Reverse;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::attack::BattleStat;
+use crate::battle::{Action, Battle, Battler, MoveDataLookup, SideId};
+use crate::item::Item;
+use crate::species::Species;
+
+/// The group an action falls in.
+/// Actions in a later bracket always go before actions in an earlier one, regardless of speed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bracket {
+    /// A move, with its priority
+    Move(i8),
+    Run,
+    Item,
+    Switch
+}
+impl Bracket {
+    /// Get the bracket a chosen action falls in
+    pub fn of(action: &Action, moves: &MoveDataLookup) -> Bracket {
+        match action {
+            Action::Fight(m) => Bracket::Move(moves.get(m).map_or(0, |d| d.priority)),
+            Action::Struggle => Bracket::Move(0),
+            Action::Run => Bracket::Run,
+            Action::UseItem(..) => Bracket::Item,
+            Action::Switch(_) => Bracket::Switch
+        }
+    }
+}
+
+/// Where a Pokemon falls within its bracket, before speed is considered
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Haste {
+    /// Held a Lagging Tail or Full Incense
+    Last,
+    Normal,
+    /// Had its Quick Claw activate
+    First
+}
+
+/// The chance, out of 100, that a Quick Claw lets its holder move first
+const QUICK_CLAW_CHANCE: u32 = 20;
+
+/// Get a Pokemon's speed, after its stat stages, status, and held item are applied.
+/// Trick Room is not considered here, as it reverses the order rather than changing speed.
+pub fn effective_speed(battler: &Battler) -> u16 {
+    modified_speed(battler.stat(BattleStat::Speed), battler.pokemon.held_item.as_ref(), battler.pokemon.species, battler.pokemon.status.paralysis)
+}
+
+/// Apply a held item and paralysis to a speed stat which already has stat stages applied
+pub fn modified_speed(speed: u16, held_item: Option<&Item>, species: Species, paralyzed: bool) -> u16 {
+    let mut speed = u32::from(speed);
+    match held_item {
+        Some(Item::ChoiceScarf) => speed = speed * 3 / 2,
+        Some(Item::IronBall) => speed /= 2,
+        Some(Item::QuickPowder) if species == Species::Ditto => speed *= 2,
+        _ => {}
+    }
+    if paralyzed {
+        speed /= 4;
+    }
+    speed.min(u32::from(u16::MAX)) as u16
+}
+
+/// Roll where a Pokemon falls within its bracket.
+/// Held items only take effect on moves, as everything else happens before speed matters.
+fn haste<R: Rng + ?Sized>(battler: &Battler, bracket: Bracket, rng: &mut R) -> Haste {
+    if !matches!(bracket, Bracket::Move(_)) {
+        return Haste::Normal;
+    }
+    match battler.pokemon.held_item {
+        Some(Item::LaggingTail) | Some(Item::FullIncense) => Haste::Last,
+        Some(Item::QuickClaw) if rng.gen_ratio(QUICK_CLAW_CHANCE, 100) => Haste::First,
+        _ => Haste::Normal
+    }
+}
+
+/// Sort the actions chosen this turn into the order they are carried out.
+/// Actions are sorted by bracket, then by held items like Quick Claw, then by effective speed.
+/// While Trick Room is active, slower Pokemon move first. Any remaining ties are broken randomly.
+pub fn resolve<R: Rng + ?Sized>(battle: &Battle, actions: Vec<(SideId, Action)>, moves: &MoveDataLookup, rng: &mut R) -> Vec<(SideId, Action)> {
+    let mut keyed = actions.into_iter()
+        .map(|(side, action)| {
+            let battler = battle.side(side).active();
+            let bracket = Bracket::of(&action, moves);
+            let haste = haste(battler, bracket, rng);
+            let speed = i32::from(effective_speed(battler));
+            let speed = if battle.trick_room > 0 { -speed } else { speed };
+            ((bracket, haste, speed), (side, action))
+        })
+        .collect::<Vec<_>>();
+
+    // Shuffling before a stable sort leaves tied actions in a random order
+    keyed.shuffle(rng);
+    keyed.sort_by_key(|(key, _)| Reverse(*key));
+    keyed.into_iter()
+        .map(|(_, action)| action)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use crate::abilities::Ability;
+    use crate::attack::{DamageType, Move};
+    use crate::battle::replay::BattleRng;
+    use crate::battle::testing::{battler, move_data};
+    use crate::battle::Side;
+    use crate::core::OneOrTwo;
+    use crate::types::Type;
+
+    use super::*;
+
+    fn battle(player_speed: u8, opponent_speed: u8) -> Battle {
+        let mon = |speed| battler(Species::Rattata, OneOrTwo::One(Type::Normal), Ability::RunAway,
+                                  [50, 50, 50, 50, 50, speed], 50, &[Move::Tackle]);
+        Battle::new(Side::new(vec![mon(player_speed)]), Side::new(vec![mon(opponent_speed)]))
+    }
+
+    fn first(battle: &Battle, player: Action, opponent: Action, moves: &MoveDataLookup) -> SideId {
+        let mut rng = BattleRng::seed_from_u64(0);
+        resolve(battle, vec![(SideId::Player, player), (SideId::Opponent, opponent)], moves, &mut rng)[0].0
+    }
+
+    #[test]
+    fn turn_order() {
+        let mut moves = MoveDataLookup::new();
+        moves.insert(Move::Tackle, move_data(Move::Tackle, Type::Normal, 35, 95, DamageType::Physical));
+        let mut quick = move_data(Move::QuickAttack, Type::Normal, 40, 100, DamageType::Physical);
+        quick.priority = 1;
+        moves.insert(Move::QuickAttack, quick);
+
+        let mut b = battle(100, 50);
+        assert_eq!(first(&b, Action::Fight(Move::Tackle), Action::Fight(Move::Tackle), &moves), SideId::Player);
+        assert_eq!(first(&b, Action::Fight(Move::Tackle), Action::Fight(Move::QuickAttack), &moves), SideId::Opponent);
+        assert_eq!(first(&b, Action::Fight(Move::QuickAttack), Action::Switch(0), &moves), SideId::Opponent);
+        assert_eq!(first(&b, Action::Run, Action::UseItem(Item::Potion, 0), &moves), SideId::Opponent);
+
+        b.trick_room = 5;
+        assert_eq!(first(&b, Action::Fight(Move::Tackle), Action::Fight(Move::Tackle), &moves), SideId::Opponent);
+        b.trick_room = 0;
+
+        b.player.active_mut().pokemon.status.paralysis = true;
+        assert_eq!(first(&b, Action::Fight(Move::Tackle), Action::Fight(Move::Tackle), &moves), SideId::Opponent);
+        b.player.active_mut().pokemon.status.paralysis = false;
+
+        b.player.active_mut().pokemon.held_item = Some(Item::LaggingTail);
+        assert_eq!(first(&b, Action::Fight(Move::Tackle), Action::Fight(Move::Tackle), &moves), SideId::Opponent);
+    }
+
+    #[test]
+    fn effective_speed_items() {
+        let mut b = battle(100, 100);
+        let base = effective_speed(b.player.active());
+        b.player.active_mut().pokemon.held_item = Some(Item::ChoiceScarf);
+        assert_eq!(effective_speed(b.player.active()), base * 3 / 2);
+        b.player.active_mut().pokemon.held_item = Some(Item::IronBall);
+        assert_eq!(effective_speed(b.player.active()), base / 2);
+        b.player.active_mut().pokemon.held_item = Some(Item::QuickPowder);
+        assert_eq!(effective_speed(b.player.active()), base);
+        b.player.active_mut().stages.change(BattleStat::Speed, 2);
+        b.player.active_mut().pokemon.held_item = None;
+        assert_eq!(effective_speed(b.player.active()), base * 2);
+    }
+}
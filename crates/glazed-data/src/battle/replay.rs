@@ -0,0 +1,245 @@
+use std::fmt::{Display, Formatter};
+use std::io::{Read, Write};
+
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256StarStar;
+use serde::{Deserialize, Serialize};
+
+use crate::battle::engine::BattleEvent;
+use crate::battle::{Action, Battle, BattleOutcome, Battler, MoveDataLookup, Side, SideId};
+
+/// The RNG used to drive a battle. Seeded, so a battle can be played back exactly.
+pub type BattleRng = Xoshiro256StarStar;
+
+/// The choices both sides made during a single turn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnRecord {
+    pub player: Action,
+    pub opponent: Action,
+    /// The party slot the player sent out after the turn, if their Pokemon fainted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_replacement: Option<usize>,
+    /// The party slot the opponent sent out after the turn, if their Pokemon fainted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opponent_replacement: Option<usize>
+}
+
+/// A summary of the state a battle ended in, used to verify a replay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BattleResult {
+    pub outcome: Option<BattleOutcome>,
+    pub turns: u16,
+    pub player_hp: Vec<u16>,
+    pub opponent_hp: Vec<u16>
+}
+impl From<&Battle> for BattleResult {
+    fn from(battle: &Battle) -> Self {
+        let hp = |side: &Side| side.party.iter().map(|b| b.pokemon.current_hp).collect();
+        BattleResult {
+            outcome: battle.outcome(),
+            turns: battle.turn,
+            player_hp: hp(&battle.player),
+            opponent_hp: hp(&battle.opponent)
+        }
+    }
+}
+
+/// Everything needed to play a battle back: the seed, both starting parties, and every action taken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BattleRecord {
+    pub seed: u64,
+    pub player: Vec<Battler>,
+    pub opponent: Vec<Battler>,
+    pub turns: Vec<TurnRecord>,
+    /// How the battle ended. Present once recording is finished.
+    #[serde(default)]
+    pub result: Option<BattleResult>
+}
+impl BattleRecord {
+    /// Write this record out, as YAML
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), serde_yaml::Error> {
+        serde_yaml::to_writer(writer, self)
+    }
+
+    /// Read a record in, from YAML
+    pub fn read<R: Read>(reader: R) -> Result<BattleRecord, serde_yaml::Error> {
+        serde_yaml::from_reader(reader)
+    }
+}
+
+/// Plays a battle while recording every action taken, so it can be replayed later
+pub struct BattleRecorder {
+    battle: Battle,
+    rng: BattleRng,
+    record: BattleRecord
+}
+impl BattleRecorder {
+    pub fn new(seed: u64, player: Vec<Battler>, opponent: Vec<Battler>) -> BattleRecorder {
+        BattleRecorder {
+            battle: Battle::new(Side::new(player.clone()), Side::new(opponent.clone())),
+            rng: BattleRng::seed_from_u64(seed),
+            record: BattleRecord { seed, player, opponent, turns: Vec::new(), result: None }
+        }
+    }
+
+    pub fn battle(&self) -> &Battle {
+        &self.battle
+    }
+
+    /// Play out a turn, and record the actions taken
+    pub fn play_turn(&mut self, player: Action, opponent: Action, moves: &MoveDataLookup) -> Vec<BattleEvent> {
+        self.record.turns.push(TurnRecord {
+            player: player.clone(),
+            opponent: opponent.clone(),
+            player_replacement: None,
+            opponent_replacement: None
+        });
+        self.battle.play_turn(player, opponent, moves, &mut self.rng)
+    }
+
+    /// Send out a replacement for a fainted Pokemon, and record it against the last turn
+    pub fn replace(&mut self, side: SideId, slot: usize) -> Vec<BattleEvent> {
+        if let Some(turn) = self.record.turns.last_mut() {
+            match side {
+                SideId::Player => turn.player_replacement = Some(slot),
+                SideId::Opponent => turn.opponent_replacement = Some(slot)
+            }
+        }
+        self.battle.replace(side, slot)
+    }
+
+    /// Stop recording, and get the completed record
+    pub fn finish(mut self) -> BattleRecord {
+        self.record.result = Some(BattleResult::from(&self.battle));
+        self.record
+    }
+}
+
+/// Represents a replay not ending the way it was recorded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The record has no result to compare against
+    Unfinished,
+    Mismatch { expected: BattleResult, actual: BattleResult }
+}
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Unfinished => write!(f, "Battle record has no result"),
+            ReplayError::Mismatch { expected, actual } =>
+                write!(f, "Replay diverged from record: expected {:?}, got {:?}", expected, actual)
+        }
+    }
+}
+impl std::error::Error for ReplayError {}
+
+/// Play a recorded battle back, and check that it ends exactly as it did when recorded.
+/// Returns the final state of the battle.
+pub fn replay(record: &BattleRecord, moves: &MoveDataLookup) -> Result<Battle, ReplayError> {
+    let expected = record.result.as_ref().ok_or(ReplayError::Unfinished)?;
+    let mut battle = Battle::new(Side::new(record.player.clone()), Side::new(record.opponent.clone()));
+    let mut rng = BattleRng::seed_from_u64(record.seed);
+
+    for turn in record.turns.iter() {
+        battle.play_turn(turn.player.clone(), turn.opponent.clone(), moves, &mut rng);
+        if let Some(slot) = turn.player_replacement {
+            battle.replace(SideId::Player, slot);
+        }
+        if let Some(slot) = turn.opponent_replacement {
+            battle.replace(SideId::Opponent, slot);
+        }
+    }
+
+    let actual = BattleResult::from(&battle);
+    if actual == *expected {
+        Ok(battle)
+    } else {
+        Err(ReplayError::Mismatch { expected: expected.clone(), actual })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use crate::abilities::Ability;
+    use crate::attack::{DamageType, Move};
+    use crate::battle::ai::AiTier;
+    use crate::battle::testing::{battler, move_data, pikachu};
+    use crate::battle::{MoveDataLookup, SideId};
+    use crate::core::OneOrTwo;
+    use crate::species::Species;
+    use crate::types::Type;
+
+    use super::*;
+
+    fn moves() -> MoveDataLookup {
+        vec![
+            move_data(Move::Tackle, Type::Normal, 50, 100, DamageType::Physical),
+            move_data(Move::Ember, Type::Fire, 40, 100, DamageType::Special),
+            move_data(Move::WaterGun, Type::Water, 40, 100, DamageType::Special),
+            move_data(Move::ThunderShock, Type::Electric, 40, 100, DamageType::Special)
+        ].into_iter().map(|m| (m.id, m)).collect()
+    }
+
+    fn record(seed: u64) -> BattleRecord {
+        let moves = moves();
+        let player = vec![
+            battler(Species::Charmander, OneOrTwo::One(Type::Fire), Ability::Blaze, [39, 52, 43, 60, 50, 65], 10, &[Move::Ember, Move::Tackle]),
+            pikachu(10, &[Move::ThunderShock])
+        ];
+        let opponent = vec![
+            battler(Species::Squirtle, OneOrTwo::One(Type::Water), Ability::Torrent, [44, 48, 65, 50, 64, 43], 10, &[Move::WaterGun, Move::Tackle]),
+            battler(Species::Bulbasaur, OneOrTwo::Two(Type::Grass, Type::Poison), Ability::Overgrow, [45, 49, 49, 65, 65, 45], 10, &[Move::Tackle])
+        ];
+
+        let mut ai_rng = BattleRng::seed_from_u64(seed.wrapping_add(1));
+        let mut recorder = BattleRecorder::new(seed, player, opponent);
+        while !recorder.battle().is_over() && recorder.battle().turn < 100 {
+            let player = AiTier::Greedy.choose_action(recorder.battle(), SideId::Player, &moves, &mut ai_rng);
+            let opponent = AiTier::Random.choose_action(recorder.battle(), SideId::Opponent, &moves, &mut ai_rng);
+            recorder.play_turn(player, opponent, &moves);
+            for side in [SideId::Player, SideId::Opponent] {
+                if recorder.battle().needs_replacement(side) {
+                    let slot = AiTier::Greedy.choose_replacement(recorder.battle(), side, &moves, &mut ai_rng).unwrap();
+                    recorder.replace(side, slot);
+                }
+            }
+        }
+        recorder.finish()
+    }
+
+    #[test]
+    fn replay_matches_recording() {
+        let record = record(1234);
+        assert!(record.result.as_ref().unwrap().outcome.is_some());
+        assert!(replay(&record, &moves()).is_ok());
+    }
+
+    #[test]
+    fn replay_survives_serialization() {
+        let record = record(5678);
+        let mut buffer = Vec::new();
+        record.write(&mut buffer).unwrap();
+        let read = BattleRecord::read(buffer.as_slice()).unwrap();
+        assert_eq!(read.turns, record.turns);
+        assert!(replay(&read, &moves()).is_ok());
+    }
+
+    #[test]
+    fn replay_detects_divergence() {
+        let mut record = record(42);
+        assert!(replay(&record, &moves()).is_ok());
+
+        // Same actions, different luck
+        record.seed += 1;
+        match replay(&record, &moves()) {
+            Err(ReplayError::Mismatch { expected, actual }) => {
+                assert_eq!(Some(&expected), record.result.as_ref());
+                assert_eq!(usize::from(actual.turns), record.turns.len());
+                assert!(actual.player_hp != expected.player_hp || actual.opponent_hp != expected.opponent_hp);
+            },
+            other => panic!("Expected a mismatch, got {:?}", other.map(|b| BattleResult::from(&b)))
+        }
+    }
+}
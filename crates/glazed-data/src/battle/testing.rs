@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::abilities::Ability;
+use crate::attack::{Accuracy, DamageType, Move, MoveData, Power, Target};
+use crate::battle::Battler;
+use crate::core::OneOrTwo;
+use crate::item::Pokeball;
+use crate::locations::Location;
+use crate::pokemon::{self, AbilitySlot, Gender, Markings, MoveSlot, Nature, NatureBoost, Pokemon, PokemonContestStats, PokemonPokerusStatus, PokemonStatusCondition, SpeciesData, StatSlot};
+use crate::species::Species;
+use crate::types::Type;
+
+/// Create a battler with perfect IVs, no EVs, and a neutral nature
+pub fn battler(species: Species, types: OneOrTwo<Type>, ability: Ability, base_stats: [u8; 6], level: u8, moves: &[Move]) -> Battler {
+    let [hp, attack, defense, special_attack, special_defense, speed] = base_stats;
+    let stat = |base| StatSlot::stat(base, level, 31, 0, NatureBoost::Neutral);
+    let slot = |i: usize| moves.get(i).map(|attack| MoveSlot { attack: *attack, pp: 10, pp_bonus: 0 });
+    let hp = StatSlot::hp(hp, level, 31, 0);
+    let pokemon = Pokemon {
+        species,
+        gender: Gender::None,
+        egg: false,
+        level_met: level,
+        nature: Nature::Hardy,
+        ability: AbilitySlot::SlotOne,
+        poke_ball: Pokeball::PokeBall,
+        held_item: None,
+        move_1: slot(0),
+        move_2: slot(1),
+        move_3: slot(2),
+        move_4: slot(3),
+        experience: 0,
+        personality: 0,
+        friendship: 70,
+        original_trainer_id: 0,
+        original_trainer_secret_id: 0,
+        original_trainer_name: String::from("Test"),
+        nickname: None,
+        level,
+        markings: Markings::empty(),
+        status: PokemonStatusCondition::default(),
+        pokerus: PokemonPokerusStatus::None,
+        current_hp: hp.value,
+        hp,
+        attack: stat(attack),
+        defense: stat(defense),
+        special_attack: stat(special_attack),
+        special_defense: stat(special_defense),
+        speed: stat(speed),
+        contest: PokemonContestStats::default(),
+        fateful_encounter: false,
+        date_caught: 0,
+        location_caught: Location::FarawayPlace
+    };
+    Battler {
+        pokemon,
+        types,
+        ability,
+        base_stats,
+        stages: Default::default(),
+        revealed_moves: Vec::new(),
+        locked_on: false,
+        identified: false,
+        ev_yield: [0; 6],
+        faced_by: Vec::new()
+    }
+}
+
+/// Create a Pikachu battler, which most tests use when the species doesn't matter
+pub fn pikachu(level: u8, moves: &[Move]) -> Battler {
+    battler(Species::Pikachu, OneOrTwo::One(Type::Electric), Ability::Static, [35, 55, 40, 50, 50, 90], level, moves)
+}
+
+/// Load the species data the game ships with, panicking if it is invalid
+pub fn shipped_species() -> HashMap<Species, SpeciesData> {
+    let yaml = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../glazed-dx/assets/pkmn/data.pkmn")).unwrap();
+    pokemon::validate_species_data(&yaml).unwrap()
+}
+
+/// Create data for a simple move, which targets the opponent and has no effects
+pub fn move_data(id: Move, _type: Type, power: u8, accuracy: u8, damage_type: DamageType) -> MoveData {
+    MoveData {
+        id,
+        pp: 10,
+        priority: 0,
+        power: Power::Base(power),
+        accuracy: Accuracy::Percentage(accuracy),
+        _type,
+        damage_type,
+        target: Target::Opponent,
+        crit_rate: 0,
+        effects: Vec::new()
+    }
+}
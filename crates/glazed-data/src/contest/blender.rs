@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::contest::{BerryPokeblockData, Pokeblock, PokeblockColor};
+use crate::item::Berry;
+
+/// The most berries that can go into the Berry Blender at once, one per participant
+pub const MAX_BLENDER_BERRIES: usize = 4;
+/// Each multiple of this RPM reached while blending multiplies the Pokeblock's flavors once more
+pub const RPM_PER_MULTIPLIER: u16 = 333;
+/// The level at which a Pokeblock with one or two flavors becomes Gold
+pub const GOLD_LEVEL: u8 = 50;
+
+/// Lookup of a berry's flavor data
+pub type BerryPokeblockLookup = HashMap<Berry, BerryPokeblockData>;
+
+/// Reasons berries could not be blended
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlendError {
+    /// At least one berry must be blended
+    NoBerries,
+    /// More berries were given than there are places in the blender
+    TooManyBerries(usize),
+    /// A berry has no flavor data
+    MissingBerryData(Berry)
+}
+impl Display for BlendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlendError::NoBerries => write!(f, "No berries were put in the blender"),
+            BlendError::TooManyBerries(b) => write!(f, "Only {} berries can be blended, got {}", MAX_BLENDER_BERRIES, b),
+            BlendError::MissingBerryData(b) => write!(f, "Missing flavor data for {:?}", b)
+        }
+    }
+}
+impl Error for BlendError {}
+
+/// Get the color of a Pokeblock with the given flavors, in the order spicy, dry, sweet, bitter, sour.
+/// See Pokeblock::color for the rules.
+pub fn color_for_flavors(flavors: [u8; 5]) -> PokeblockColor {
+    let count = flavors.iter().filter(|f| **f > 0).count();
+    let level = flavors.iter().copied().max().unwrap_or(0);
+    // The first of the strongest flavors, if two are tied
+    let best = flavors.iter()
+        .position(|f| *f == level)
+        .unwrap_or(0);
+    match count {
+        0 => PokeblockColor::Black,
+        1 | 2 if level >= GOLD_LEVEL => PokeblockColor::Gold,
+        1 => [PokeblockColor::Red, PokeblockColor::Blue, PokeblockColor::Pink, PokeblockColor::Green, PokeblockColor::Yellow][best],
+        2 => [PokeblockColor::Purple, PokeblockColor::Indigo, PokeblockColor::Brown, PokeblockColor::LightBlue, PokeblockColor::Olive][best],
+        3 => PokeblockColor::Gray,
+        _ => PokeblockColor::White
+    }
+}
+
+/// Blend berries into a Pokeblock.
+/// Each flavor is weakened by the flavor after it, and then by the number of flavors that ended up negative.
+/// The remaining flavors are multiplied by how fast the blender spun, and the Pokeblock's feel is the
+/// average smoothness of the berries, less the number of berries.
+/// Blending two of the same berry always makes a Black Pokeblock.
+pub fn blend<R: Rng + ?Sized>(berries: &[&BerryPokeblockData], max_rpm: u16, rng: &mut R) -> Result<Pokeblock, BlendError> {
+    if berries.is_empty() {
+        return Err(BlendError::NoBerries);
+    }
+    if berries.len() > MAX_BLENDER_BERRIES {
+        return Err(BlendError::TooManyBerries(berries.len()));
+    }
+    let duplicate = berries.iter()
+        .enumerate()
+        .any(|(idx, berry)| berries[..idx].iter().any(|other| other.id == berry.id));
+    if duplicate {
+        return Ok(Pokeblock::create_black_pokeblock(rng));
+    }
+
+    let mut sums = [0i16; 5];
+    for berry in berries {
+        let flavors = [berry.spicy, berry.dry, berry.sweet, berry.bitter, berry.sour];
+        for (sum, flavor) in sums.iter_mut().zip(flavors.iter()) {
+            *sum += i16::from(*flavor);
+        }
+    }
+    let weakened = [
+        sums[0] - sums[1],
+        sums[1] - sums[2],
+        sums[2] - sums[3],
+        sums[3] - sums[4],
+        sums[4] - sums[0]
+    ];
+    let negatives = weakened.iter().filter(|f| **f < 0).count() as i16;
+    let multiplier = 1.0 + f64::from(max_rpm) / f64::from(RPM_PER_MULTIPLIER);
+    let mut flavors = [0u8; 5];
+    for (flavor, value) in flavors.iter_mut().zip(weakened.iter()) {
+        let value = (value - negatives).max(0);
+        *flavor = (f64::from(value) * multiplier).round().min(f64::from(u8::MAX)) as u8;
+    }
+
+    let count = berries.len() as u16;
+    let smoothness = berries.iter().map(|b| u16::from(b.smoothness)).sum::<u16>() / count;
+    let [spicy, dry, sweet, bitter, sour] = flavors;
+    Ok(Pokeblock {
+        spicy,
+        dry,
+        sweet,
+        bitter,
+        sour,
+        feel: smoothness.saturating_sub(count) as u8,
+        level: flavors.iter().copied().max().unwrap_or(0),
+        color: color_for_flavors(flavors)
+    })
+}
+
+/// Choose the berries NPC participants put into the blender.
+/// Each NPC picks a random berry that nobody else has picked, so they never cause a Black Pokeblock.
+pub fn npc_berries<'a, R: Rng + ?Sized>(lookup: &'a BerryPokeblockLookup, chosen: &[Berry], npcs: usize, rng: &mut R) -> Vec<&'a BerryPokeblockData> {
+    // Sorted, so the same seed always gives the same berries
+    let mut available = lookup.values()
+        .filter(|b| !chosen.contains(&b.id))
+        .collect::<Vec<_>>();
+    available.sort_by_key(|b| b.id);
+    available.partial_shuffle(rng, npcs).0.to_vec()
+}
+
+/// Blend the player's berry along with those of some NPC participants
+pub fn blend_with_npcs<R: Rng + ?Sized>(berry: Berry, npcs: usize, lookup: &BerryPokeblockLookup, max_rpm: u16, rng: &mut R) -> Result<Pokeblock, BlendError> {
+    if npcs >= MAX_BLENDER_BERRIES {
+        return Err(BlendError::TooManyBerries(npcs + 1));
+    }
+    let player = lookup.get(&berry).ok_or(BlendError::MissingBerryData(berry))?;
+    let mut berries = vec![player];
+    berries.extend(npc_berries(lookup, &[berry], npcs, rng));
+    blend(&berries, max_rpm, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256StarStar;
+
+    use super::*;
+
+    fn lookup() -> BerryPokeblockLookup {
+        let data = |id, [spicy, dry, sweet, bitter, sour]: [i8; 5]| (id, BerryPokeblockData { id, spicy, dry, sweet, bitter, sour, smoothness: 25 });
+        vec![
+            data(Berry::CheriBerry, [10, 0, 0, 0, 0]),
+            data(Berry::ChestoBerry, [0, 10, 0, 0, 0]),
+            data(Berry::PechaBerry, [0, 0, 10, 0, 0]),
+            data(Berry::RawstBerry, [0, 0, 0, 10, 0]),
+            data(Berry::AspearBerry, [0, 0, 0, 0, 10])
+        ].into_iter().collect()
+    }
+
+    #[test]
+    fn blending() {
+        let lookup = lookup();
+        let mut rng = Xoshiro256StarStar::seed_from_u64(0);
+        let berry = |b| &lookup[&b];
+
+        let block = blend(&[berry(Berry::CheriBerry)], 0, &mut rng).unwrap();
+        assert_eq!((block.spicy, block.level, block.feel, block.color), (9, 9, 24, PokeblockColor::Red));
+
+        let block = blend(&[berry(Berry::CheriBerry), berry(Berry::ChestoBerry)], 0, &mut rng).unwrap();
+        assert_eq!((block.spicy, block.dry, block.feel, block.color), (0, 9, 23, PokeblockColor::Blue));
+
+        let block = blend(&[berry(Berry::CheriBerry), berry(Berry::PechaBerry)], 0, &mut rng).unwrap();
+        assert_eq!((block.spicy, block.sweet, block.color), (8, 8, PokeblockColor::Purple));
+
+        let block = blend(&[berry(Berry::CheriBerry)], RPM_PER_MULTIPLIER * 5, &mut rng).unwrap();
+        assert_eq!((block.level, block.color), (54, PokeblockColor::Gold));
+
+        let block = blend(&[berry(Berry::CheriBerry), berry(Berry::CheriBerry)], 0, &mut rng).unwrap();
+        assert_eq!(block.color, PokeblockColor::Black);
+        assert_eq!(block.spicy + block.dry + block.sweet + block.bitter + block.sour, 6);
+
+        assert_eq!(blend(&[], 0, &mut rng).unwrap_err(), BlendError::NoBerries);
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(color_for_flavors([0, 0, 0, 0, 0]), PokeblockColor::Black);
+        assert_eq!(color_for_flavors([0, 0, 0, 0, 12]), PokeblockColor::Yellow);
+        assert_eq!(color_for_flavors([0, 4, 0, 8, 0]), PokeblockColor::LightBlue);
+        assert_eq!(color_for_flavors([0, 60, 0, 8, 0]), PokeblockColor::Gold);
+        assert_eq!(color_for_flavors([1, 60, 0, 8, 0]), PokeblockColor::Gray);
+        assert_eq!(color_for_flavors([1, 1, 0, 1, 1]), PokeblockColor::White);
+    }
+
+    #[test]
+    fn npcs() {
+        let lookup = lookup();
+        let blend_seeded = |seed| {
+            let mut rng = Xoshiro256StarStar::seed_from_u64(seed);
+            let berries = npc_berries(&lookup, &[Berry::CheriBerry], 3, &mut rng);
+            berries.iter().map(|b| b.id).collect::<Vec<_>>()
+        };
+        let berries = blend_seeded(7);
+        assert_eq!(berries.len(), 3);
+        assert!(!berries.contains(&Berry::CheriBerry));
+        assert_eq!(berries, blend_seeded(7));
+
+        let mut rng = Xoshiro256StarStar::seed_from_u64(7);
+        let block = blend_with_npcs(Berry::CheriBerry, 3, &lookup, 0, &mut rng).unwrap();
+        assert_ne!(block.color, PokeblockColor::Black);
+        assert_eq!(block.feel, 21);
+        assert_eq!(blend_with_npcs(Berry::OranBerry, 1, &lookup, 0, &mut rng).unwrap_err(), BlendError::MissingBerryData(Berry::OranBerry));
+    }
+
+    #[test]
+    fn shipped_berry_flavors() {
+        let yaml = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../glazed-dx/assets/pkmn/berries.flavors")).unwrap();
+        let berries: Vec<BerryPokeblockData> = serde_yaml::from_str(&yaml).unwrap();
+        let lookup = berries.into_iter().map(|b| (b.id, b)).collect::<BerryPokeblockLookup>();
+        assert!(lookup.values().all(|b| b.smoothness > 0), "A berry has no smoothness");
+
+        let mut rng = Xoshiro256StarStar::seed_from_u64(0);
+        let block = blend(&[&lookup[&Berry::CheriBerry]], 0, &mut rng).unwrap();
+        assert_eq!(block.color, PokeblockColor::Red);
+        assert_ne!(blend_with_npcs(Berry::OranBerry, 3, &lookup, 0, &mut rng).unwrap().color, PokeblockColor::Black);
+    }
+}
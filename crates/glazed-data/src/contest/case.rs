@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::contest::{FeedError, Pokeblock, PokeblockReaction};
+use crate::pokemon::Pokemon;
+
+/// The most Pokeblocks the case can hold
+pub const POKEBLOCK_CASE_CAPACITY: usize = 40;
+
+/// Reasons a Pokeblock could not be put in the case
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PokeblockCaseError {
+    /// The case already holds POKEBLOCK_CASE_CAPACITY Pokeblocks
+    Full
+}
+impl Display for PokeblockCaseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PokeblockCaseError::Full => write!(f, "The Pokeblock Case is full")
+        }
+    }
+}
+impl Error for PokeblockCaseError {}
+
+/// The player's Pokeblock Case, which holds the Pokeblocks they have made
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PokeblockCase {
+    blocks: Vec<Pokeblock>
+}
+impl PokeblockCase {
+    /// Get every Pokeblock in the case
+    pub fn blocks(&self) -> &[Pokeblock] {
+        &self.blocks
+    }
+
+    /// Get the Pokeblock in a slot of the case
+    pub fn get(&self, slot: usize) -> Option<&Pokeblock> {
+        self.blocks.get(slot)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.blocks.len() >= POKEBLOCK_CASE_CAPACITY
+    }
+
+    /// Put a Pokeblock in the case. If the case is full, the Pokeblock is handed back.
+    pub fn add(&mut self, block: Pokeblock) -> Result<(), (Pokeblock, PokeblockCaseError)> {
+        if self.is_full() {
+            return Err((block, PokeblockCaseError::Full));
+        }
+        self.blocks.push(block);
+        Ok(())
+    }
+
+    /// Throw away the Pokeblock in a slot of the case
+    pub fn toss(&mut self, slot: usize) -> Option<Pokeblock> {
+        (slot < self.blocks.len()).then(|| self.blocks.remove(slot))
+    }
+
+    /// Sort the Pokeblocks from highest level to lowest
+    pub fn sort_by_level(&mut self) {
+        self.blocks.sort_by_key(|b| std::cmp::Reverse(b.level));
+    }
+
+    /// Feed the Pokeblock in a slot of the case to a Pokemon.
+    /// The Pokeblock is only used up if the Pokemon eats it.
+    pub fn feed(&mut self, slot: usize, pokemon: &mut Pokemon) -> Result<PokeblockReaction, FeedError> {
+        let block = self.blocks.get(slot).ok_or(FeedError::NoPokeblock)?;
+        let reaction = pokemon.feed_pokeblock(block)?;
+        self.blocks.remove(slot);
+        Ok(reaction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::pikachu;
+    use crate::contest::{FlavorPreference, MAX_SHEEN, PokeblockColor};
+    use crate::pokemon::Nature;
+
+    use super::*;
+
+    fn block(spicy: u8, dry: u8, feel: u8) -> Pokeblock {
+        Pokeblock { spicy, dry, sweet: 0, bitter: 0, sour: 0, feel, level: spicy.max(dry), color: PokeblockColor::Purple }
+    }
+
+    #[test]
+    fn feeding() {
+        let mut pokemon = pikachu(20, &[]).pokemon;
+        pokemon.nature = Nature::Adamant;
+        let mut case = PokeblockCase::default();
+        case.add(block(20, 10, 30)).unwrap();
+        case.add(block(0, 10, 30)).unwrap();
+
+        let reaction = case.feed(0, &mut pokemon).unwrap();
+        assert_eq!(reaction, PokeblockReaction { preference: FlavorPreference::Liked, conditions: [22, 9, 0, 0, 0], sheen: 30 });
+        assert_eq!(case.len(), 1);
+
+        let reaction = case.feed(0, &mut pokemon).unwrap();
+        assert_eq!(reaction.preference, FlavorPreference::Disliked);
+        assert_eq!(case.feed(0, &mut pokemon), Err(FeedError::NoPokeblock));
+
+        pokemon.contest.feel = MAX_SHEEN - 10;
+        case.add(block(5, 0, 30)).unwrap();
+        assert_eq!(case.feed(0, &mut pokemon).unwrap().sheen, 10);
+        assert!(!pokemon.can_eat_pokeblock());
+        case.add(block(5, 0, 30)).unwrap();
+        assert_eq!(case.feed(0, &mut pokemon), Err(FeedError::TooFull));
+        assert_eq!(case.len(), 1);
+    }
+
+    #[test]
+    fn capacity() {
+        let mut case = PokeblockCase::default();
+        for level in 0..POKEBLOCK_CASE_CAPACITY as u8 {
+            case.add(block(level, 0, 1)).unwrap();
+        }
+        assert_eq!(case.add(block(1, 0, 1)), Err((block(1, 0, 1), PokeblockCaseError::Full)));
+        case.sort_by_level();
+        assert_eq!(case.get(0).map(|b| b.level), Some(39));
+        assert!(case.toss(0).is_some());
+        assert!(case.toss(POKEBLOCK_CASE_CAPACITY).is_none());
+
+        let yaml = serde_yaml::to_string(&case).unwrap();
+        let loaded: PokeblockCase = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(loaded.blocks(), case.blocks());
+    }
+}
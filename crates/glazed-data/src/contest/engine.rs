@@ -0,0 +1,455 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::attack::Move;
+use crate::contest::Condition;
+use crate::item::Item;
+use crate::pokemon::{Pokemon, Ribbons};
+
+/// The number of turns each contestant makes an appeal
+pub const APPEAL_TURNS: usize = 5;
+/// The crowd's excitement, once which the crowd goes wild
+pub const MAX_EXCITEMENT: u8 = 5;
+/// The points given for each heart of appeal
+pub const POINTS_PER_HEART: i16 = 10;
+/// The bonus appeal given for maxing out the crowd's excitement
+pub const MAX_EXCITEMENT_BONUS: i16 = 60;
+
+/// Something a move does in a contest, besides making an appeal
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContestEffect {
+    #[default]
+    None,
+    /// Jams every contestant who appealed earlier this turn
+    JamAll,
+    /// Jams only the contestant who appealed directly before
+    JamPrevious,
+    /// Prevents being jammed for the rest of the turn
+    Protect,
+    /// The user appeals first next turn
+    NextFirst,
+    /// The user appeals last next turn
+    NextLast
+}
+
+/// A move's contest data
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContestMove {
+    pub id: Move,
+    pub condition: Condition,
+    /// The hearts of appeal this move makes
+    pub appeal: u8,
+    /// The hearts of appeal this move removes from jammed contestants
+    #[serde(default)]
+    pub jam: u8,
+    #[serde(default)]
+    pub effect: ContestEffect,
+    /// Moves which make a combo if used on the turn after this one
+    #[serde(default)]
+    pub combos: Vec<Move>
+}
+
+/// Lookup of a move's contest data
+pub type ContestMoveLookup = HashMap<Move, ContestMove>;
+
+/// The ranks of contests. A Pokemon must win a rank's ribbon to enter the next rank.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ContestRank {
+    Normal,
+    Super,
+    Hyper,
+    Master
+}
+impl ContestRank {
+    /// Get the ribbon for winning a contest of this rank and condition
+    pub fn ribbon(&self, condition: Condition) -> Ribbons {
+        match (condition, self) {
+            (Condition::Cool, ContestRank::Normal) => Ribbons::COOL_RIBBON,
+            (Condition::Cool, ContestRank::Super) => Ribbons::COOL_SUPER_RIBBON,
+            (Condition::Cool, ContestRank::Hyper) => Ribbons::COOL_HYPER_RIBBON,
+            (Condition::Cool, ContestRank::Master) => Ribbons::COOL_MASTER_RIBBON,
+            (Condition::Beautiful, ContestRank::Normal) => Ribbons::BEAUTY_RIBBON,
+            (Condition::Beautiful, ContestRank::Super) => Ribbons::BEAUTY_SUPER_RIBBON,
+            (Condition::Beautiful, ContestRank::Hyper) => Ribbons::BEAUTY_HYPER_RIBBON,
+            (Condition::Beautiful, ContestRank::Master) => Ribbons::BEAUTY_MASTER_RIBBON,
+            (Condition::Cute, ContestRank::Normal) => Ribbons::CUTE_RIBBON,
+            (Condition::Cute, ContestRank::Super) => Ribbons::CUTE_SUPER_RIBBON,
+            (Condition::Cute, ContestRank::Hyper) => Ribbons::CUTE_HYPER_RIBBON,
+            (Condition::Cute, ContestRank::Master) => Ribbons::CUTE_MASTER_RIBBON,
+            (Condition::Clever, ContestRank::Normal) => Ribbons::SMART_RIBBON,
+            (Condition::Clever, ContestRank::Super) => Ribbons::SMART_SUPER_RIBBON,
+            (Condition::Clever, ContestRank::Hyper) => Ribbons::SMART_HYPER_RIBBON,
+            (Condition::Clever, ContestRank::Master) => Ribbons::SMART_MASTER_RIBBON,
+            (Condition::Tough, ContestRank::Normal) => Ribbons::TOUGH_RIBBON,
+            (Condition::Tough, ContestRank::Super) => Ribbons::TOUGH_SUPER_RIBBON,
+            (Condition::Tough, ContestRank::Hyper) => Ribbons::TOUGH_HYPER_RIBBON,
+            (Condition::Tough, ContestRank::Master) => Ribbons::TOUGH_MASTER_RIBBON
+        }
+    }
+
+    /// Get the rank below this one, whose ribbon is needed to enter
+    pub fn previous(&self) -> Option<ContestRank> {
+        match self {
+            ContestRank::Normal => None,
+            ContestRank::Super => Some(ContestRank::Normal),
+            ContestRank::Hyper => Some(ContestRank::Super),
+            ContestRank::Master => Some(ContestRank::Hyper)
+        }
+    }
+
+    /// Check if a Pokemon can enter a contest of this rank and condition
+    pub fn can_enter(&self, pokemon: &Pokemon, condition: Condition) -> bool {
+        !pokemon.egg && self.previous()
+            .is_none_or(|r| pokemon.contest.ribbons.contains(r.ribbon(condition)))
+    }
+}
+
+/// Get the scarf which shows off a condition
+fn scarf(condition: Condition) -> Item {
+    match condition {
+        Condition::Cool => Item::RedScarf,
+        Condition::Beautiful => Item::BlueScarf,
+        Condition::Cute => Item::PinkScarf,
+        Condition::Clever => Item::GreenScarf,
+        Condition::Tough => Item::YellowScarf
+    }
+}
+
+/// Get a Pokemon's score in the primary judging, which is based on its condition.
+/// The contest's condition counts fully, and its two neighbors and the Pokemon's sheen count for half.
+/// Holding the matching scarf raises the contest's condition by a tenth.
+pub fn primary_score(pokemon: &Pokemon, condition: Condition) -> i16 {
+    let stats = &pokemon.contest;
+    let main = i16::from(stats.get_condition(&condition));
+    let main = if pokemon.is_holding(&scarf(condition)) { main + main / 10 } else { main };
+    let [left, right] = condition.neighbors();
+    let others = i16::from(stats.get_condition(&left)) + i16::from(stats.get_condition(&right)) + i16::from(stats.feel);
+    main + others / 2
+}
+
+/// One Pokemon competing in a contest
+#[derive(Debug, Clone)]
+struct Contestant {
+    moves: Vec<Move>,
+    primary: i16,
+    appeal: i16,
+    last_move: Option<Move>,
+    last_appeal: i16
+}
+
+/// Something that happened during an appeal turn, so it can be displayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContestEvent {
+    /// A contestant used a move, making this much appeal before any jamming
+    Appealed { contestant: usize, attack: Move, appeal: i16 },
+    /// The move made a combo with the contestant's previous move
+    Combo(usize),
+    /// The contestant used the same move twice in a row, and lost appeal
+    Repeated(usize),
+    /// A contestant was jammed, losing appeal
+    Jammed { contestant: usize, by: usize, amount: i16 },
+    /// A contestant avoided being jammed
+    Protected { contestant: usize, by: usize },
+    /// The move matched the contest, and excited the crowd to this level
+    Excited { contestant: usize, excitement: u8 },
+    /// The crowd's excitement was maxed out, giving a large bonus
+    CrowdWentWild(usize),
+    /// The move opposed the contest, and disappointed the crowd
+    Disappointed(usize)
+}
+
+/// Reasons a turn of a contest could not be played
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContestError {
+    /// Every appeal turn has already been played
+    Finished,
+    /// Each contestant must choose exactly one move
+    WrongMoveCount(usize),
+    /// A contestant chose a move it does not know
+    UnknownMove(usize, Move),
+    /// A move has no contest data
+    MissingContestData(Move)
+}
+impl Display for ContestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContestError::Finished => write!(f, "The contest is already finished"),
+            ContestError::WrongMoveCount(c) => write!(f, "Expected one move for each contestant, got {}", c),
+            ContestError::UnknownMove(c, m) => write!(f, "Contestant {} does not know {:?}", c, m),
+            ContestError::MissingContestData(m) => write!(f, "Missing contest data for {:?}", m)
+        }
+    }
+}
+impl Error for ContestError {}
+
+/// A Hoenn-style contest. Contestants are first judged on their condition,
+/// then make appeals with their moves over several turns to win over the crowd.
+#[derive(Debug, Clone)]
+pub struct Contest {
+    pub condition: Condition,
+    pub rank: ContestRank,
+    contestants: Vec<Contestant>,
+    /// The order contestants appeal in this turn
+    order: Vec<usize>,
+    turn: usize,
+    excitement: u8
+}
+impl Contest {
+    /// Start a contest. Contestants are numbered in the order given.
+    /// The first turn's appeal order is decided by the primary judging.
+    pub fn new(condition: Condition, rank: ContestRank, pokemon: &[&Pokemon]) -> Contest {
+        let contestants = pokemon.iter()
+            .map(|p| Contestant {
+                moves: p.get_moves(),
+                primary: primary_score(p, condition),
+                appeal: 0,
+                last_move: None,
+                last_appeal: 0
+            })
+            .collect::<Vec<_>>();
+        let mut order = (0..contestants.len()).collect::<Vec<_>>();
+        order.sort_by_key(|c| -contestants[*c].primary);
+        Contest { condition, rank, contestants, order, turn: 0, excitement: 0 }
+    }
+
+    /// Get the order contestants will appeal in next turn
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Get the crowd's current excitement
+    pub fn excitement(&self) -> u8 {
+        self.excitement
+    }
+
+    /// Check if every appeal turn has been played
+    pub fn is_finished(&self) -> bool {
+        self.turn >= APPEAL_TURNS
+    }
+
+    /// Get a contestant's total score so far: their primary judging plus every appeal
+    pub fn score(&self, contestant: usize) -> i16 {
+        self.contestants.get(contestant).map_or(0, |c| c.primary + c.appeal)
+    }
+
+    /// Play one appeal turn. `moves` is the move each contestant uses, in contestant order.
+    pub fn play_turn(&mut self, moves: &[Move], lookup: &ContestMoveLookup) -> Result<Vec<ContestEvent>, ContestError> {
+        if self.is_finished() {
+            return Err(ContestError::Finished);
+        }
+        if moves.len() != self.contestants.len() {
+            return Err(ContestError::WrongMoveCount(moves.len()));
+        }
+        for (idx, attack) in moves.iter().enumerate() {
+            if !self.contestants[idx].moves.contains(attack) {
+                return Err(ContestError::UnknownMove(idx, *attack));
+            }
+            if !lookup.contains_key(attack) {
+                return Err(ContestError::MissingContestData(*attack));
+            }
+        }
+
+        let mut events = Vec::new();
+        let mut turn_appeal = vec![0i16; self.contestants.len()];
+        let mut protected = vec![false; self.contestants.len()];
+        let mut appealed: Vec<usize> = Vec::new();
+        let mut first = None;
+        let mut last = None;
+        for idx in self.order.clone() {
+            let data = &lookup[&moves[idx]];
+            let contestant = &self.contestants[idx];
+            let mut appeal = i16::from(data.appeal) * POINTS_PER_HEART;
+            events.push(ContestEvent::Appealed { contestant: idx, attack: data.id, appeal });
+
+            let combo = contestant.last_move
+                .and_then(|m| lookup.get(&m))
+                .is_some_and(|previous| previous.combos.contains(&data.id));
+            if combo {
+                appeal += i16::from(data.appeal) * POINTS_PER_HEART;
+                events.push(ContestEvent::Combo(idx));
+            } else if contestant.last_move == Some(data.id) {
+                appeal -= POINTS_PER_HEART;
+                events.push(ContestEvent::Repeated(idx));
+            }
+
+            if data.condition == self.condition {
+                self.excitement += 1;
+                if self.excitement >= MAX_EXCITEMENT {
+                    self.excitement = 0;
+                    appeal += MAX_EXCITEMENT_BONUS;
+                    events.push(ContestEvent::CrowdWentWild(idx));
+                } else {
+                    appeal += i16::from(self.excitement) * POINTS_PER_HEART;
+                    events.push(ContestEvent::Excited { contestant: idx, excitement: self.excitement });
+                }
+            } else if self.condition.opposites().contains(&data.condition) {
+                self.excitement = self.excitement.saturating_sub(1);
+                appeal -= POINTS_PER_HEART;
+                events.push(ContestEvent::Disappointed(idx));
+            }
+
+            let jammed = match data.effect {
+                ContestEffect::JamAll => appealed.clone(),
+                ContestEffect::JamPrevious => appealed.last().copied().into_iter().collect(),
+                _ => Vec::new()
+            };
+            for target in jammed {
+                if protected[target] {
+                    events.push(ContestEvent::Protected { contestant: target, by: idx });
+                } else {
+                    let amount = i16::from(data.jam) * POINTS_PER_HEART;
+                    turn_appeal[target] -= amount;
+                    events.push(ContestEvent::Jammed { contestant: target, by: idx, amount });
+                }
+            }
+            match data.effect {
+                ContestEffect::Protect => protected[idx] = true,
+                ContestEffect::NextFirst => first = Some(idx),
+                ContestEffect::NextLast => last = Some(idx),
+                _ => {}
+            }
+
+            turn_appeal[idx] += appeal;
+            appealed.push(idx);
+        }
+
+        for (idx, contestant) in self.contestants.iter_mut().enumerate() {
+            contestant.appeal += turn_appeal[idx];
+            contestant.last_appeal = turn_appeal[idx];
+            contestant.last_move = Some(moves[idx]);
+        }
+
+        // Those who appealed best go first next turn, unless a move changed the order
+        let mut order = (0..self.contestants.len()).collect::<Vec<_>>();
+        order.sort_by_key(|c| -self.contestants[*c].last_appeal);
+        if let Some(first) = first {
+            order.retain(|c| *c != first);
+            order.insert(0, first);
+        }
+        if let Some(last) = last {
+            order.retain(|c| *c != last);
+            order.push(last);
+        }
+        self.order = order;
+        self.turn += 1;
+        Ok(events)
+    }
+
+    /// Get the contestants ranked from first place to last.
+    /// Ties are broken by the primary judging, then by contestant order.
+    pub fn placements(&self) -> Vec<usize> {
+        let mut placements = (0..self.contestants.len()).collect::<Vec<_>>();
+        placements.sort_by_key(|c| (-self.score(*c), -self.contestants[*c].primary));
+        placements
+    }
+
+    /// Give the winner this contest's ribbon, once every turn has been played.
+    /// Returns true if the Pokemon was the winner.
+    pub fn award_ribbon(&self, contestant: usize, pokemon: &mut Pokemon) -> bool {
+        let won = self.is_finished() && self.placements().first() == Some(&contestant);
+        if won {
+            pokemon.contest.ribbons.insert(self.rank.ribbon(self.condition));
+        }
+        won
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::pikachu;
+
+    use super::*;
+
+    fn contestant(moves: &[Move], coolness: u8) -> Pokemon {
+        let mut pokemon = pikachu(20, moves).pokemon;
+        pokemon.contest.coolness = coolness;
+        pokemon
+    }
+
+    fn lookup() -> ContestMoveLookup {
+        let data = |id, condition, appeal, jam, effect, combos: &[Move]| (id, ContestMove { id, condition, appeal, jam, effect, combos: combos.to_vec() });
+        vec![
+            data(Move::Thunderbolt, Condition::Cool, 2, 0, ContestEffect::None, &[]),
+            data(Move::Growl, Condition::Cute, 1, 3, ContestEffect::JamAll, &[]),
+            data(Move::TailWhip, Condition::Cute, 1, 0, ContestEffect::Protect, &[]),
+            data(Move::Charge, Condition::Clever, 1, 0, ContestEffect::NextFirst, &[Move::Thunderbolt])
+        ].into_iter().collect()
+    }
+
+    #[test]
+    fn primary_judging() {
+        let mut pokemon = contestant(&[], 100);
+        pokemon.contest.beauty = 20;
+        pokemon.contest.toughness = 40;
+        pokemon.contest.feel = 10;
+        assert_eq!(primary_score(&pokemon, Condition::Cool), 135);
+        pokemon.held_item = Some(Item::RedScarf);
+        assert_eq!(primary_score(&pokemon, Condition::Cool), 145);
+
+        let contest = Contest::new(Condition::Cool, ContestRank::Normal, &[&contestant(&[], 10), &pokemon]);
+        assert_eq!(contest.order(), &[1, 0]);
+    }
+
+    #[test]
+    fn appeals() {
+        let lookup = lookup();
+        let moves = [Move::Thunderbolt, Move::Growl, Move::TailWhip, Move::Charge];
+        let a = contestant(&moves, 50);
+        let b = contestant(&moves, 10);
+        let mut contest = Contest::new(Condition::Cool, ContestRank::Normal, &[&a, &b]);
+        assert_eq!(contest.play_turn(&[Move::Thunderbolt], &lookup), Err(ContestError::WrongMoveCount(1)));
+        assert_eq!(contest.play_turn(&[Move::Surf, Move::Growl], &lookup), Err(ContestError::UnknownMove(0, Move::Surf)));
+
+        // A appeals first, gets jammed by B, and B disappoints the crowd
+        let events = contest.play_turn(&[Move::Thunderbolt, Move::Growl], &lookup).unwrap();
+        assert!(events.contains(&ContestEvent::Excited { contestant: 0, excitement: 1 }));
+        assert!(events.contains(&ContestEvent::Jammed { contestant: 0, by: 1, amount: 30 }));
+        assert!(events.contains(&ContestEvent::Disappointed(1)));
+        assert_eq!(contest.score(0), 50 + 20 + 10 - 30);
+        assert_eq!(contest.excitement(), 0);
+
+        // B appeals better, so would go first, but Charge moves A first for the next turn
+        contest.play_turn(&[Move::Charge, Move::Thunderbolt], &lookup).unwrap();
+        assert_eq!(contest.order(), &[0, 1]);
+
+        // Charge into Thunderbolt is a combo
+        let events = contest.play_turn(&[Move::Thunderbolt, Move::TailWhip], &lookup).unwrap();
+        assert!(events.contains(&ContestEvent::Combo(0)));
+
+        let events = contest.play_turn(&[Move::Thunderbolt, Move::Growl], &lookup).unwrap();
+        assert!(events.contains(&ContestEvent::Repeated(0)));
+        assert!(!contest.is_finished());
+        let mut winner = a.clone();
+        assert!(!contest.award_ribbon(0, &mut winner));
+
+        contest.play_turn(&[Move::TailWhip, Move::Growl], &lookup).unwrap();
+        assert!(contest.is_finished());
+        assert_eq!(contest.play_turn(&[Move::Thunderbolt, Move::Growl], &lookup), Err(ContestError::Finished));
+        assert_eq!(contest.placements(), vec![0, 1]);
+        assert!(contest.award_ribbon(0, &mut winner));
+        assert!(!contest.award_ribbon(1, &mut b.clone()));
+        assert!(ContestRank::Super.can_enter(&winner, Condition::Cool));
+        assert!(!ContestRank::Super.can_enter(&b, Condition::Cool));
+    }
+
+    #[test]
+    fn shipped_contest_moves() {
+        let yaml = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../glazed-dx/assets/pkmn/contest.appeals")).unwrap();
+        let moves: Vec<ContestMove> = serde_yaml::from_str(&yaml).unwrap();
+        let lookup = moves.iter().cloned().map(|m| (m.id, m)).collect::<ContestMoveLookup>();
+        assert_eq!(lookup.len(), moves.len(), "A move is listed more than once");
+        for m in moves.iter() {
+            assert!(m.combos.iter().all(|c| lookup.contains_key(c)), "{:?} combos into a move with no contest data", m.id);
+        }
+
+        // Rain Dance sets up Thunder
+        let pokemon = contestant(&[Move::RainDance, Move::Thunder], 100);
+        let mut contest = Contest::new(Condition::Tough, ContestRank::Normal, &[&pokemon]);
+        contest.play_turn(&[Move::RainDance], &lookup).unwrap();
+        let events = contest.play_turn(&[Move::Thunder], &lookup).unwrap();
+        assert!(events.contains(&ContestEvent::Combo(0)));
+    }
+}
@@ -1,10 +1,22 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::item::Berry;
 use crate::pokemon::{Nature, Pokemon, PokemonContestStats};
 
-#[derive(Debug, Copy, Clone, Deserialize)]
+pub mod blender;
+pub mod case;
+pub mod engine;
+
+/// The most sheen a Pokemon can have. Once reached, it can't eat any more Pokeblocks.
+pub const MAX_SHEEN: u8 = 255;
+
+/// The five conditions, which are also the five contest categories.
+/// Each condition neighbors the ones before and after it, in a cycle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Condition {
     Cool,
     Beautiful,
@@ -12,6 +24,26 @@ pub enum Condition {
     Clever,
     Tough
 }
+impl Condition {
+    /// Every condition, in order around the condition pentagon
+    pub const ALL: [Condition; 5] = [Condition::Cool, Condition::Beautiful, Condition::Cute, Condition::Clever, Condition::Tough];
+
+    fn position(&self) -> usize {
+        Condition::ALL.iter().position(|c| c == self).unwrap_or_default()
+    }
+
+    /// Get the two conditions next to this one on the condition pentagon
+    pub fn neighbors(&self) -> [Condition; 2] {
+        let idx = self.position();
+        [Condition::ALL[(idx + 4) % 5], Condition::ALL[(idx + 1) % 5]]
+    }
+
+    /// Get the two conditions opposite this one on the condition pentagon
+    pub fn opposites(&self) -> [Condition; 2] {
+        let idx = self.position();
+        [Condition::ALL[(idx + 2) % 5], Condition::ALL[(idx + 3) % 5]]
+    }
+}
 impl From<BerryFlavor> for Condition {
     fn from(b: BerryFlavor) -> Self {
         match b {
@@ -24,6 +56,11 @@ impl From<BerryFlavor> for Condition {
     }
 }
 impl PokemonContestStats {
+    /// Get every condition, in the order cool, beautiful, cute, clever, tough
+    pub fn conditions(&self) -> [u8; 5] {
+        [self.coolness, self.beauty, self.cuteness, self.smartness, self.toughness]
+    }
+
     pub fn get_condition(&self, condition: &Condition) -> u8 {
         match condition {
             Condition::Cool => self.coolness,
@@ -44,7 +81,7 @@ pub enum BerryFlavor {
     Sour
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum FlavorPreference {
     Liked,
     Neutral,
@@ -83,13 +120,13 @@ impl FlavorPreference {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PokeblockColor {
     Black, Red, Blue, Pink, Green, Yellow, Gold,
     Purple, Indigo, Brown, LightBlue, Olive, Gray, White
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Pokeblock {
     pub spicy: u8,
     pub dry: u8,
@@ -97,7 +134,7 @@ pub struct Pokeblock {
     pub bitter: u8,
     pub sour: u8,
     /// Represents how "full" the Pokeblock makes a Pokemon. Added directly to a Pokemon's sheen when
-    /// eaten. When sheen is at 255, no more Pokeblocks can be eaten.
+    /// eaten, up to MAX_SHEEN. When sheen is at MAX_SHEEN, no more Pokeblocks can be eaten.
     pub feel: u8,
     /// The maximum flavor value.
     pub level: u8,
@@ -111,8 +148,8 @@ pub struct Pokeblock {
     pub color: PokeblockColor
 }
 impl Pokeblock {
-    /// Generate a black Pokeblock
-    pub fn create_black_pokeblock() -> Pokeblock {
+    /// Generate a black Pokeblock, which has three random flavors
+    pub fn create_black_pokeblock<R: Rng + ?Sized>(rng: &mut R) -> Pokeblock {
         let mut block = Pokeblock {
             spicy: 0,
             dry: 0,
@@ -125,7 +162,7 @@ impl Pokeblock {
         };
         let mut counter = 0;
         while counter < 3 {
-            match rand::thread_rng().gen_range(0..5) {
+            match rng.gen_range(0..5) {
                 0 if block.spicy == 0 => { counter += 1; block.spicy = 2; }
                 1 if block.dry == 0 => { counter += 1; block.dry = 2; }
                 2 if block.sweet == 0 => { counter += 1; block.sweet = 2; }
@@ -163,25 +200,74 @@ impl Pokeblock {
         FlavorPreference::Neutral
     }
 }
+
+/// How a Pokemon reacted to eating a Pokeblock, for display
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokeblockReaction {
+    /// How happy the Pokemon was to eat the Pokeblock
+    pub preference: FlavorPreference,
+    /// How much each condition rose, in the order cool, beautiful, cute, clever, tough
+    pub conditions: [u8; 5],
+    /// How much the Pokemon's sheen rose
+    pub sheen: u8
+}
+
+/// Reasons a Pokemon could not eat a Pokeblock
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FeedError {
+    /// Eggs can't eat
+    Egg,
+    /// The Pokemon's sheen is at MAX_SHEEN
+    TooFull,
+    /// There is no Pokeblock in that slot of the case
+    NoPokeblock
+}
+impl Display for FeedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedError::Egg => write!(f, "An egg can't eat Pokeblocks"),
+            FeedError::TooFull => write!(f, "The Pokemon is too full to eat any more Pokeblocks"),
+            FeedError::NoPokeblock => write!(f, "There is no Pokeblock there")
+        }
+    }
+}
+impl Error for FeedError {}
+
 impl Pokemon {
     /// Check if this Pokemon can eat a Pokeblock
     pub fn can_eat_pokeblock(&self) -> bool {
-        self.contest.feel == u8::MAX
+        !self.egg && self.contest.feel < MAX_SHEEN
     }
 
-    /// Feed this Pokemon a Pokeblock
+    /// Feed this Pokemon a Pokeblock. Sheen rises by the Pokeblock's feel, but never past MAX_SHEEN.
     /// Returns: How happy this Pokemon was to eat the Pokeblock, based on their nature and the
-    /// Pokeblock's flavors.
-    pub fn feed_pokeblock(&mut self, block: Pokeblock) -> FlavorPreference {
-        let Pokeblock { spicy, dry, sweet, bitter, sour, feel, .. } = block;
+    /// Pokeblock's flavors, and how much its condition rose.
+    pub fn feed_pokeblock(&mut self, block: &Pokeblock) -> Result<PokeblockReaction, FeedError> {
+        if self.egg {
+            return Err(FeedError::Egg);
+        }
+        if !self.can_eat_pokeblock() {
+            return Err(FeedError::TooFull);
+        }
+        let Pokeblock { spicy, dry, sweet, bitter, sour, feel, .. } = *block;
+        let start_feel = self.contest.feel;
         self.contest.feel = self.contest.feel.saturating_add(feel);
+        let before = self.contest.conditions();
         self.contest.coolness = self.contest.coolness.saturating_add(self.get_increase_value(Condition::Cool, spicy));
         self.contest.beauty = self.contest.beauty.saturating_add(self.get_increase_value(Condition::Beautiful, dry));
         self.contest.cuteness = self.contest.cuteness.saturating_add(self.get_increase_value(Condition::Cute, sweet));
         self.contest.smartness = self.contest.smartness.saturating_add(self.get_increase_value(Condition::Clever, bitter));
         self.contest.toughness = self.contest.toughness.saturating_add(self.get_increase_value(Condition::Tough, sour));
 
-        block.get_happiness_for_nature(&self.nature)
+        let mut conditions = self.contest.conditions();
+        for (after, before) in conditions.iter_mut().zip(before.iter()) {
+            *after -= before;
+        }
+        Ok(PokeblockReaction {
+            preference: block.get_happiness_for_nature(&self.nature),
+            conditions,
+            sheen: self.contest.feel - start_feel
+        })
     }
 
     fn get_increase_value(&self, condition: Condition, value: u8) -> u8 {
@@ -1,4 +1,4 @@
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::Error;
 
 #[derive(Debug, Clone, Copy)]
@@ -12,9 +12,40 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for OneOrTwo<T> {
         if s.len() == 1 {
             Ok(Self::One(s.pop().unwrap()))
         } else if s.len() == 2 {
-            Ok(Self::Two(s.pop().unwrap(), s.pop().unwrap()))
+            let second = s.pop().unwrap();
+            let first = s.pop().unwrap();
+            Ok(Self::Two(first, second))
         } else {
             Err(D::Error::invalid_length(s.len(), &"One or two types only"))
         }
     }
-}
\ No newline at end of file
+}
+impl<T: Serialize> Serialize for OneOrTwo<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match self {
+            OneOrTwo::One(a) => [a].serialize(serializer),
+            OneOrTwo::Two(a, b) => [a, b].serialize(serializer)
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::types::Type;
+
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        match serde_yaml::from_str::<OneOrTwo<Type>>("[Grass, Poison]").unwrap() {
+            OneOrTwo::Two(Type::Grass, Type::Poison) => {},
+            other => panic!("Expected Two(Grass, Poison), got {:?}", other)
+        }
+        let yaml = serde_yaml::to_string(&OneOrTwo::Two(Type::Water, Type::Ground)).unwrap();
+        match serde_yaml::from_str::<OneOrTwo<Type>>(&yaml).unwrap() {
+            OneOrTwo::Two(Type::Water, Type::Ground) => {},
+            other => panic!("Expected Two(Water, Ground), got {:?}", other)
+        }
+        assert!(matches!(serde_yaml::from_str::<OneOrTwo<Type>>("[Fire]").unwrap(), OneOrTwo::One(Type::Fire)));
+        assert!(serde_yaml::from_str::<OneOrTwo<Type>>("[Fire, Water, Grass]").is_err());
+    }
+}
@@ -4,9 +4,10 @@ use serde::Deserialize;
 
 use crate::attack::Move;
 use crate::contest::Condition;
+use crate::friendship;
 use crate::time::TimeOfDay;
 use crate::item::{EvolutionStone, Item};
-use crate::pokemon::Gender;
+use crate::pokemon::{Gender, Pokemon};
 use crate::species::Species;
 use crate::types::Type;
 
@@ -96,6 +97,38 @@ pub enum EvolutionCondition {
     EqualAttackAndDefense
 }
 
+impl EvolutionCondition {
+    /// Check if a Pokemon meets this condition, using only what is known about the Pokemon itself.
+    /// Conditions which depend on the world, such as the time or the party, are never met here.
+    pub fn is_met_by(&self, pokemon: &Pokemon) -> bool {
+        match self {
+            EvolutionCondition::Level(level) => pokemon.level >= *level,
+            EvolutionCondition::Gender(gender) => pokemon.gender == *gender,
+            EvolutionCondition::HighFriendship => friendship::has_high_friendship(pokemon.friendship),
+            EvolutionCondition::HighCondition(condition) => {
+                let contest = &pokemon.contest;
+                let value = match condition {
+                    Condition::Cool => contest.coolness,
+                    Condition::Beautiful => contest.beauty,
+                    Condition::Cute => contest.cuteness,
+                    Condition::Clever => contest.smartness,
+                    Condition::Tough => contest.toughness
+                };
+                value >= 170
+            },
+            EvolutionCondition::HoldingItem(item) => pokemon.is_holding(item),
+            EvolutionCondition::KnowsMove(attack) => pokemon.knows_move(*attack),
+            EvolutionCondition::LowPersonality => (pokemon.personality >> 16) % 10 < 5,
+            EvolutionCondition::HighPersonality => (pokemon.personality >> 16) % 10 >= 5,
+            EvolutionCondition::HigherAttackThanDefense => pokemon.attack.value > pokemon.defense.value,
+            EvolutionCondition::HigherDefenseThanAttack => pokemon.attack.value < pokemon.defense.value,
+            EvolutionCondition::EqualAttackAndDefense => pokemon.attack.value == pokemon.defense.value,
+            EvolutionCondition::TimeOfDay(_) | EvolutionCondition::KnowsMoveOfType(_) |
+            EvolutionCondition::WithPartyPokemon(_) | EvolutionCondition::AtPlace(_) => false
+        }
+    }
+}
+
 /// Represents a type of location that can trigger Evolution
 #[derive(Debug, Deserialize)]
 pub enum EvolutionTriggerLocation {
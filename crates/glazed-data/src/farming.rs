@@ -0,0 +1,441 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::bag::Bag;
+use crate::item::{Berry, Item};
+use crate::locations::Location;
+
+/// The moisture of freshly watered soil
+pub const MAX_MOISTURE: u8 = 100;
+/// The moisture soil loses each hour
+pub const DRY_RATE: u8 = 4;
+/// The fewest berries a plant yields, no matter how dry its soil was
+pub const MIN_YIELD: u16 = 2;
+/// The number of times a plant regrows after dropping its berries, before it withers
+pub const REGROWTHS: u8 = 9;
+/// The number of times a plant in Gooey Mulch regrows
+pub const GOOEY_REGROWTHS: u8 = 14;
+
+const SECONDS_PER_HOUR: i64 = 60 * 60;
+
+/// The stages a berry plant passes through as it grows
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GrowthStage {
+    Planted,
+    Sprouted,
+    Taller,
+    Flowering,
+    /// The plant has berries, and can be harvested
+    Ripe
+}
+impl GrowthStage {
+    fn next(&self) -> GrowthStage {
+        match self {
+            GrowthStage::Planted => GrowthStage::Sprouted,
+            GrowthStage::Sprouted => GrowthStage::Taller,
+            GrowthStage::Taller => GrowthStage::Flowering,
+            GrowthStage::Flowering | GrowthStage::Ripe => GrowthStage::Ripe
+        }
+    }
+}
+
+/// Mulch, which is spread on empty soil to change how the plant grows
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Mulch {
+    /// The plant grows faster, but the soil dries faster
+    Growth,
+    /// The soil dries slower, but the plant grows slower
+    Damp,
+    /// The berries stay on the plant longer before dropping
+    Stable,
+    /// The plant regrows more times before withering
+    Gooey
+}
+impl Mulch {
+    /// Get the mulch an item represents, if any
+    pub fn from_item(item: &Item) -> Option<Mulch> {
+        match item {
+            Item::GrowthMulch => Some(Mulch::Growth),
+            Item::DampMulch => Some(Mulch::Damp),
+            Item::StableMulch => Some(Mulch::Stable),
+            Item::GooeyMulch => Some(Mulch::Gooey),
+            _ => None
+        }
+    }
+
+    pub fn item(&self) -> Item {
+        match self {
+            Mulch::Growth => Item::GrowthMulch,
+            Mulch::Damp => Item::DampMulch,
+            Mulch::Stable => Item::StableMulch,
+            Mulch::Gooey => Item::GooeyMulch
+        }
+    }
+}
+
+/// Reasons something could not be done to a plot of soil
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FarmingError {
+    /// Something is already growing in the soil
+    Occupied,
+    /// Nothing is growing in the soil
+    Empty,
+    /// The soil already has mulch
+    AlreadyMulched,
+    /// The plant has no berries to harvest yet
+    NotRipe,
+    /// The item is not mulch
+    NotMulch(Item),
+    NotInBag(Item),
+    /// The bag can't hold all the harvested berries
+    BagFull(Berry)
+}
+impl Display for FarmingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FarmingError::Occupied => write!(f, "Something is already growing here"),
+            FarmingError::Empty => write!(f, "Nothing is growing here"),
+            FarmingError::AlreadyMulched => write!(f, "The soil already has mulch"),
+            FarmingError::NotRipe => write!(f, "The plant has no berries yet"),
+            FarmingError::NotMulch(i) => write!(f, "{:?} is not mulch", i),
+            FarmingError::NotInBag(i) => write!(f, "There is no {:?} in the bag", i),
+            FarmingError::BagFull(b) => write!(f, "The bag can't hold any more {:?}", b)
+        }
+    }
+}
+impl Error for FarmingError {}
+
+/// A berry plant growing in a plot of soil
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BerryPlant {
+    pub berry: Berry,
+    pub stage: GrowthStage,
+    /// Hours spent in the current stage
+    hours_in_stage: u32,
+    moisture: u8,
+    /// Hours spent growing in dry soil, which lowers the yield
+    dry_hours: u32,
+    /// Times the plant has dropped its berries and regrown
+    regrowths: u8
+}
+
+/// A plot of soil where berries can be planted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoilPlot {
+    pub location: Location,
+    /// Which plot this is, of those at the location
+    pub slot: u8,
+    mulch: Option<Mulch>,
+    plant: Option<BerryPlant>,
+    /// The last time the plot was updated, in seconds since the Unix epoch
+    last_update: Option<i64>
+}
+impl SoilPlot {
+    pub fn new(location: Location, slot: u8) -> SoilPlot {
+        SoilPlot { location, slot, mulch: None, plant: None, last_update: None }
+    }
+
+    pub fn mulch(&self) -> Option<Mulch> {
+        self.mulch
+    }
+
+    pub fn plant(&self) -> Option<&BerryPlant> {
+        self.plant.as_ref()
+    }
+
+    /// Get the soil's moisture. Empty soil is always dry.
+    pub fn moisture(&self) -> u8 {
+        self.plant.as_ref().map_or(0, |p| p.moisture)
+    }
+
+    /// Spread mulch on the soil. Mulch can only be spread before planting.
+    pub fn apply_mulch(&mut self, mulch: Mulch) -> Result<(), FarmingError> {
+        if self.plant.is_some() {
+            return Err(FarmingError::Occupied);
+        }
+        if self.mulch.is_some() {
+            return Err(FarmingError::AlreadyMulched);
+        }
+        self.mulch = Some(mulch);
+        Ok(())
+    }
+
+    /// Spread mulch from the bag on the soil, using it up
+    pub fn apply_mulch_from_bag(&mut self, item: &Item, bag: &mut Bag) -> Result<(), FarmingError> {
+        let mulch = Mulch::from_item(item).ok_or_else(|| FarmingError::NotMulch(item.clone()))?;
+        if !bag.contains(item) {
+            return Err(FarmingError::NotInBag(item.clone()));
+        }
+        self.apply_mulch(mulch)?;
+        bag.remove(item, 1).map_err(|_| FarmingError::NotInBag(item.clone()))
+    }
+
+    /// Plant a berry in the soil. The soil starts out watered.
+    pub fn plant_berry(&mut self, berry: Berry, now: NaiveDateTime) -> Result<(), FarmingError> {
+        if self.plant.is_some() {
+            return Err(FarmingError::Occupied);
+        }
+        self.plant = Some(BerryPlant {
+            berry,
+            stage: GrowthStage::Planted,
+            hours_in_stage: 0,
+            moisture: MAX_MOISTURE,
+            dry_hours: 0,
+            regrowths: 0
+        });
+        self.last_update = Some(now.and_utc().timestamp());
+        Ok(())
+    }
+
+    /// Plant a berry from the bag, using it up
+    pub fn plant_from_bag(&mut self, berry: Berry, bag: &mut Bag, now: NaiveDateTime) -> Result<(), FarmingError> {
+        let item = Item::Berry(berry);
+        if !bag.contains(&item) {
+            return Err(FarmingError::NotInBag(item));
+        }
+        self.plant_berry(berry, now)?;
+        bag.remove(&item, 1).map_err(|_| FarmingError::NotInBag(item))
+    }
+
+    /// Water the soil, bringing it to MAX_MOISTURE
+    pub fn water(&mut self, now: NaiveDateTime) -> Result<(), FarmingError> {
+        self.update(now);
+        let plant = self.plant.as_mut().ok_or(FarmingError::Empty)?;
+        plant.moisture = MAX_MOISTURE;
+        Ok(())
+    }
+
+    /// Get the hours a berry spends in each stage before it is ripe, after mulch
+    fn growing_stage_hours(&self, berry: Berry) -> u32 {
+        let growth = berry.growth_hours();
+        let growth = match self.mulch {
+            Some(Mulch::Growth) => growth * 3 / 4,
+            Some(Mulch::Damp) => growth * 5 / 4,
+            _ => growth
+        };
+        (growth / 4).max(1)
+    }
+
+    /// Get the hours the plant spends in its current stage, after mulch
+    fn stage_hours(&self, plant: &BerryPlant) -> u32 {
+        match (plant.stage, self.mulch) {
+            (GrowthStage::Ripe, Some(Mulch::Stable)) => plant.berry.growth_hours() * 3 / 2,
+            (GrowthStage::Ripe, _) => plant.berry.growth_hours(),
+            _ => self.growing_stage_hours(plant.berry)
+        }
+    }
+
+    /// Get the number of berries the plant would yield, if harvested now
+    pub fn expected_yield(&self) -> u16 {
+        let plant = match &self.plant {
+            Some(p) => p,
+            None => return 0
+        };
+        let max = plant.berry.max_yield();
+        let growing_hours = self.growing_stage_hours(plant.berry) * 4;
+        let dry = plant.dry_hours.min(growing_hours);
+        let lost = u32::from(max - MIN_YIELD) * dry / growing_hours;
+        max - lost as u16
+    }
+
+    /// Grow the plant by one hour.
+    /// Ripe berries eventually drop, and the plant regrows, until it withers and leaves the soil empty.
+    fn grow_hour(&mut self) {
+        let dry_rate = match self.mulch {
+            Some(Mulch::Growth) => DRY_RATE * 3 / 2,
+            Some(Mulch::Damp) => DRY_RATE / 2,
+            _ => DRY_RATE
+        };
+        let max_regrowths = if self.mulch == Some(Mulch::Gooey) { GOOEY_REGROWTHS } else { REGROWTHS };
+        let stage_hours = match &self.plant {
+            Some(p) => self.stage_hours(p),
+            None => return
+        };
+        let plant = match self.plant.as_mut() {
+            Some(p) => p,
+            None => return
+        };
+        plant.moisture = plant.moisture.saturating_sub(dry_rate);
+        if plant.stage != GrowthStage::Ripe && plant.moisture == 0 {
+            plant.dry_hours += 1;
+        }
+        plant.hours_in_stage += 1;
+        if plant.hours_in_stage < stage_hours {
+            return;
+        }
+        plant.hours_in_stage = 0;
+        if plant.stage != GrowthStage::Ripe {
+            plant.stage = plant.stage.next();
+        } else if plant.regrowths < max_regrowths {
+            plant.regrowths += 1;
+            plant.stage = GrowthStage::Sprouted;
+            plant.dry_hours = 0;
+        } else {
+            self.plant = None;
+            self.mulch = None;
+        }
+    }
+
+    /// Grow the plant for every full hour that has passed since the last update.
+    /// Times are in UTC, so changes to the local time zone do not grow anything.
+    /// Setting the clock backwards does not grow, or undo growth of, anything, and the time
+    /// of the last update is kept so that moving the clock forward again does not count any hour twice.
+    pub fn update(&mut self, now: NaiveDateTime) {
+        let now = now.and_utc().timestamp();
+        let last = match self.last_update {
+            Some(l) if self.plant.is_some() => l,
+            _ => return
+        };
+        if now < last {
+            return;
+        }
+        let hours = (now - last) / SECONDS_PER_HOUR;
+        for _ in 0..hours {
+            if self.plant.is_none() {
+                break;
+            }
+            self.grow_hour();
+        }
+        // Keep the leftover part of an hour, so it counts towards the next one
+        self.last_update = Some(last + hours * SECONDS_PER_HOUR);
+    }
+
+    /// Pick the ripe berries, putting them in the bag. The plant and mulch are removed.
+    /// Returns the berry and how many were picked.
+    pub fn harvest(&mut self, bag: &mut Bag, now: NaiveDateTime) -> Result<(Berry, u16), FarmingError> {
+        self.update(now);
+        let plant = self.plant.as_ref().ok_or(FarmingError::Empty)?;
+        if plant.stage != GrowthStage::Ripe {
+            return Err(FarmingError::NotRipe);
+        }
+        let berry = plant.berry;
+        let amount = self.expected_yield();
+        bag.try_add(Item::Berry(berry), amount).map_err(|_| FarmingError::BagFull(berry))?;
+        self.plant = None;
+        self.mulch = None;
+        self.last_update = None;
+        Ok((berry, amount))
+    }
+}
+
+/// Every plot of soil the player has used
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BerryFarm {
+    plots: Vec<SoilPlot>
+}
+impl BerryFarm {
+    /// Get a plot of soil, if it has been used
+    pub fn plot(&self, location: Location, slot: u8) -> Option<&SoilPlot> {
+        self.plots.iter().find(|p| p.location == location && p.slot == slot)
+    }
+
+    /// Get a plot of soil, starting a new one if it has never been used
+    pub fn plot_mut(&mut self, location: Location, slot: u8) -> &mut SoilPlot {
+        match self.plots.iter().position(|p| p.location == location && p.slot == slot) {
+            Some(idx) => &mut self.plots[idx],
+            None => {
+                self.plots.push(SoilPlot::new(location, slot));
+                self.plots.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Iterate through the used plots of soil at a location
+    pub fn plots_at(&self, location: Location) -> impl Iterator<Item = &SoilPlot> {
+        self.plots.iter().filter(move |p| p.location == location)
+    }
+
+    /// Grow every plant for the time that has passed since it was last updated
+    pub fn update(&mut self, now: NaiveDateTime) {
+        for plot in self.plots.iter_mut() {
+            plot.update(now);
+        }
+    }
+
+    /// Grow every plant using the system's current time
+    pub fn update_now(&mut self) {
+        self.update(chrono::Utc::now().naive_utc());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    use super::*;
+
+    fn start() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2022, 6, 1).unwrap().and_hms_opt(12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn growing_and_harvesting() {
+        let mut farm = BerryFarm::default();
+        let mut bag = Bag::default();
+        bag.add(Item::Berry(Berry::OranBerry), 1);
+        bag.add(Item::DampMulch, 1);
+        let plot = farm.plot_mut(Location::FarawayPlace, 0);
+        assert_eq!(plot.water(start()), Err(FarmingError::Empty));
+        plot.apply_mulch_from_bag(&Item::DampMulch, &mut bag).unwrap();
+        plot.plant_from_bag(Berry::OranBerry, &mut bag, start()).unwrap();
+        assert_eq!(plot.plant_from_bag(Berry::OranBerry, &mut bag, start()), Err(FarmingError::NotInBag(Item::Berry(Berry::OranBerry))));
+        assert_eq!(bag.count(&Item::DampMulch), 0);
+        assert_eq!(plot.apply_mulch(Mulch::Growth), Err(FarmingError::Occupied));
+
+        // Damp Mulch makes Oran take 20 hours, and the soil stays moist the whole time
+        farm.update(start() + Duration::minutes(5 * 60 + 59));
+        let plot = farm.plot(Location::FarawayPlace, 0).unwrap();
+        assert_eq!(plot.plant().map(|p| p.stage), Some(GrowthStage::Sprouted));
+        assert_eq!(plot.moisture(), 90);
+        let plot = farm.plot_mut(Location::FarawayPlace, 0);
+        assert_eq!(plot.harvest(&mut bag, start() + Duration::hours(19)), Err(FarmingError::NotRipe));
+        assert_eq!(plot.harvest(&mut bag, start() + Duration::hours(20)), Ok((Berry::OranBerry, 5)));
+        assert_eq!(bag.count(&Item::Berry(Berry::OranBerry)), 5);
+        assert_eq!(plot.plant(), None);
+        assert_eq!(plot.mulch(), None);
+    }
+
+    #[test]
+    fn dry_soil_and_regrowth() {
+        let mut plot = SoilPlot::new(Location::FarawayPlace, 1);
+        plot.plant_berry(Berry::LumBerry, start()).unwrap();
+        plot.update(start() + Duration::hours(47));
+        assert_eq!(plot.moisture(), 0);
+        assert_eq!(plot.plant().map(|p| p.stage), Some(GrowthStage::Flowering));
+        plot.water(start() + Duration::hours(47)).unwrap();
+        assert_eq!(plot.moisture(), MAX_MOISTURE);
+        plot.update(start() + Duration::hours(48));
+        // Dry for 23 of 48 hours
+        assert_eq!(plot.expected_yield(), 4);
+
+        // Berries drop after another 48 hours, then the plant regrows and eventually withers
+        plot.update(start() + Duration::hours(96));
+        assert_eq!(plot.plant().map(|p| p.stage), Some(GrowthStage::Sprouted));
+        plot.update(start() + Duration::days(100));
+        assert_eq!(plot.plant(), None);
+
+        // The clock going backwards grows nothing, and setting it forward again only counts the time since planting
+        plot.plant_berry(Berry::OranBerry, start()).unwrap();
+        let mut control = plot.clone();
+        plot.update(start() - Duration::days(1));
+        assert_eq!(plot.plant().map(|p| p.stage), Some(GrowthStage::Planted));
+        plot.update(start() + Duration::hours(5));
+        control.update(start() + Duration::hours(5));
+        assert_eq!(plot, control);
+    }
+
+    #[test]
+    fn persistence() {
+        let mut farm = BerryFarm::default();
+        farm.plot_mut(Location::FarawayPlace, 2).plant_berry(Berry::SitrusBerry, start()).unwrap();
+        farm.update(start() + Duration::hours(10));
+
+        let yaml = serde_yaml::to_string(&farm).unwrap();
+        let loaded: BerryFarm = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(loaded.plot(Location::FarawayPlace, 2), farm.plot(Location::FarawayPlace, 2));
+        assert_eq!(loaded.plots_at(Location::FarawayPlace).count(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::abilities::Ability;
+use crate::attack::Weather;
+use crate::item::Item;
+use crate::pokemon::Pokemon;
+use crate::species::{CastformForm, CherrimForm, DarmanitanForm, ForcesOfNatureForm, GenesectForm, GiratinaForm, MeloettaForm, RotomForm, ShayminForm, Species};
+use crate::time::TimeOfDay;
+
+/// Something which may cause a Pokemon to change form
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FormTrigger {
+    /// The weather changed, or the Pokemon entered a battle with this weather (Forecast, Flower Gift)
+    Weather(Option<Weather>),
+    /// The turn ended, and the Pokemon's HP may have crossed a threshold (Zen Mode)
+    EndOfTurn,
+    /// The Pokemon used Relic Song
+    RelicSong,
+    /// The Pokemon's held item changed (Griseous Orb, Drives)
+    HeldItem,
+    /// The battle ended, reverting any form which only lasts in battle
+    BattleEnded,
+    /// Rotom was moved into an appliance. The Normal form takes it back out.
+    Appliance(RotomForm),
+    /// The time of day changed, or the Pokemon was withdrawn from the PC at this time
+    TimeOfDay(TimeOfDay),
+    /// The Pokemon was frozen
+    Frozen,
+    /// The Gracidea was used on the Pokemon, at this time of day
+    Gracidea(TimeOfDay),
+    /// The Reveal Glass was used on the Pokemon
+    RevealGlass
+}
+
+/// Get the form a Pokemon would change into, because of a trigger.
+/// The ability is passed separately, as it depends on the species data.
+/// Returns None if the Pokemon's form would not change.
+pub fn resulting_form(pokemon: &Pokemon, ability: Ability, trigger: FormTrigger) -> Option<Species> {
+    let form = match (pokemon.species, trigger) {
+        (Species::Castform(_), FormTrigger::Weather(weather)) => Species::Castform(match weather {
+            _ if ability != Ability::Forecast => CastformForm::Normal,
+            Some(Weather::HarshSun) => CastformForm::Sunny,
+            Some(Weather::Rain) => CastformForm::Rainy,
+            Some(Weather::Hail) => CastformForm::Snowy,
+            _ => CastformForm::Normal
+        }),
+        (Species::Cherrim(_), FormTrigger::Weather(weather)) => Species::Cherrim(match weather {
+            Some(Weather::HarshSun) if ability == Ability::FlowerGift => CherrimForm::Sunshine,
+            _ => CherrimForm::Overcast
+        }),
+        (Species::Darmanitan(_), FormTrigger::EndOfTurn) => Species::Darmanitan(
+            if ability == Ability::ZenMode && !pokemon.is_fainted() && pokemon.current_hp <= pokemon.hp.value / 2 {
+                DarmanitanForm::Zen
+            } else {
+                DarmanitanForm::Standard
+            }
+        ),
+        (Species::Meloetta(form), FormTrigger::RelicSong) => Species::Meloetta(match form {
+            MeloettaForm::Aria => MeloettaForm::Pirouette,
+            MeloettaForm::Pirouette => MeloettaForm::Aria
+        }),
+        (Species::Giratina(_), FormTrigger::HeldItem) => Species::Giratina(match pokemon.held_item {
+            Some(Item::GriseousOrb) => GiratinaForm::Origin,
+            _ => GiratinaForm::Altered
+        }),
+        (Species::Genesect(_), FormTrigger::HeldItem) => Species::Genesect(match &pokemon.held_item {
+            Some(item) if item.is_drive() => match item {
+                Item::ShockDrive => GenesectForm::Shock,
+                Item::BurnDrive => GenesectForm::Burn,
+                Item::ChillDrive => GenesectForm::Chill,
+                _ => GenesectForm::Douse
+            },
+            _ => GenesectForm::Normal
+        }),
+        (Species::Castform(_), FormTrigger::BattleEnded) => Species::Castform(CastformForm::Normal),
+        (Species::Cherrim(_), FormTrigger::BattleEnded) => Species::Cherrim(CherrimForm::Overcast),
+        (Species::Darmanitan(_), FormTrigger::BattleEnded) => Species::Darmanitan(DarmanitanForm::Standard),
+        (Species::Meloetta(_), FormTrigger::BattleEnded) => Species::Meloetta(MeloettaForm::Aria),
+        (Species::Rotom(_), FormTrigger::Appliance(form)) => Species::Rotom(form),
+        (Species::Shaymin(ShayminForm::Sky), FormTrigger::TimeOfDay(TimeOfDay::Night)) |
+        (Species::Shaymin(ShayminForm::Sky), FormTrigger::Frozen) => Species::Shaymin(ShayminForm::Land),
+        (Species::Shaymin(ShayminForm::Land), FormTrigger::Gracidea(TimeOfDay::Day)) if !pokemon.status.freeze => Species::Shaymin(ShayminForm::Sky),
+        (Species::Tornadus(form), FormTrigger::RevealGlass) => Species::Tornadus(toggle_nature_form(form)),
+        (Species::Thundurus(form), FormTrigger::RevealGlass) => Species::Thundurus(toggle_nature_form(form)),
+        (Species::Landorus(form), FormTrigger::RevealGlass) => Species::Landorus(toggle_nature_form(form)),
+        _ => return None
+    };
+
+    if form == pokemon.species { None } else { Some(form) }
+}
+
+fn toggle_nature_form(form: ForcesOfNatureForm) -> ForcesOfNatureForm {
+    match form {
+        ForcesOfNatureForm::Incarnate => ForcesOfNatureForm::Therian,
+        ForcesOfNatureForm::Therian => ForcesOfNatureForm::Incarnate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::battler;
+    use crate::core::OneOrTwo;
+    use crate::types::Type;
+
+    use super::*;
+
+    fn pokemon(species: Species) -> Pokemon {
+        battler(species, OneOrTwo::One(Type::Normal), Ability::RunAway, [50; 6], 50, &[]).pokemon
+    }
+
+    #[test]
+    fn form_triggers() {
+        let castform = pokemon(Species::Castform(CastformForm::Normal));
+        assert_eq!(resulting_form(&castform, Ability::Forecast, FormTrigger::Weather(Some(Weather::Rain))), Some(Species::Castform(CastformForm::Rainy)));
+        assert_eq!(resulting_form(&castform, Ability::Forecast, FormTrigger::Weather(None)), None);
+
+        let mut darmanitan = pokemon(Species::Darmanitan(DarmanitanForm::Standard));
+        darmanitan.current_hp = darmanitan.hp.value / 2;
+        assert_eq!(resulting_form(&darmanitan, Ability::ZenMode, FormTrigger::EndOfTurn), Some(Species::Darmanitan(DarmanitanForm::Zen)));
+        assert_eq!(resulting_form(&darmanitan, Ability::SheerForce, FormTrigger::EndOfTurn), None);
+
+        let mut genesect = pokemon(Species::Genesect(GenesectForm::Normal));
+        genesect.held_item = Some(Item::ChillDrive);
+        assert_eq!(resulting_form(&genesect, Ability::Download, FormTrigger::HeldItem), Some(Species::Genesect(GenesectForm::Chill)));
+
+        let shaymin = pokemon(Species::Shaymin(ShayminForm::Sky));
+        assert_eq!(resulting_form(&shaymin, Ability::SereneGrace, FormTrigger::TimeOfDay(TimeOfDay::Night)), Some(Species::Shaymin(ShayminForm::Land)));
+        assert_eq!(resulting_form(&shaymin, Ability::SereneGrace, FormTrigger::TimeOfDay(TimeOfDay::Day)), None);
+
+        let landorus = pokemon(Species::Landorus(ForcesOfNatureForm::Incarnate));
+        assert_eq!(resulting_form(&landorus, Ability::SandForce, FormTrigger::RevealGlass), Some(Species::Landorus(ForcesOfNatureForm::Therian)));
+    }
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use crate::item::{Item, Pokeball};
+use crate::pokemon::Pokemon;
+
+/// The most friendship a Pokemon can have
+pub const MAX_FRIENDSHIP: u8 = 255;
+/// The friendship needed to evolve through EvolutionCondition::HighFriendship
+pub const HIGH_FRIENDSHIP: u8 = 220;
+/// The friendship a Pokemon caught in a Friend Ball starts with
+pub const FRIEND_BALL_FRIENDSHIP: u8 = 200;
+/// The number of steps the player must walk for the party to gain friendship
+pub const STEPS_PER_FRIENDSHIP: u16 = 256;
+
+/// Something that happened to a Pokemon, which changes its friendship
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FriendshipEvent {
+    /// The player walked enough steps with the Pokemon in the party
+    Walked,
+    LevelUp,
+    /// A vitamin, such as Protein, was used on the Pokemon
+    Vitamin,
+    /// The Pokemon was in the party for a battle against a Gym Leader, Elite Four member, or Champion
+    BossBattle,
+    Fainted,
+    /// The Pokemon fainted to an opponent at least 30 levels higher than it
+    FaintedToStrongerFoe,
+    EnergyPowder,
+    EnergyRoot,
+    RevivalHerb
+}
+impl FriendshipEvent {
+    /// Get the event caused by using an item on a Pokemon, if it changes friendship
+    pub fn for_item(item: &Item) -> Option<FriendshipEvent> {
+        match item {
+            Item::HPUp | Item::Protein | Item::Iron | Item::Calcium | Item::Zinc | Item::Carbos => Some(FriendshipEvent::Vitamin),
+            Item::EnergyPowder => Some(FriendshipEvent::EnergyPowder),
+            Item::EnergyRoot => Some(FriendshipEvent::EnergyRoot),
+            Item::RevivalHerb => Some(FriendshipEvent::RevivalHerb),
+            _ => None
+        }
+    }
+
+    /// Get the change in friendship this event causes, before any boosts.
+    /// Pokemon with more friendship gain less from the same event, and dislike bitter medicine more.
+    pub fn delta(&self, friendship: u8) -> i16 {
+        let (low, mid, high) = match self {
+            FriendshipEvent::Walked => (1, 1, 1),
+            FriendshipEvent::LevelUp => (5, 4, 3),
+            FriendshipEvent::Vitamin => (5, 3, 2),
+            FriendshipEvent::BossBattle => (3, 2, 1),
+            FriendshipEvent::Fainted => (-1, -1, -1),
+            FriendshipEvent::FaintedToStrongerFoe => (-5, -5, -10),
+            FriendshipEvent::EnergyPowder => (-5, -5, -10),
+            FriendshipEvent::EnergyRoot => (-10, -10, -15),
+            FriendshipEvent::RevivalHerb => (-15, -15, -20)
+        };
+        match friendship {
+            0..=99 => low,
+            100..=199 => mid,
+            _ => high
+        }
+    }
+}
+
+/// Get the change in friendship an event causes for a specific Pokemon.
+/// Gains are raised by one if the Pokemon was caught in a Luxury Ball, and then by half if it holds a Soothe Bell.
+pub fn change(pokemon: &Pokemon, event: FriendshipEvent) -> i16 {
+    let delta = event.delta(pokemon.friendship);
+    if delta <= 0 {
+        return delta;
+    }
+    let delta = if pokemon.poke_ball == Pokeball::LuxuryBall { delta + 1 } else { delta };
+    if pokemon.is_holding(&Item::SootheBell) { delta * 3 / 2 } else { delta }
+}
+
+/// Change a Pokemon's friendship after an event.
+/// Returns the friendship before and after.
+pub fn apply(pokemon: &mut Pokemon, event: FriendshipEvent) -> (u8, u8) {
+    let start = pokemon.friendship;
+    let end = (i16::from(start) + change(pokemon, event)).clamp(0, i16::from(MAX_FRIENDSHIP)) as u8;
+    pokemon.friendship = end;
+    (start, end)
+}
+
+/// Get the friendship a newly caught Pokemon starts with
+pub fn starting_friendship(ball: Pokeball, base_friendship: u8) -> u8 {
+    match ball {
+        Pokeball::FriendBall => FRIEND_BALL_FRIENDSHIP,
+        _ => base_friendship
+    }
+}
+
+/// Check if a Pokemon is friendly enough to evolve by friendship
+pub fn has_high_friendship(friendship: u8) -> bool {
+    friendship >= HIGH_FRIENDSHIP
+}
+
+/// Get the power of Return, which is stronger the more friendly the user is
+pub fn return_power(friendship: u8) -> u16 {
+    (u16::from(friendship) * 10 / 25).max(1)
+}
+
+/// Get the power of Frustration, which is stronger the less friendly the user is
+pub fn frustration_power(friendship: u8) -> u16 {
+    (u16::from(MAX_FRIENDSHIP - friendship) * 10 / 25).max(1)
+}
+
+/// Counts the player's steps, so the party gains friendship as they walk
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct StepCounter(u16);
+impl StepCounter {
+    /// Count one step. Every STEPS_PER_FRIENDSHIP steps, each Pokemon in the party gains friendship.
+    /// Returns true if friendship was gained.
+    pub fn step(&mut self, party: &mut [Pokemon]) -> bool {
+        self.0 += 1;
+        if self.0 < STEPS_PER_FRIENDSHIP {
+            return false;
+        }
+        self.0 = 0;
+        for pokemon in party.iter_mut().filter(|p| !p.egg) {
+            apply(pokemon, FriendshipEvent::Walked);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::pikachu;
+
+    use super::*;
+
+    #[test]
+    fn events_and_boosts() {
+        let mut pokemon = pikachu(20, &[]).pokemon;
+        assert_eq!(apply(&mut pokemon, FriendshipEvent::LevelUp), (70, 75));
+        pokemon.friendship = 150;
+        assert_eq!(apply(&mut pokemon, FriendshipEvent::LevelUp), (150, 154));
+        assert_eq!(apply(&mut pokemon, FriendshipEvent::RevivalHerb), (154, 139));
+
+        pokemon.poke_ball = Pokeball::LuxuryBall;
+        pokemon.held_item = Some(Item::SootheBell);
+        assert_eq!(change(&pokemon, FriendshipEvent::Vitamin), 6);
+        assert_eq!(change(&pokemon, FriendshipEvent::Fainted), -1);
+
+        pokemon.friendship = 254;
+        assert_eq!(apply(&mut pokemon, FriendshipEvent::BossBattle), (254, MAX_FRIENDSHIP));
+        pokemon.friendship = 3;
+        assert_eq!(apply(&mut pokemon, FriendshipEvent::EnergyPowder), (3, 0));
+    }
+
+    #[test]
+    fn walking() {
+        let mut party = vec![pikachu(20, &[]).pokemon, pikachu(20, &[]).pokemon];
+        party[1].friendship = MAX_FRIENDSHIP;
+        let mut counter = StepCounter::default();
+        let gained = (0..STEPS_PER_FRIENDSHIP * 2).filter(|_| counter.step(&mut party)).count();
+        assert_eq!(gained, 2);
+        assert_eq!(party[0].friendship, 72);
+        assert_eq!(party[1].friendship, 255);
+    }
+
+    #[test]
+    fn thresholds() {
+        assert_eq!(starting_friendship(Pokeball::FriendBall, 70), FRIEND_BALL_FRIENDSHIP);
+        assert_eq!(starting_friendship(Pokeball::PokeBall, 70), 70);
+        assert!(has_high_friendship(220) && !has_high_friendship(219));
+        assert_eq!((return_power(255), frustration_power(255)), (102, 1));
+        assert_eq!((return_power(0), frustration_power(0)), (1, 102));
+    }
+}
@@ -7,7 +7,7 @@ use crate::attack::Move;
 use crate::types::Type;
 
 /// All Pokeballs in the game
-#[derive(Debug, Clone, Copy, Eq, PartialEq, EnumIter, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, EnumIter, Serialize, Deserialize)]
 pub enum Pokeball {
     PokeBall,
     GreatBall,
@@ -41,7 +41,7 @@ impl From<Pokeball> for Item {
 }
 
 /// All Evolution Stones in the game
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum EvolutionStone {
     FireStone,
     WaterStone,
@@ -59,7 +59,7 @@ impl From<EvolutionStone> for Item {
 }
 
 /// All berries in the game
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum Berry {
     CheriBerry,
     ChestoBerry,
@@ -156,6 +156,36 @@ impl Berry {
         }
     }
 
+    /// Get how many hours a planted berry takes to grow ripe
+    pub fn growth_hours(&self) -> u32 {
+        use Berry::*;
+        match self {
+            RazzBerry | BlukBerry | NanabBerry | WepearBerry | PinapBerry => 8,
+            CheriBerry | ChestoBerry | PechaBerry | RawstBerry | AspearBerry => 12,
+            LeppaBerry | OranBerry | PersimBerry => 16,
+            FigyBerry | WikiBerry | MagoBerry | AguavBerry | IapapaBerry => 20,
+            CornnBerry | MagostBerry | RabutaBerry | NomelBerry => 24,
+            SitrusBerry | PomegBerry | KelpsyBerry | QualotBerry | HondewBerry | GrepaBerry | TamatoBerry => 32,
+            LumBerry => 48,
+            SpelonBerry | PamtreBerry | WatmelBerry | DurinBerry | BelueBerry => 60,
+            LiechiBerry | GanlonBerry | SalacBerry | PetayaBerry | ApicotBerry | LansatBerry | StarfBerry |
+            EnigmaBerry | MicleBerry | CustapBerry | JabocaBerry | RowapBerry | KeeBerry | MarangaBerry => 96,
+            _ => 72
+        }
+    }
+
+    /// Get the most berries a planted berry can yield, if its soil never dries out
+    pub fn max_yield(&self) -> u16 {
+        use Berry::*;
+        match self {
+            RazzBerry | BlukBerry | NanabBerry | WepearBerry | PinapBerry |
+            PomegBerry | KelpsyBerry | QualotBerry | HondewBerry | GrepaBerry | TamatoBerry |
+            CornnBerry | MagostBerry | RabutaBerry | NomelBerry => 10,
+            SpelonBerry | PamtreBerry | WatmelBerry | DurinBerry | BelueBerry => 15,
+            _ => 5
+        }
+    }
+
     pub fn get_resistance_berry_type(&self) -> Option<Type> {
         match self {
             Berry::OccaBerry => Some(Type::Fire),
@@ -183,7 +213,7 @@ impl Berry {
 
 //region TMs and HMs
 /// All TMs in the game, and their mappings
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, EnumIter)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize, EnumIter)]
 pub enum TM {
     TM01, TM02, TM03, TM04, TM05, TM06, TM07, TM08, TM09, TM10, TM11, TM12, TM13, TM14, TM15, TM16, TM17, TM18, TM19, TM20, TM21, TM22, TM23, TM24, TM25, TM26, TM27, TM28, TM29, TM30, TM31, TM32, TM33, TM34, TM35, TM36, TM37, TM38, TM39, TM40, TM41, TM42, TM43, TM44, TM45, TM46, TM47, TM48, TM49, TM50, TM51, TM52, TM53, TM54, TM55, TM56, TM57, TM58, TM59, TM60, TM61, TM62, TM63, TM64, TM65, TM66, TM67, TM68, TM69, TM70, TM71, TM72, TM73, TM74, TM75, TM76, TM77, TM78, TM79, TM80, TM81, TM82, TM83, TM84, TM85, TM86, TM87, TM88, TM89, TM90, TM91, TM92, TM93, TM94, TM95, TM96, TM97
 }
@@ -295,7 +325,7 @@ impl From<TM> for Item {
 }
 
 /// All HMs in the game, and their mappings
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, EnumIter)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize, EnumIter)]
 pub enum HM {
     HM01, HM02, HM03, HM04, HM05, HM06, HM07, HM08
 }
@@ -322,7 +352,7 @@ impl From<HM> for Item {
 /// Messages are attached via the included String
 /// For convenience, MailDiscriminants is provided, which is all the same Mail
 /// types without attached messages.
-#[derive(Debug, Clone, Eq, PartialEq, EnumDiscriminants, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, EnumDiscriminants, Serialize, Deserialize)]
 pub enum Mail {
     AirMail(Option<String>),
     BloomMail(Option<String>),
@@ -360,17 +390,18 @@ impl From<MailDiscriminants> for Mail {
 }
 
 /// All Key items in the game
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum KeyItem {
-
+    Gracidea, RevealGlass
 }
 impl From<KeyItem> for Item {
     fn from(p: KeyItem) -> Self { Item::KeyItem(p) }
 }
 
 /// All Items in the game
-/// Items from the above lists are wrapped, so they can still be considered items
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+/// Items from the above lists are wrapped, so they can still be considered items.
+/// Items are ordered by their number, which is the order they are listed in.
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Item {
     Ball(Pokeball),
     Stone(EvolutionStone),
@@ -404,7 +435,7 @@ pub enum Item {
     AdamantOrb, LustrousOrb, GriseousOrb,
     BlueScarf, GreenScarf, PinkScarf, RedScarf, YellowScarf,
     SootheBell, CleanseTag, ChoiceBand, ChoiceScarf, ChoiceSpecs, BlackSludge, LifeOrb, RockyHelmet, StickyBarb,
-    GripClaw, LightClay, TerrainExtender, AssaultVest, FlameOrb, IronBall, LaggingTail, MachoBrace,
+    GripClaw, QuickClaw, LightClay, TerrainExtender, AssaultVest, FlameOrb, IronBall, LaggingTail, MachoBrace,
     RingTarget, ToxicOrb, Leftovers, AbsorbBulb, CellBattery, EjectButton, LuminousMoss,
     Snowball, WeaknessPolicy, WideLens, ZoomLens, ScopeLens, BrightPowder, ExpertBelt, Metronome, FocusSash,
     FloatStone, BindingBand,
@@ -415,10 +446,13 @@ pub enum Item {
     Magmarizer, OvalStone, PrismScale, Protector, RazorClaw, RazorFang, ReaperCloth, UpGrade,
     // Incenses
     FullIncense, LaxIncense, LuckIncense, OddIncense,
-    PureIncense, RockIncense, RoseIncense, SeaIncense, WaveIncense
+    PureIncense, RockIncense, RoseIncense, SeaIncense, WaveIncense,
+    // Mulch
+    GrowthMulch, DampMulch, StableMulch, GooeyMulch
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The pockets of the bag. Every item belongs in exactly one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
 pub enum Pocket {
     Items,
     Medicine,
@@ -431,6 +465,59 @@ pub enum Pocket {
 }
 
 impl Item {
+    /// Get the pocket of the bag this item is stored in
+    pub fn pocket(&self) -> Pocket {
+        use Item::*;
+        match self {
+            Ball(_) => Pocket::PokeBalls,
+            TM(_) | HM(_) => Pocket::TMsAndHMs,
+            Berry(_) => Pocket::Berries,
+            Mail(_) => Pocket::Mail,
+            KeyItem(_) => Pocket::KeyItems,
+            Potion | SuperPotion | HyperPotion | MaxPotion |
+            BerryJuice | EnergyRoot | EnergyPowder | FreshWater | Lemonade | MoomooMilk | RageCandyBar |
+            SacredAsh | SodaPop | SweetHeart | RevivalHerb |
+            Antidote | Awakening | BurnHeal | FullHeal | FullRestore | IceHeal | ParalyzeHeal | Revive | MaxRevive |
+            LavaCookie | OldGateau | Casteliacone |
+            Ether | MaxEther | Elixir | MaxElixir | PPUp | PPMax |
+            HPUp | Protein | Iron | Calcium | Zinc | Carbos => Pocket::Medicine,
+            Stone(_) | Everstone | ShellBell | MentalHerb |
+            ShockDrive | BurnDrive | ChillDrive | DouseDrive |
+            PowerAnklet | PowerBand | PowerBelt | PowerBracer | PowerLens | PowerWeight |
+            ExpShare | LuckyEgg |
+            DracoPlate | DreadPlate | EarthPlate | FistPlate | FlamePlate | IciclePlate | InsectPlate | IronPlate |
+            MeadowPlate | MindPlate | PixiePlate | SkyPlate | SplashPlate | SpookyPlate | StonePlate | ToxicPlate | ZapPlate |
+            Eviolite | Leek | LightBall | LuckyPunch | MetalPowder | QuickPowder | SoulDew | ThickClub |
+            BlackBelt | BlackGlasses | Charcoal | DragonFang | HardStone | Magnet | MetalCoat | MiracleSeed | MysticWater |
+            NeverMeltIce | PoisonBarb | SharpBeak | SilkScarf | SilverPowder | SoftSand | SpellTag | TwistedSpoon |
+            AdamantOrb | LustrousOrb | GriseousOrb |
+            BlueScarf | GreenScarf | PinkScarf | RedScarf | YellowScarf |
+            SootheBell | CleanseTag | ChoiceBand | ChoiceScarf | ChoiceSpecs | BlackSludge | LifeOrb | RockyHelmet | StickyBarb |
+            GripClaw | QuickClaw | LightClay | TerrainExtender | AssaultVest | FlameOrb | IronBall | LaggingTail | MachoBrace |
+            RingTarget | ToxicOrb | Leftovers | AbsorbBulb | CellBattery | EjectButton | LuminousMoss |
+            Snowball | WeaknessPolicy | WideLens | ZoomLens | ScopeLens | BrightPowder | ExpertBelt | Metronome | FocusSash |
+            FloatStone | BindingBand |
+            HeatRock | DampRock | SmoothRock | IcyRock |
+            DestinyKnot |
+            DeepSeaTooth | DeepSeaScale | DragonScale | DubiousDisk | Electirizer | KingsRock |
+            Magmarizer | OvalStone | PrismScale | Protector | RazorClaw | RazorFang | ReaperCloth | UpGrade |
+            FullIncense | LaxIncense | LuckIncense | OddIncense |
+            PureIncense | RockIncense | RoseIncense | SeaIncense | WaveIncense |
+            GrowthMulch | DampMulch | StableMulch | GooeyMulch => Pocket::Items
+        }
+    }
+
+    /// Check if this item is kept after being used, such as key items, TMs and HMs.
+    /// Only one of these can be in the bag at a time.
+    pub fn is_reusable(&self) -> bool {
+        matches!(self, Item::KeyItem(_) | Item::TM(_) | Item::HM(_))
+    }
+
+    /// Check if this item can be held by a Pokemon
+    pub fn is_holdable(&self) -> bool {
+        !matches!(self.pocket(), Pocket::KeyItems | Pocket::TMsAndHMs)
+    }
+
     pub fn is_mail(&self) -> bool {
         match self {
             Item::Mail(_) => true,
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::attack::Move;
+use crate::bag::Bag;
+use crate::battle::MoveDataLookup;
+use crate::evolutions::{Evolution, EvolutionTrigger};
+use crate::friendship::{self, FriendshipEvent};
+use crate::item::{EvolutionStone, Item};
+use crate::pokemon::{self, MoveSlot, Pokemon, SpeciesData, StatSlot};
+use crate::species::Species;
+
+/// The most PP Ups a single move can receive
+pub const MAX_PP_BONUS: u8 = 3;
+/// Vitamins cannot raise a stat's effort values past this
+pub const VITAMIN_EV_LIMIT: u8 = 100;
+/// The effort values a single vitamin gives
+pub const VITAMIN_EVS: u8 = 10;
+
+/// The data needed to use items outside of battle
+pub struct ItemUseContext<'a> {
+    pub moves: &'a MoveDataLookup,
+    pub species: &'a HashMap<Species, SpeciesData>,
+    pub evolutions: &'a [Evolution]
+}
+
+/// What happened after an item was used, so it can be displayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemUseResult {
+    /// HP was restored, status conditions were cured, or both
+    Healed {
+        start_hp: u16,
+        end_hp: u16,
+        cured: bool
+    },
+    /// A fainted Pokemon was brought back, with this much HP
+    Revived(u16),
+    /// PP was restored to one or more moves, and how much was restored
+    PpRestored(Vec<(Move, u8)>),
+    /// A move's max PP was raised, to the new max
+    PpRaised(Move, u8),
+    /// A stat's effort values were raised by a vitamin, by this much
+    EffortRaised(u8),
+    /// The Pokemon evolved
+    Evolved {
+        from: Species,
+        to: Species
+    }
+}
+
+/// Reasons an item could not be used. The item should not be consumed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemUseError {
+    /// The item can be used, but would do nothing to this Pokemon
+    NoEffect,
+    /// The item cannot be used on a Pokemon outside of battle
+    CannotUse(Item),
+    /// The item works on one move, but none was chosen
+    ChooseMove,
+    /// The chosen move is not known by the Pokemon
+    UnknownMove(Move),
+    /// The move's data is needed, but could not be found
+    MissingMoveData(Move),
+    /// The evolved species' data could not be found
+    MissingSpeciesData(Species),
+    /// The item is not in the bag
+    NotInBag(Item)
+}
+impl Display for ItemUseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemUseError::NoEffect => write!(f, "It won't have any effect"),
+            ItemUseError::CannotUse(i) => write!(f, "{:?} cannot be used on a Pokemon", i),
+            ItemUseError::ChooseMove => write!(f, "A move must be chosen"),
+            ItemUseError::UnknownMove(m) => write!(f, "The Pokemon does not know {:?}", m),
+            ItemUseError::MissingMoveData(m) => write!(f, "Missing move data for {:?}", m),
+            ItemUseError::MissingSpeciesData(s) => write!(f, "Missing species data for {:?}", s),
+            ItemUseError::NotInBag(i) => write!(f, "{:?} is not in the bag", i)
+        }
+    }
+}
+impl Error for ItemUseError {}
+
+/// The status conditions an item can cure
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Cure {
+    None,
+    Poison,
+    Sleep,
+    Burn,
+    Freeze,
+    Paralysis,
+    All
+}
+
+/// How an item restores HP
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Restore {
+    None,
+    Amount(u16),
+    Full
+}
+
+/// Use an item from the bag on a Pokemon. The item is only consumed if it had an effect.
+/// `attack` is the move chosen for items that work on a single move, such as Ether.
+pub fn use_from_bag(bag: &mut Bag, item: &Item, pokemon: &mut Pokemon, attack: Option<Move>, context: &ItemUseContext) -> Result<ItemUseResult, ItemUseError> {
+    if !bag.contains(item) {
+        return Err(ItemUseError::NotInBag(item.clone()));
+    }
+    let result = use_item(item, pokemon, attack, context)?;
+    bag.use_item(item).map_err(|_| ItemUseError::NotInBag(item.clone()))?;
+    Ok(result)
+}
+
+/// Use an item on a party Pokemon, outside of battle.
+/// `attack` is the move chosen for items that work on a single move, such as Ether.
+///
+/// Nothing is changed if an error is returned, so the item should not be consumed.
+pub fn use_item(item: &Item, pokemon: &mut Pokemon, attack: Option<Move>, context: &ItemUseContext) -> Result<ItemUseResult, ItemUseError> {
+    use Item::*;
+    if pokemon.egg {
+        return Err(ItemUseError::NoEffect);
+    }
+    let result = match item {
+        Potion | BerryJuice | SweetHeart => heal(pokemon, Restore::Amount(20), Cure::None),
+        SuperPotion | FreshWater | EnergyPowder => heal(pokemon, Restore::Amount(50), Cure::None),
+        SodaPop => heal(pokemon, Restore::Amount(60), Cure::None),
+        Lemonade => heal(pokemon, Restore::Amount(80), Cure::None),
+        MoomooMilk => heal(pokemon, Restore::Amount(100), Cure::None),
+        HyperPotion | EnergyRoot => heal(pokemon, Restore::Amount(200), Cure::None),
+        MaxPotion => heal(pokemon, Restore::Full, Cure::None),
+        FullRestore => heal(pokemon, Restore::Full, Cure::All),
+        Antidote => heal(pokemon, Restore::None, Cure::Poison),
+        Awakening => heal(pokemon, Restore::None, Cure::Sleep),
+        BurnHeal => heal(pokemon, Restore::None, Cure::Burn),
+        IceHeal => heal(pokemon, Restore::None, Cure::Freeze),
+        ParalyzeHeal => heal(pokemon, Restore::None, Cure::Paralysis),
+        FullHeal | LavaCookie | OldGateau | Casteliacone | RageCandyBar => heal(pokemon, Restore::None, Cure::All),
+        Revive => revive(pokemon, Restore::Amount(pokemon.hp.value / 2)),
+        MaxRevive | RevivalHerb => revive(pokemon, Restore::Full),
+        Ether => restore_pp(pokemon, Some(choose(pokemon, attack)?), Some(10), context.moves),
+        MaxEther => restore_pp(pokemon, Some(choose(pokemon, attack)?), None, context.moves),
+        Elixir => restore_pp(pokemon, None, Some(10), context.moves),
+        MaxElixir => restore_pp(pokemon, None, None, context.moves),
+        PPUp => raise_pp(pokemon, choose(pokemon, attack)?, 1, context.moves),
+        PPMax => raise_pp(pokemon, choose(pokemon, attack)?, MAX_PP_BONUS, context.moves),
+        HPUp => vitamin(pokemon, |p| &mut p.hp, context),
+        Protein => vitamin(pokemon, |p| &mut p.attack, context),
+        Iron => vitamin(pokemon, |p| &mut p.defense, context),
+        Calcium => vitamin(pokemon, |p| &mut p.special_attack, context),
+        Zinc => vitamin(pokemon, |p| &mut p.special_defense, context),
+        Carbos => vitamin(pokemon, |p| &mut p.speed, context),
+        Stone(stone) => evolve(pokemon, *stone, context),
+        _ => Err(ItemUseError::CannotUse(item.clone()))
+    }?;
+
+    if let Some(event) = FriendshipEvent::for_item(item) {
+        friendship::apply(pokemon, event);
+    }
+    Ok(result)
+}
+
+/// Use Sacred Ash, which revives every fainted Pokemon in the party to full HP.
+/// Returns the index of each Pokemon revived.
+pub fn use_sacred_ash(party: &mut [Pokemon]) -> Result<Vec<usize>, ItemUseError> {
+    let revived = party.iter_mut()
+        .enumerate()
+        .filter(|(_, p)| !p.egg && p.is_fainted())
+        .map(|(idx, p)| {
+            p.heal();
+            idx
+        })
+        .collect::<Vec<_>>();
+    if revived.is_empty() { Err(ItemUseError::NoEffect) } else { Ok(revived) }
+}
+
+fn choose(pokemon: &Pokemon, attack: Option<Move>) -> Result<Move, ItemUseError> {
+    let attack = attack.ok_or(ItemUseError::ChooseMove)?;
+    if pokemon.knows_move(attack) { Ok(attack) } else { Err(ItemUseError::UnknownMove(attack)) }
+}
+
+fn heal(pokemon: &mut Pokemon, restore: Restore, cure: Cure) -> Result<ItemUseResult, ItemUseError> {
+    if pokemon.is_fainted() {
+        return Err(ItemUseError::NoEffect);
+    }
+    let status = &mut pokemon.status;
+    let cured = match cure {
+        Cure::None => false,
+        Cure::Poison => std::mem::take(&mut status.poison),
+        Cure::Sleep => std::mem::take(&mut status.sleep) > 0,
+        Cure::Burn => std::mem::take(&mut status.burn),
+        Cure::Freeze => std::mem::take(&mut status.freeze),
+        Cure::Paralysis => std::mem::take(&mut status.paralysis),
+        Cure::All => std::mem::take(status).has_status_condition()
+    };
+    let (start_hp, end_hp) = match restore {
+        Restore::None => (pokemon.current_hp, pokemon.current_hp),
+        Restore::Amount(amount) => pokemon.add_hp(amount),
+        Restore::Full => pokemon.add_hp(pokemon.hp.value)
+    };
+    if start_hp == end_hp && !cured {
+        return Err(ItemUseError::NoEffect);
+    }
+    Ok(ItemUseResult::Healed { start_hp, end_hp, cured })
+}
+
+fn revive(pokemon: &mut Pokemon, restore: Restore) -> Result<ItemUseResult, ItemUseError> {
+    if !pokemon.is_fainted() {
+        return Err(ItemUseError::NoEffect);
+    }
+    pokemon.status = Default::default();
+    let (_, hp) = match restore {
+        Restore::Amount(amount) => pokemon.add_hp(amount.max(1)),
+        _ => pokemon.add_hp(pokemon.hp.value)
+    };
+    Ok(ItemUseResult::Revived(hp))
+}
+
+/// Get the max PP of a move slot, including any PP Ups it has received.
+/// Each PP Up raises the max PP by a fifth of the move's base PP.
+pub fn max_pp(slot: &MoveSlot, moves: &MoveDataLookup) -> Result<u8, ItemUseError> {
+    let base = moves.get(&slot.attack)
+        .ok_or(ItemUseError::MissingMoveData(slot.attack))?
+        .pp;
+    Ok(base + base / 5 * slot.pp_bonus.min(MAX_PP_BONUS))
+}
+
+fn move_slots(pokemon: &mut Pokemon) -> impl Iterator<Item = &mut MoveSlot> {
+    IntoIterator::into_iter([&mut pokemon.move_1, &mut pokemon.move_2, &mut pokemon.move_3, &mut pokemon.move_4])
+        .flatten()
+}
+
+/// Restore PP to one move, or all moves if None. An amount of None restores all PP.
+fn restore_pp(pokemon: &mut Pokemon, attack: Option<Move>, amount: Option<u8>, moves: &MoveDataLookup) -> Result<ItemUseResult, ItemUseError> {
+    let mut restored = Vec::new();
+    for slot in move_slots(pokemon).filter(|s| attack.is_none_or(|a| a == s.attack)) {
+        let max = max_pp(slot, moves)?;
+        let gained = match amount {
+            Some(amount) => amount.min(max.saturating_sub(slot.pp)),
+            None => max.saturating_sub(slot.pp)
+        };
+        if gained > 0 {
+            restored.push((slot, gained));
+        }
+    }
+    if restored.is_empty() {
+        return Err(ItemUseError::NoEffect);
+    }
+    Ok(ItemUseResult::PpRestored(restored.into_iter()
+        .map(|(slot, gained)| {
+            slot.pp += gained;
+            (slot.attack, gained)
+        })
+        .collect()))
+}
+
+/// Raise the max PP of a move by some number of PP Ups. The move's current PP rises by the same amount.
+fn raise_pp(pokemon: &mut Pokemon, attack: Move, bonus: u8, moves: &MoveDataLookup) -> Result<ItemUseResult, ItemUseError> {
+    let slot = pokemon.get_move_slot_mut(attack).ok_or(ItemUseError::UnknownMove(attack))?;
+    let before = max_pp(slot, moves)?;
+    let pp_bonus = (slot.pp_bonus + bonus).min(MAX_PP_BONUS);
+    let after = max_pp(&MoveSlot { pp_bonus, ..*slot }, moves)?;
+    if after == before {
+        return Err(ItemUseError::NoEffect);
+    }
+    slot.pp_bonus = pp_bonus;
+    slot.pp += after - before;
+    Ok(ItemUseResult::PpRaised(attack, after))
+}
+
+/// Raise one stat's effort values, up to the vitamin limit and the total limit
+fn vitamin(pokemon: &mut Pokemon, stat: fn(&mut Pokemon) -> &mut StatSlot, context: &ItemUseContext) -> Result<ItemUseResult, ItemUseError> {
+    let data = pokemon::species_data(context.species, pokemon.species)
+        .ok_or(ItemUseError::MissingSpeciesData(pokemon.species))?;
+    let remaining = pokemon::MAX_TOTAL_EVS.saturating_sub(pokemon.total_evs()).min(u16::from(VITAMIN_EVS)) as u8;
+    let added = stat(pokemon).add_evs(remaining, VITAMIN_EV_LIMIT);
+    if added == 0 {
+        return Err(ItemUseError::NoEffect);
+    }
+    pokemon.recalculate_stats(data);
+    Ok(ItemUseResult::EffortRaised(added))
+}
+
+fn evolve(pokemon: &mut Pokemon, stone: EvolutionStone, context: &ItemUseContext) -> Result<ItemUseResult, ItemUseError> {
+    let from = pokemon.species;
+    let to = context.evolutions.iter()
+        .find(|e| e.id == from)
+        .and_then(|e| e.paths.as_ref())
+        .into_iter()
+        .flatten()
+        .find_map(|path| match &path.trigger {
+            EvolutionTrigger::EvolutionStone { stone: s, conditions } if *s == stone => {
+                let met = conditions.iter().flatten().all(|c| c.is_met_by(pokemon));
+                met.then_some(path.to)
+            },
+            _ => None
+        })
+        .ok_or(ItemUseError::NoEffect)?;
+    let data = pokemon::species_data(context.species, to)
+        .ok_or(ItemUseError::MissingSpeciesData(to))?;
+    pokemon.change_form(to, data);
+    Ok(ItemUseResult::Evolved { from, to })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::abilities::Ability;
+    use crate::attack::{DamageType, Move};
+    use crate::battle::testing::{battler, move_data, pikachu, shipped_species};
+    use crate::core::OneOrTwo;
+    use crate::evolutions::{EvolutionCondition, EvolutionPath};
+    use crate::pokemon::{Gender, PokemonStatusCondition};
+    use crate::types::Type;
+
+    use super::*;
+
+    fn pokemon() -> Pokemon {
+        pikachu(20, &[Move::Thunderbolt, Move::QuickAttack]).pokemon
+    }
+
+    fn test<F: FnOnce(&ItemUseContext)>(f: F) {
+        let moves = vec![
+            move_data(Move::Thunderbolt, Type::Electric, 95, 100, DamageType::Special),
+            move_data(Move::QuickAttack, Type::Normal, 40, 100, DamageType::Physical)
+        ].into_iter()
+            .map(|mut m| {
+                m.pp = if m.id == Move::Thunderbolt { 15 } else { 30 };
+                (m.id, m)
+            })
+            .collect();
+        let species = shipped_species();
+        let evolutions = vec![Evolution {
+            id: Species::Pikachu,
+            base: Species::Pichu,
+            baby: None,
+            paths: Some(vec![EvolutionPath {
+                to: Species::Raichu,
+                trigger: EvolutionTrigger::EvolutionStone {
+                    stone: EvolutionStone::ThunderStone,
+                    conditions: None
+                }
+            }])
+        }, Evolution {
+            id: Species::Snorunt,
+            base: Species::Snorunt,
+            baby: None,
+            paths: Some(vec![EvolutionPath {
+                to: Species::Froslass,
+                trigger: EvolutionTrigger::EvolutionStone {
+                    stone: EvolutionStone::DawnStone,
+                    conditions: Some(vec![EvolutionCondition::Gender(Gender::Female)])
+                }
+            }])
+        }];
+        f(&ItemUseContext { moves: &moves, species: &species, evolutions: &evolutions });
+    }
+
+    #[test]
+    fn healing() {
+        test(|context| {
+            let mut pokemon = pokemon();
+            assert_eq!(use_item(&Item::Potion, &mut pokemon, None, context), Err(ItemUseError::NoEffect));
+            pokemon.subtract_hp(30);
+            pokemon.status = PokemonStatusCondition::asleep();
+            let max = pokemon.hp.value;
+            assert_eq!(use_item(&Item::Potion, &mut pokemon, None, context), Ok(ItemUseResult::Healed { start_hp: max - 30, end_hp: max - 10, cured: false }));
+            assert_eq!(use_item(&Item::Antidote, &mut pokemon, None, context), Err(ItemUseError::NoEffect));
+            assert_eq!(use_item(&Item::FullRestore, &mut pokemon, None, context), Ok(ItemUseResult::Healed { start_hp: max - 10, end_hp: max, cured: true }));
+
+            pokemon.subtract_hp(max);
+            assert_eq!(use_item(&Item::MaxPotion, &mut pokemon, None, context), Err(ItemUseError::NoEffect));
+            assert_eq!(use_item(&Item::Revive, &mut pokemon, None, context), Ok(ItemUseResult::Revived(max / 2)));
+
+            pokemon.subtract_hp(max);
+            pokemon.friendship = 150;
+            assert_eq!(use_item(&Item::RevivalHerb, &mut pokemon, None, context), Ok(ItemUseResult::Revived(max)));
+            assert_eq!(pokemon.friendship, 135);
+            assert_eq!(use_item(&Item::Leftovers, &mut pokemon, None, context), Err(ItemUseError::CannotUse(Item::Leftovers)));
+        });
+    }
+
+    #[test]
+    fn pp() {
+        test(|context| {
+            let mut pokemon = pokemon();
+            pokemon.move_1 = Some(MoveSlot { attack: Move::Thunderbolt, pp: 2, pp_bonus: 0 });
+            pokemon.move_2 = Some(MoveSlot { attack: Move::QuickAttack, pp: 29, pp_bonus: 0 });
+
+            assert_eq!(use_item(&Item::Ether, &mut pokemon, None, context), Err(ItemUseError::ChooseMove));
+            assert_eq!(use_item(&Item::Ether, &mut pokemon, Some(Move::Surf), context), Err(ItemUseError::UnknownMove(Move::Surf)));
+            assert_eq!(use_item(&Item::Elixir, &mut pokemon, None, context), Ok(ItemUseResult::PpRestored(vec![(Move::Thunderbolt, 10), (Move::QuickAttack, 1)])));
+            assert_eq!(use_item(&Item::MaxEther, &mut pokemon, Some(Move::QuickAttack), context), Err(ItemUseError::NoEffect));
+
+            assert_eq!(use_item(&Item::PPUp, &mut pokemon, Some(Move::Thunderbolt), context), Ok(ItemUseResult::PpRaised(Move::Thunderbolt, 18)));
+            assert_eq!(use_item(&Item::PPMax, &mut pokemon, Some(Move::Thunderbolt), context), Ok(ItemUseResult::PpRaised(Move::Thunderbolt, 24)));
+            assert_eq!(use_item(&Item::PPUp, &mut pokemon, Some(Move::Thunderbolt), context), Err(ItemUseError::NoEffect));
+            assert_eq!(pokemon.move_1.map(|m| (m.pp, m.pp_bonus)), Some((21, 3)));
+        });
+    }
+
+    #[test]
+    fn stones_and_bag() {
+        test(|context| {
+            let mut pokemon = pokemon();
+            let mut bag = Bag::default();
+            let stone = Item::Stone(EvolutionStone::ThunderStone);
+            assert_eq!(use_from_bag(&mut bag, &stone, &mut pokemon, None, context), Err(ItemUseError::NotInBag(stone.clone())));
+
+            bag.add(Item::Stone(EvolutionStone::FireStone), 1);
+            bag.add(stone.clone(), 1);
+            assert_eq!(use_from_bag(&mut bag, &Item::Stone(EvolutionStone::FireStone), &mut pokemon, None, context), Err(ItemUseError::NoEffect));
+            assert_eq!(bag.count(&Item::Stone(EvolutionStone::FireStone)), 1);
+
+            let attack = pokemon.attack.value;
+            assert_eq!(use_from_bag(&mut bag, &stone, &mut pokemon, None, context), Ok(ItemUseResult::Evolved { from: Species::Pikachu, to: Species::Raichu }));
+            assert_eq!(pokemon.species, Species::Raichu);
+            assert!(pokemon.attack.value > attack);
+            assert!(!bag.contains(&stone));
+        });
+    }
+
+    #[test]
+    fn stone_conditions() {
+        test(|context| {
+            let mut pokemon = battler(Species::Snorunt, OneOrTwo::One(Type::Ice), Ability::InnerFocus, [50, 50, 50, 50, 50, 50], 20, &[Move::PowderSnow]).pokemon;
+            let stone = Item::Stone(EvolutionStone::DawnStone);
+            pokemon.gender = Gender::Male;
+            assert_eq!(use_item(&stone, &mut pokemon, None, context), Err(ItemUseError::NoEffect));
+            assert_eq!(pokemon.species, Species::Snorunt);
+
+            pokemon.gender = Gender::Female;
+            assert_eq!(use_item(&stone, &mut pokemon, None, context), Ok(ItemUseResult::Evolved { from: Species::Snorunt, to: Species::Froslass }));
+            assert_eq!(pokemon.species, Species::Froslass);
+        });
+    }
+
+    #[test]
+    fn vitamins() {
+        test(|context| {
+            let mut pokemon = pokemon();
+            pokemon.friendship = 70;
+            let speed = pokemon.speed.value;
+            assert_eq!(use_item(&Item::Carbos, &mut pokemon, None, context), Ok(ItemUseResult::EffortRaised(10)));
+            assert_eq!((pokemon.speed.ev(), pokemon.friendship), (10, 75));
+            for _ in 0..9 {
+                use_item(&Item::Carbos, &mut pokemon, None, context).unwrap();
+            }
+            assert!(pokemon.speed.value > speed);
+            assert_eq!(use_item(&Item::Carbos, &mut pokemon, None, context), Err(ItemUseError::NoEffect));
+
+            pokemon.attack.add_evs(255, 255);
+            pokemon.defense.add_evs(150, 255);
+            assert_eq!(use_item(&Item::Iron, &mut pokemon, None, context), Err(ItemUseError::NoEffect));
+            assert_eq!(use_item(&Item::Zinc, &mut pokemon, None, context), Ok(ItemUseResult::EffortRaised(5)));
+            assert_eq!(pokemon.total_evs(), pokemon::MAX_TOTAL_EVS);
+        });
+    }
+
+    #[test]
+    fn sacred_ash() {
+        let mut party = vec![pokemon(), pokemon(), pokemon()];
+        assert_eq!(use_sacred_ash(&mut party), Err(ItemUseError::NoEffect));
+        party[0].subtract_hp(999);
+        party[2].subtract_hp(999);
+        assert_eq!(use_sacred_ash(&mut party), Ok(vec![0, 2]));
+        assert!(party.iter().all(|p| p.is_full_health()));
+    }
+}
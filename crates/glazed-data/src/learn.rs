@@ -0,0 +1,271 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use strum::IntoEnumIterator;
+
+use crate::attack::Move;
+use crate::bag::Bag;
+use crate::battle::MoveDataLookup;
+use crate::item::{HM, Item, TM};
+use crate::pokemon::{MoveSlot, Pokemon, SpeciesData};
+
+/// A way of teaching a move outside of leveling up
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Teachable {
+    TM(TM),
+    HM(HM),
+    /// A move taught by a Move Tutor
+    Tutor(Move)
+}
+impl Teachable {
+    /// Get the machine an item teaches, if it is a TM or HM
+    pub fn from_item(item: &Item) -> Option<Teachable> {
+        match item {
+            Item::TM(tm) => Some(Teachable::TM(*tm)),
+            Item::HM(hm) => Some(Teachable::HM(*hm)),
+            _ => None
+        }
+    }
+
+    /// Get the move that is taught
+    pub fn get_move(&self) -> Move {
+        match self {
+            Teachable::TM(tm) => *tm.get_move(),
+            Teachable::HM(hm) => *hm.get_move(),
+            Teachable::Tutor(attack) => *attack
+        }
+    }
+
+    /// Check if a species can be taught this move
+    pub fn is_compatible(&self, data: &SpeciesData) -> bool {
+        match self {
+            Teachable::TM(tm) => data.can_learn_tm(*tm),
+            Teachable::HM(hm) => data.can_learn_hm(*hm),
+            Teachable::Tutor(attack) => data.can_learn_from_tutor(*attack)
+        }
+    }
+}
+
+/// Check if a move is taught by an HM. These moves can only be forgotten at the Move Deleter.
+pub fn is_hm_move(attack: Move) -> bool {
+    HM::iter().any(|hm| *hm.get_move() == attack)
+}
+
+/// What happened after a Pokemon learned a move
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LearnResult {
+    /// The move was learned into an empty slot
+    Learned(Move),
+    /// The Pokemon knew four moves, so one was forgotten to make room
+    Replaced {
+        forgotten: Move,
+        learned: Move
+    }
+}
+
+/// Reasons a Pokemon could not learn a move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LearnError {
+    /// Eggs cannot learn moves
+    Egg,
+    /// The Pokemon's species cannot learn the move this way
+    Incompatible(Move),
+    /// The Pokemon already knows the move
+    AlreadyKnown(Move),
+    /// The Pokemon knows four moves, and one must be chosen to forget
+    ChooseMoveToForget,
+    /// The move chosen to forget is not known by the Pokemon
+    UnknownMove(Move),
+    /// HM moves cannot be forgotten, except at the Move Deleter
+    CannotForgetHm(Move),
+    /// The move's data is needed, but could not be found
+    MissingMoveData(Move),
+    /// The item is not in the bag, or does not teach a move
+    NotInBag(Item),
+    /// A Pokemon must always know at least one move
+    LastMove
+}
+impl Display for LearnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LearnError::Egg => write!(f, "Eggs cannot learn moves"),
+            LearnError::Incompatible(m) => write!(f, "{:?} cannot be learned", m),
+            LearnError::AlreadyKnown(m) => write!(f, "{:?} is already known", m),
+            LearnError::ChooseMoveToForget => write!(f, "A move must be chosen to forget"),
+            LearnError::UnknownMove(m) => write!(f, "The Pokemon does not know {:?}", m),
+            LearnError::CannotForgetHm(m) => write!(f, "HM moves such as {:?} cannot be forgotten", m),
+            LearnError::MissingMoveData(m) => write!(f, "Missing move data for {:?}", m),
+            LearnError::NotInBag(i) => write!(f, "{:?} is not in the bag", i),
+            LearnError::LastMove => write!(f, "The Pokemon's last move cannot be forgotten")
+        }
+    }
+}
+impl Error for LearnError {}
+
+/// Teach a Pokemon a move from a TM, HM, or Move Tutor, if its species is compatible.
+/// `forget` is the move to replace, which is only needed if the Pokemon already knows four moves.
+pub fn teach(pokemon: &mut Pokemon, data: &SpeciesData, teachable: Teachable, forget: Option<Move>, moves: &MoveDataLookup) -> Result<LearnResult, LearnError> {
+    if !teachable.is_compatible(data) {
+        return Err(LearnError::Incompatible(teachable.get_move()));
+    }
+    learn_move(pokemon, teachable.get_move(), forget, moves)
+}
+
+/// Teach a Pokemon the move of a TM or HM in the bag. TMs and HMs are not used up.
+pub fn teach_from_bag(bag: &mut Bag, item: &Item, pokemon: &mut Pokemon, data: &SpeciesData, forget: Option<Move>, moves: &MoveDataLookup) -> Result<LearnResult, LearnError> {
+    let teachable = Teachable::from_item(item)
+        .filter(|_| bag.contains(item))
+        .ok_or_else(|| LearnError::NotInBag(item.clone()))?;
+    let result = teach(pokemon, data, teachable, forget, moves)?;
+    bag.use_item(item).map_err(|_| LearnError::NotInBag(item.clone()))?;
+    Ok(result)
+}
+
+/// Have a Pokemon learn a move, without checking if its species can learn it.
+/// The move is placed in the first empty slot. If there is none, `forget` is replaced,
+/// unless it is an HM move.
+pub fn learn_move(pokemon: &mut Pokemon, attack: Move, forget: Option<Move>, moves: &MoveDataLookup) -> Result<LearnResult, LearnError> {
+    if pokemon.egg {
+        return Err(LearnError::Egg);
+    }
+    if pokemon.knows_move(attack) {
+        return Err(LearnError::AlreadyKnown(attack));
+    }
+    let slot = MoveSlot {
+        attack,
+        pp: moves.get(&attack).ok_or(LearnError::MissingMoveData(attack))?.pp,
+        pp_bonus: 0
+    };
+
+    let empty = [&mut pokemon.move_1, &mut pokemon.move_2, &mut pokemon.move_3, &mut pokemon.move_4];
+    if let Some(empty) = IntoIterator::into_iter(empty).find(|s| s.is_none()) {
+        *empty = Some(slot);
+        return Ok(LearnResult::Learned(attack));
+    }
+
+    let forgotten = forget.ok_or(LearnError::ChooseMoveToForget)?;
+    if is_hm_move(forgotten) {
+        return Err(LearnError::CannotForgetHm(forgotten));
+    }
+    let replaced = pokemon.get_move_slot_mut(forgotten).ok_or(LearnError::UnknownMove(forgotten))?;
+    *replaced = slot;
+    Ok(LearnResult::Replaced { forgotten, learned: attack })
+}
+
+/// Get the moves the Move Relearner can teach a Pokemon: level-up moves at or below its current level,
+/// and optionally its egg moves, which it does not currently know.
+/// Level-up moves are listed from lowest level to highest, followed by egg moves.
+pub fn relearnable_moves(pokemon: &Pokemon, data: &SpeciesData, include_egg_moves: bool) -> Vec<Move> {
+    let level_up = data.level_up_moves.range(..=pokemon.level)
+        .flat_map(|(_, moves)| moves);
+    let egg = data.egg_moves.iter()
+        .flatten()
+        .filter(|_| include_egg_moves);
+    let mut relearnable = Vec::new();
+    for attack in level_up.chain(egg) {
+        if !pokemon.knows_move(*attack) && !relearnable.contains(attack) {
+            relearnable.push(*attack);
+        }
+    }
+    relearnable
+}
+
+/// Have the Move Relearner teach a Pokemon a move it could have learned before.
+/// `forget` is the move to replace, which is only needed if the Pokemon already knows four moves.
+pub fn relearn(pokemon: &mut Pokemon, data: &SpeciesData, attack: Move, forget: Option<Move>, include_egg_moves: bool, moves: &MoveDataLookup) -> Result<LearnResult, LearnError> {
+    if pokemon.knows_move(attack) {
+        return Err(LearnError::AlreadyKnown(attack));
+    }
+    if !relearnable_moves(pokemon, data, include_egg_moves).contains(&attack) {
+        return Err(LearnError::Incompatible(attack));
+    }
+    learn_move(pokemon, attack, forget, moves)
+}
+
+/// Have the Move Deleter make a Pokemon forget a move. Unlike when learning a move, HM moves can be forgotten.
+/// The remaining moves are moved up, so there are no gaps between them.
+pub fn delete_move(pokemon: &mut Pokemon, attack: Move) -> Result<(), LearnError> {
+    if !pokemon.knows_move(attack) {
+        return Err(LearnError::UnknownMove(attack));
+    }
+    let mut remaining = [pokemon.move_1, pokemon.move_2, pokemon.move_3, pokemon.move_4]
+        .iter()
+        .flatten()
+        .filter(|s| s.attack != attack)
+        .copied()
+        .collect::<Vec<_>>()
+        .into_iter();
+    if remaining.len() == 0 {
+        return Err(LearnError::LastMove);
+    }
+    pokemon.move_1 = remaining.next();
+    pokemon.move_2 = remaining.next();
+    pokemon.move_3 = remaining.next();
+    pokemon.move_4 = remaining.next();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::abilities::Ability;
+    use crate::attack::DamageType;
+    use crate::battle::testing::{battler, move_data, pikachu, shipped_species};
+    use crate::core::OneOrTwo;
+    use crate::species::Species;
+    use crate::types::Type;
+
+    use super::*;
+
+    #[test]
+    fn teaching() {
+        let species = shipped_species();
+        let data = &species[&Species::Pikachu];
+        let moves = vec![Move::ThunderShock, Move::Growl, Move::TailWhip, Move::QuickAttack, Move::Thunderbolt, Move::Strength, Move::IronTail, Move::HoneClaws]
+            .into_iter()
+            .map(|m| (m, move_data(m, Type::Normal, 40, 100, DamageType::Physical)))
+            .collect();
+        let mut pikachu = pikachu(20, &[Move::ThunderShock, Move::Growl, Move::TailWhip]).pokemon;
+
+        assert_eq!(teach(&mut pikachu, data, Teachable::TM(TM::TM01), None, &moves), Err(LearnError::Incompatible(Move::HoneClaws)));
+        assert_eq!(teach(&mut pikachu, data, Teachable::HM(HM::HM04), None, &moves), Ok(LearnResult::Learned(Move::Strength)));
+        assert_eq!(teach(&mut pikachu, data, Teachable::HM(HM::HM04), None, &moves), Err(LearnError::AlreadyKnown(Move::Strength)));
+        assert_eq!(teach(&mut pikachu, data, Teachable::TM(TM::TM24), None, &moves), Err(LearnError::ChooseMoveToForget));
+        assert_eq!(teach(&mut pikachu, data, Teachable::TM(TM::TM24), Some(Move::Strength), &moves), Err(LearnError::CannotForgetHm(Move::Strength)));
+        assert_eq!(teach(&mut pikachu, data, Teachable::TM(TM::TM24), Some(Move::QuickAttack), &moves), Err(LearnError::UnknownMove(Move::QuickAttack)));
+        assert_eq!(teach(&mut pikachu, data, Teachable::TM(TM::TM24), Some(Move::Growl), &moves), Ok(LearnResult::Replaced { forgotten: Move::Growl, learned: Move::Thunderbolt }));
+        assert_eq!(pikachu.get_moves(), vec![Move::ThunderShock, Move::Thunderbolt, Move::TailWhip, Move::Strength]);
+
+        let mut bag = Bag::default();
+        assert_eq!(teach_from_bag(&mut bag, &Item::TM(TM::TM24), &mut pikachu, data, None, &moves), Err(LearnError::NotInBag(Item::TM(TM::TM24))));
+        bag.add(Item::TM(TM::TM24), 1);
+        assert_eq!(teach(&mut pikachu, data, Teachable::Tutor(Move::IronTail), Some(Move::Thunderbolt), &moves), Ok(LearnResult::Replaced { forgotten: Move::Thunderbolt, learned: Move::IronTail }));
+        assert_eq!(teach_from_bag(&mut bag, &Item::TM(TM::TM24), &mut pikachu, data, Some(Move::TailWhip), &moves), Ok(LearnResult::Replaced { forgotten: Move::TailWhip, learned: Move::Thunderbolt }));
+        assert!(bag.contains(&Item::TM(TM::TM24)));
+        assert!(is_hm_move(Move::Strength) && !is_hm_move(Move::Thunderbolt));
+        assert!(data.get_all_knowable_moves().contains(&Move::IronTail));
+    }
+
+    #[test]
+    fn relearning_and_deleting() {
+        let species = shipped_species();
+        let data = &species[&Species::Bulbasaur];
+        let moves = vec![Move::Tackle, Move::Growl, Move::LeechSeed, Move::VineWhip, Move::Strength]
+            .into_iter()
+            .map(|m| (m, move_data(m, Type::Normal, 40, 100, DamageType::Physical)))
+            .collect();
+        let mut bulbasaur = battler(Species::Bulbasaur, OneOrTwo::Two(Type::Grass, Type::Poison), Ability::Overgrow, [45, 49, 49, 65, 65, 45], 9, &[Move::Tackle, Move::Strength]).pokemon;
+
+        let relearnable = relearnable_moves(&bulbasaur, data, false);
+        assert_eq!(relearnable, vec![Move::Growl, Move::LeechSeed, Move::VineWhip]);
+        assert!(relearnable_moves(&bulbasaur, data, true).len() > relearnable.len());
+        assert_eq!(relearn(&mut bulbasaur, data, Move::SolarBeam, None, true, &moves), Err(LearnError::Incompatible(Move::SolarBeam)));
+        assert_eq!(relearn(&mut bulbasaur, data, Move::VineWhip, None, false, &moves), Ok(LearnResult::Learned(Move::VineWhip)));
+
+        assert_eq!(delete_move(&mut bulbasaur, Move::Growl), Err(LearnError::UnknownMove(Move::Growl)));
+        assert_eq!(delete_move(&mut bulbasaur, Move::Tackle), Ok(()));
+        assert_eq!(delete_move(&mut bulbasaur, Move::Strength), Ok(()));
+        assert_eq!(delete_move(&mut bulbasaur, Move::VineWhip), Err(LearnError::LastMove));
+        assert_eq!(bulbasaur.get_moves(), vec![Move::VineWhip]);
+        assert!(bulbasaur.move_2.is_none());
+    }
+}
@@ -2,13 +2,25 @@ pub mod abilities;
 pub mod attack;
 pub mod pokemon;
 pub mod types;
+pub mod matchup;
 pub mod core;
 pub mod species;
 pub mod item;
+pub mod bag;
+pub mod item_use;
+pub mod learn;
 pub mod evolutions;
+pub mod farming;
 pub mod contest;
 pub mod time;
 pub mod locations;
+pub mod battle;
+pub mod forms;
+pub mod friendship;
+pub mod pokedex;
+pub mod pokerus;
+pub mod ribbons;
+pub mod trainer;
 
 #[cfg(test)]
 mod tests {
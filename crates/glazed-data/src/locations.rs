@@ -1,7 +1,8 @@
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Location {
     // To be filled as-needed
+    #[default]
     FarawayPlace
 }
\ No newline at end of file
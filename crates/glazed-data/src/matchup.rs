@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use strum::IntoEnumIterator;
+
+use crate::attack::{DamageType, Move, MoveData};
+use crate::core::OneOrTwo;
+use crate::pokemon::Pokemon;
+use crate::types::{Effectiveness, Type};
+
+/// The effectiveness of one type against another, from either side of the matchup
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TypeMatchup {
+    /// The type on the other side of the matchup
+    pub against: Type,
+    pub effectiveness: Effectiveness
+}
+
+/// Describes how attacks of every type fare against a Pokemon's types
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefensiveReport {
+    /// Types which deal more than normal damage, weakest first
+    pub weaknesses: Vec<TypeMatchup>,
+    /// Types which deal less than normal damage, most resisted first
+    pub resistances: Vec<TypeMatchup>,
+    /// Types which deal no damage
+    pub immunities: Vec<Type>
+}
+impl DefensiveReport {
+    /// Build the report for a Pokemon with these types
+    pub fn of(types: &OneOrTwo<Type>) -> DefensiveReport {
+        let mut report = DefensiveReport::default();
+        for attacking in Type::iter() {
+            let effectiveness = types.defending_against(&attacking);
+            let matchup = TypeMatchup { against: attacking, effectiveness };
+            match effectiveness {
+                Effectiveness::Immune => report.immunities.push(attacking),
+                Effectiveness::Effect(p) if p > 0 => report.weaknesses.push(matchup),
+                Effectiveness::Effect(p) if p < 0 => report.resistances.push(matchup),
+                Effectiveness::Effect(_) => {}
+            }
+        }
+        report.weaknesses.sort_by_key(|m| std::cmp::Reverse(m.effectiveness.power()));
+        report.resistances.sort_by_key(|m| m.effectiveness.power());
+        report
+    }
+}
+
+/// Describes how well a set of attacking types covers every defending type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// The best effectiveness available against each type, in type order.
+    /// Empty if there are no attacking types at all.
+    pub best: Vec<TypeMatchup>
+}
+impl CoverageReport {
+    /// Build the report for a set of attacking types
+    pub fn of(attacking: &[Type]) -> CoverageReport {
+        let best = if attacking.is_empty() {
+            Vec::new()
+        } else {
+            Type::iter()
+                .map(|defending| {
+                    let effectiveness = attacking.iter()
+                        .map(|a| a.attacking(&defending))
+                        .max_by_key(|e| e.power())
+                        .unwrap_or(Effectiveness::NORMAL);
+                    TypeMatchup { against: defending, effectiveness }
+                })
+                .collect()
+        };
+        CoverageReport { best }
+    }
+
+    /// Build the report for a Pokemon's damaging moves.
+    /// Hidden Power is counted as the Pokemon's Hidden Power type.
+    pub fn of_moveset(pokemon: &Pokemon, moves: &HashMap<Move, MoveData>) -> CoverageReport {
+        CoverageReport::of(&moveset_types(pokemon, moves))
+    }
+
+    /// Types that can be hit super effectively
+    pub fn super_effective(&self) -> Vec<Type> {
+        self.filter(|e| e.is_super_effective())
+    }
+
+    /// Types that can only be hit for reduced damage
+    pub fn resisted(&self) -> Vec<Type> {
+        self.filter(|e| matches!(e, Effectiveness::Effect(p) if *p < 0))
+    }
+
+    /// Types that cannot be damaged at all
+    pub fn unhittable(&self) -> Vec<Type> {
+        self.filter(|e| e.is_immune())
+    }
+
+    fn filter<F: Fn(&Effectiveness) -> bool>(&self, predicate: F) -> Vec<Type> {
+        self.best.iter()
+            .filter(|m| predicate(&m.effectiveness))
+            .map(|m| m.against)
+            .collect()
+    }
+}
+
+/// Get the distinct types of a Pokemon's damaging moves.
+/// Moves without data are skipped, and Hidden Power uses the Pokemon's Hidden Power type.
+pub fn moveset_types(pokemon: &Pokemon, moves: &HashMap<Move, MoveData>) -> Vec<Type> {
+    let mut types = Vec::new();
+    for slot in [pokemon.move_1, pokemon.move_2, pokemon.move_3, pokemon.move_4].iter().flatten() {
+        let _type = match moves.get(&slot.attack) {
+            Some(d) if d.damage_type == DamageType::Status => continue,
+            Some(_) if slot.attack == Move::HiddenPower => pokemon.get_hidden_power_type(),
+            Some(d) => d._type,
+            None => continue
+        };
+        if !types.contains(&_type) {
+            types.push(_type);
+        }
+    }
+    types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defensive_report() {
+        let report = DefensiveReport::of(&OneOrTwo::Two(Type::Ground, Type::Flying));
+        assert_eq!(report.weaknesses, vec![TypeMatchup { against: Type::Ice, effectiveness: Effectiveness::Effect(2) },
+                                           TypeMatchup { against: Type::Water, effectiveness: Effectiveness::DOUBLE }]);
+        assert_eq!(report.immunities, vec![Type::Ground, Type::Electric]);
+        assert_eq!(report.resistances.first().map(|m| m.against), Some(Type::Fighting));
+        assert_eq!(report.weaknesses[0].effectiveness.multiplier(), 4.0);
+    }
+
+    #[test]
+    fn coverage_report() {
+        let report = CoverageReport::of(&[Type::Electric, Type::Ice]);
+        assert_eq!(report.best.len(), 18);
+        assert_eq!(report.unhittable(), Vec::<Type>::new());
+        assert!(report.super_effective().contains(&Type::Ground));
+
+        let report = CoverageReport::of(&[Type::Normal]);
+        assert_eq!(report.unhittable(), vec![Type::Ghost]);
+        assert_eq!(report.resisted(), vec![Type::Rock, Type::Steel]);
+        assert!(CoverageReport::of(&[]).best.is_empty());
+    }
+}
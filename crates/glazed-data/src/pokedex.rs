@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::pokemon::{Color, EggGroup, Gender, SpeciesData};
+use crate::species::{Species, SpeciesDiscriminants};
+use crate::types::Type;
+
+/// How much the player knows about a species
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DexStatus {
+    /// The species has never been encountered
+    #[default]
+    Unknown,
+    /// The species has been encountered, but never owned
+    Seen,
+    /// The species has been owned at some point
+    Caught
+}
+
+/// One combination of form and gender the player has encountered.
+/// These decide which sprites the Pokedex is allowed to show.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Appearance {
+    pub species: Species,
+    pub gender: Gender
+}
+
+/// The player's Pokedex, recording every species they have seen or caught
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Pokedex {
+    status: HashMap<SpeciesDiscriminants, DexStatus>,
+    /// Every appearance encountered, in the order they were first encountered
+    appearances: Vec<Appearance>
+}
+impl Pokedex {
+    /// Record that a Pokemon was seen, such as in a battle
+    pub fn see(&mut self, species: Species, gender: Gender) {
+        self.record(species, gender, DexStatus::Seen);
+    }
+
+    /// Record that a Pokemon was caught, or otherwise obtained.
+    /// Catching a Pokemon also counts as seeing it.
+    pub fn catch(&mut self, species: Species, gender: Gender) {
+        self.record(species, gender, DexStatus::Caught);
+    }
+
+    fn record(&mut self, species: Species, gender: Gender, status: DexStatus) {
+        let current = self.status.entry(species.into()).or_default();
+        *current = status.max(*current);
+        let appearance = Appearance { species, gender };
+        if !self.appearances.contains(&appearance) {
+            self.appearances.push(appearance);
+        }
+    }
+
+    /// Get how much the player knows about a species. Form is disregarded.
+    pub fn status(&self, species: Species) -> DexStatus {
+        self.status.get(&species.into()).copied().unwrap_or_default()
+    }
+
+    /// Check if a species has been seen or caught
+    pub fn has_seen(&self, species: Species) -> bool {
+        self.status(species) >= DexStatus::Seen
+    }
+
+    /// Check if a species has been caught
+    pub fn has_caught(&self, species: Species) -> bool {
+        self.status(species) == DexStatus::Caught
+    }
+
+    /// Get every form and gender of a species that has been encountered, in the order they were encountered
+    pub fn appearances(&self, species: Species) -> Vec<Appearance> {
+        let species = SpeciesDiscriminants::from(species);
+        self.appearances.iter()
+            .filter(|a| SpeciesDiscriminants::from(a.species) == species)
+            .copied()
+            .collect()
+    }
+
+    /// Count the species which have been seen or caught
+    pub fn seen_count(&self) -> usize {
+        self.status.values().filter(|s| **s >= DexStatus::Seen).count()
+    }
+
+    /// Count the species which have been caught
+    pub fn caught_count(&self) -> usize {
+        self.status.values().filter(|s| **s == DexStatus::Caught).count()
+    }
+
+    /// Find the species in a regional Pokedex which the player has seen, and which match a search.
+    /// Species are returned in regional order, using the first form the player encountered if it has data.
+    pub fn search<'a, F>(&self, dex: &RegionalDex, search: &PokedexSearch, lookup: F) -> Vec<Species>
+        where F: Fn(Species) -> Option<&'a SpeciesData>
+    {
+        dex.0.iter()
+            .filter(|s| self.has_seen(**s))
+            .filter(|s| !search.caught_only || self.has_caught(**s))
+            .filter_map(|s| {
+                let shown = self.appearances(*s).first().map_or(*s, |a| a.species);
+                lookup(shown).map(|data| (shown, data))
+            })
+            .filter(|(_, data)| search.matches(data))
+            .map(|(shown, _)| shown)
+            .collect()
+    }
+}
+
+/// Get a species' number in the National Pokedex, starting at 1
+pub fn national_number(species: Species) -> u16 {
+    let species = SpeciesDiscriminants::from(species);
+    SpeciesDiscriminants::iter()
+        .position(|s| s == species)
+        .map_or(0, |i| i as u16 + 1)
+}
+
+/// A regional Pokedex, listing the species of one region in its own order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegionalDex(Vec<Species>);
+impl RegionalDex {
+    pub fn new(species: Vec<Species>) -> RegionalDex {
+        RegionalDex(species)
+    }
+
+    /// Get a species' number in this Pokedex, starting at 1. Form is disregarded.
+    /// Returns None if the species is not part of this Pokedex.
+    pub fn number(&self, species: Species) -> Option<u16> {
+        let species = SpeciesDiscriminants::from(species);
+        self.0.iter()
+            .position(|s| SpeciesDiscriminants::from(*s) == species)
+            .map(|i| i as u16 + 1)
+    }
+
+    /// Iterate through the species in this Pokedex, in order
+    pub fn iter(&self) -> impl Iterator<Item = &Species> {
+        self.0.iter()
+    }
+
+    /// Count the species in this Pokedex which the player has seen or caught
+    pub fn seen_count(&self, pokedex: &Pokedex) -> usize {
+        self.0.iter().filter(|s| pokedex.has_seen(**s)).count()
+    }
+
+    /// Count the species in this Pokedex which the player has caught
+    pub fn caught_count(&self, pokedex: &Pokedex) -> usize {
+        self.0.iter().filter(|s| pokedex.has_caught(**s)).count()
+    }
+}
+impl From<Vec<Species>> for RegionalDex {
+    fn from(species: Vec<Species>) -> Self {
+        RegionalDex(species)
+    }
+}
+
+/// The filters used when searching the Pokedex. Empty filters match every species.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PokedexSearch {
+    /// Only match species with this type
+    pub _type: Option<Type>,
+    /// Only match species listed under this color
+    pub color: Option<Color>,
+    /// Only match species in this egg group
+    pub egg_group: Option<EggGroup>,
+    /// Only match species which have been caught
+    pub caught_only: bool
+}
+impl PokedexSearch {
+    /// Check if a species' data matches every filter.
+    /// Whether the species was caught is not checked here, as it depends on the Pokedex.
+    pub fn matches(&self, data: &SpeciesData) -> bool {
+        let type_matches = self._type.is_none_or(|t| data._type.has_type(&t));
+        let color_matches = self.color.is_none_or(|c| data.color == c);
+        let egg_group_matches = self.egg_group.is_none_or(|e| data.egg_group.as_set().contains(&e));
+        type_matches && color_matches && egg_group_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::shipped_species;
+    use crate::species::{DeoxysForm, UnownForm};
+
+    use super::*;
+
+    #[test]
+    fn seen_and_caught() {
+        let mut dex = Pokedex::default();
+        dex.see(Species::Unown(UnownForm::B), Gender::None);
+        dex.catch(Species::Unown(UnownForm::Q), Gender::None);
+        dex.see(Species::Unown(UnownForm::A), Gender::None);
+        dex.see(Species::Pidgey, Gender::Female);
+
+        assert_eq!(dex.status(Species::Unown(UnownForm::Z)), DexStatus::Caught);
+        assert_eq!(dex.status(Species::Pidgey), DexStatus::Seen);
+        assert_eq!(dex.status(Species::Rattata), DexStatus::Unknown);
+        assert_eq!(dex.appearances(Species::Unown(UnownForm::A)).len(), 3);
+        assert_eq!((dex.seen_count(), dex.caught_count()), (2, 1));
+
+        let yaml = serde_yaml::to_string(&dex).unwrap();
+        let loaded: Pokedex = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(loaded.status(Species::Unown(UnownForm::C)), DexStatus::Caught);
+        assert_eq!(loaded.appearances(Species::Pidgey), vec![Appearance { species: Species::Pidgey, gender: Gender::Female }]);
+    }
+
+    #[test]
+    fn search() {
+        let lookup = shipped_species();
+        let regional = RegionalDex::new(vec![Species::Charmander, Species::Pidgey, Species::Rattata, Species::Sentret]);
+
+        let mut dex = Pokedex::default();
+        dex.see(Species::Pidgey, Gender::Male);
+        dex.see(Species::Rattata, Gender::Male);
+        dex.catch(Species::Charmander, Gender::Male);
+        let find = |search: PokedexSearch| dex.search(&regional, &search, |s| lookup.get(&s));
+
+        assert_eq!(find(PokedexSearch::default()), vec![Species::Charmander, Species::Pidgey, Species::Rattata]);
+        assert_eq!(find(PokedexSearch { _type: Some(Type::Flying), ..Default::default() }), vec![Species::Pidgey]);
+        assert_eq!(find(PokedexSearch { color: Some(Color::Purple), ..Default::default() }), vec![Species::Rattata]);
+        assert_eq!(find(PokedexSearch { egg_group: Some(EggGroup::Monster), ..Default::default() }), vec![Species::Charmander]);
+        assert_eq!(find(PokedexSearch { caught_only: true, ..Default::default() }), vec![Species::Charmander]);
+        assert_eq!((regional.seen_count(&dex), regional.caught_count(&dex)), (3, 1));
+    }
+
+    #[test]
+    fn numbering() {
+        assert_eq!(national_number(Species::Bulbasaur), 1);
+        assert_eq!(national_number(Species::Deoxys(DeoxysForm::Speed)), 386);
+        assert_eq!(national_number(Species::Genesect(Default::default())), 649);
+
+        let dex = RegionalDex::new(vec![Species::Sentret, Species::Pidgey, Species::Deoxys(DeoxysForm::Normal)]);
+        assert_eq!(dex.number(Species::Pidgey), Some(2));
+        assert_eq!(dex.number(Species::Deoxys(DeoxysForm::Attack)), Some(3));
+        assert_eq!(dex.number(Species::Rattata), None);
+    }
+}
@@ -1,22 +1,31 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
 use rand::Rng;
 use serde::{Deserialize, Deserializer, Serialize};
+use strum::IntoEnumIterator;
 
 use crate::abilities::Ability;
 use crate::attack::Move;
+use crate::battle::MoveDataLookup;
 use crate::core::OneOrTwo;
-use crate::item::{Item, Pokeball};
+use crate::friendship;
+use crate::item::{HM, Item, Pokeball, TM};
 use crate::locations::Location;
-use crate::species::Species;
+use crate::species::{Species, SpeciesDiscriminants};
 use crate::types::Type;
 
 pub const SHININESS_CHANCE: u16 = 16;
+/// The most effort values a Pokemon can have, across every stat
+pub const MAX_TOTAL_EVS: u16 = 510;
+/// The most effort values a Pokemon can have in one stat
+pub const MAX_STAT_EVS: u8 = 255;
 
 /// Represents the probability of a Pokemon being male or female (or neither)
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum GenderRatio {
     None,
     Proportion(u8, u8)
@@ -29,9 +38,13 @@ impl GenderRatio {
     pub const ONE_TO_SEVEN: GenderRatio = GenderRatio::Proportion(1, 7);
     pub const THREE_TO_ONE: GenderRatio = GenderRatio::Proportion(3, 1);
     pub const SEVEN_TO_ONE: GenderRatio = GenderRatio::Proportion(7, 1);
+
+    /// Every proportion used by a species
+    pub const ALL: [GenderRatio; 7] = [GenderRatio::MALE_ONLY, GenderRatio::FEMALE_ONLY, GenderRatio::ONE_TO_ONE,
+        GenderRatio::ONE_TO_THREE, GenderRatio::ONE_TO_SEVEN, GenderRatio::THREE_TO_ONE, GenderRatio::SEVEN_TO_ONE];
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Gender {
     Male,
     Female,
@@ -159,7 +172,7 @@ impl LevelRate {
 }
 
 /// Represents the Pokedex color of a Pokemon
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 pub enum Color {
     Red, Blue, Yellow, Green, Black, Brown, Purple, Gray, White, Pink
 }
@@ -185,7 +198,7 @@ impl Stat {
 }
 
 /// Represents data on a Pokemon species as a whole
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct SpeciesData {
     #[serde(rename = "type")]
     pub _type: OneOrTwo<Type>,
@@ -202,17 +215,364 @@ pub struct SpeciesData {
     pub stats: Stats,
     pub base_friendship: u8,
     pub level_up_moves: BTreeMap<u8, Vec<Move>>,
-    pub egg_moves: Option<Vec<Move>>
+    pub egg_moves: Option<Vec<Move>>,
+    /// The TMs this species can learn
+    #[serde(default)]
+    pub tms: Vec<TM>,
+    /// The HMs this species can learn
+    #[serde(default)]
+    pub hms: Vec<HM>,
+    /// The moves this species can learn from a Move Tutor
+    #[serde(default)]
+    pub tutor_moves: Vec<Move>,
+    /// The color this species is listed under when searching the Pokedex
+    pub color: Color
+}
+
+/// One entry in the species data file, pairing a species with its data.
+/// A form may instead name the form it is based on, and only list the fields which differ from it.
+#[derive(Debug, Deserialize)]
+pub struct SpeciesDataMapping {
+    pub id: Species,
+    pub data: Option<SpeciesData>,
+    pub form_of: Option<Species>,
+    #[serde(default)]
+    pub overrides: SpeciesDataOverrides
 }
+
+/// The fields a form may change from the form it is based on.
+/// Any field left empty is inherited.
+#[derive(Debug, Default, Deserialize)]
+pub struct SpeciesDataOverrides {
+    #[serde(rename = "type")]
+    pub _type: Option<OneOrTwo<Type>>,
+    pub ability: Option<OneOrTwo<Ability>>,
+    /// Set to `~` to remove the hidden ability, rather than inherit it
+    #[serde(default, deserialize_with = "deserialize_override")]
+    pub hidden_ability: Option<Option<Ability>>,
+    pub stats: Option<Stats>,
+    pub height: Option<u8>,
+    pub weight: Option<u16>,
+    pub level_up_moves: Option<BTreeMap<u8, Vec<Move>>>,
+    pub egg_moves: Option<Vec<Move>>,
+    pub tms: Option<Vec<TM>>,
+    pub hms: Option<Vec<HM>>,
+    pub tutor_moves: Option<Vec<Move>>,
+    pub color: Option<Color>
+}
+impl SpeciesDataOverrides {
+    /// Create the data for a form, starting from the data of the form it is based on
+    pub fn apply(&self, base: &SpeciesData) -> SpeciesData {
+        let mut data = base.clone();
+        if let Some(t) = self._type { data._type = t; }
+        if let Some(a) = self.ability { data.ability = a; }
+        if let Some(a) = self.hidden_ability { data.hidden_ability = a; }
+        if let Some(s) = &self.stats { data.stats = s.clone(); }
+        if let Some(h) = self.height { data.height = h; }
+        if let Some(w) = self.weight { data.weight = w; }
+        if let Some(m) = &self.level_up_moves { data.level_up_moves = m.clone(); }
+        if let Some(m) = &self.egg_moves { data.egg_moves = Some(m.clone()); }
+        if let Some(m) = &self.tms { data.tms = m.clone(); }
+        if let Some(m) = &self.hms { data.hms = m.clone(); }
+        if let Some(m) = &self.tutor_moves { data.tutor_moves = m.clone(); }
+        if let Some(c) = self.color { data.color = c; }
+        data
+    }
+}
+
+/// Deserialize an override which is present, even if its value is empty
+fn deserialize_override<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error> where D: Deserializer<'de>, T: Deserialize<'de> {
+    T::deserialize(deserializer).map(Some)
+}
+
+/// A problem with an entry in the species data file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpeciesDataProblem {
+    /// The file is not a list of entries
+    Unreadable(String),
+    /// The entry could not be read, for a reason other than an unknown move
+    InvalidEntry(String, String),
+    /// The entry lists a move which does not exist
+    UnknownMove(String, String),
+    /// The species has more than one entry
+    Duplicate(Species),
+    /// The entry has neither its own data nor a form it is based on
+    NoData(Species),
+    /// The entry has both its own data and a form it is based on
+    DataAndBase(Species),
+    /// The form it is based on has no data, or is part of a cycle
+    MissingBase(Species, Species),
+    /// The species has no entry at all
+    MissingSpecies(SpeciesDiscriminants),
+    /// The species has forms, but its base form has no data for the others to fall back on
+    MissingBaseForm(Species),
+    /// One of the species' base stats is zero
+    ZeroBaseStat(Species),
+    /// The species yields more than 3 EVs in a stat, or does not yield between 1 and 3 EVs in total
+    InvalidEvYield(Species, [u8; 6]),
+    /// The gender ratio is empty, or not one used by any species
+    InvalidGenderRatio(Species, GenderRatio),
+    /// The species is in the Ditto egg group, but has a gender
+    GenderedDitto(Species),
+    /// A field which must be positive is zero
+    ZeroField(Species, &'static str),
+    /// The species learns no moves by level up
+    NoLevelUpMoves(Species),
+    /// The species learns a move past the maximum level
+    InvalidLevel(Species, u8),
+    /// The species learns no moves from TMs, HMs, or Move Tutors, and is not one of the few that really can't
+    NoMachineMoves(Species)
+}
+impl Display for SpeciesDataProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpeciesDataProblem::Unreadable(e) => write!(f, "The file could not be read: {}", e),
+            SpeciesDataProblem::InvalidEntry(entry, e) => write!(f, "{} could not be read: {}", entry, e),
+            SpeciesDataProblem::UnknownMove(entry, m) => write!(f, "{} lists {}, which is not a move", entry, m),
+            SpeciesDataProblem::Duplicate(s) => write!(f, "{:?} has more than one entry", s),
+            SpeciesDataProblem::NoData(s) => write!(f, "{:?} has neither data nor a form_of", s),
+            SpeciesDataProblem::DataAndBase(s) => write!(f, "{:?} has both data and a form_of", s),
+            SpeciesDataProblem::MissingBase(s, b) => write!(f, "{:?} is a form of {:?}, which has no data", s, b),
+            SpeciesDataProblem::MissingSpecies(s) => write!(f, "{:?} has no entry", s),
+            SpeciesDataProblem::MissingBaseForm(s) => write!(f, "{:?} is the base form of its species, but has no entry", s),
+            SpeciesDataProblem::ZeroBaseStat(s) => write!(f, "{:?} has a base stat of zero", s),
+            SpeciesDataProblem::InvalidEvYield(s, evs) => write!(f, "{:?} has an invalid EV yield of {:?}", s, evs),
+            SpeciesDataProblem::InvalidGenderRatio(s, r) => write!(f, "{:?} has an invalid gender ratio of {:?}", s, r),
+            SpeciesDataProblem::GenderedDitto(s) => write!(f, "{:?} is in the Ditto egg group, but has a gender", s),
+            SpeciesDataProblem::ZeroField(s, field) => write!(f, "{:?} has a {} of zero", s, field),
+            SpeciesDataProblem::NoLevelUpMoves(s) => write!(f, "{:?} learns no moves by level up", s),
+            SpeciesDataProblem::InvalidLevel(s, l) => write!(f, "{:?} learns a move at level {}", s, l),
+            SpeciesDataProblem::NoMachineMoves(s) => write!(f, "{:?} learns no moves from TMs, HMs, or tutors", s)
+        }
+    }
+}
+
+/// Every problem found while validating a species data file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeciesDataError(pub Vec<SpeciesDataProblem>);
+impl Display for SpeciesDataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let problems = self.0.iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+        write!(f, "Invalid species data: {}", problems)
+    }
+}
+impl Error for SpeciesDataError {}
+
+/// Build the species data for every entry, applying each form's overrides to the form it is based on.
+/// Forms may be based on other forms. Entries with problems are left out, and every problem is returned.
+pub fn resolve_species_data(mappings: Vec<SpeciesDataMapping>) -> (HashMap<Species, SpeciesData>, Vec<SpeciesDataProblem>) {
+    let mut resolved = HashMap::new();
+    let mut forms = Vec::new();
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    for mapping in mappings {
+        if !seen.insert(mapping.id) {
+            problems.push(SpeciesDataProblem::Duplicate(mapping.id));
+            continue;
+        }
+        match (mapping.data, mapping.form_of) {
+            (Some(_), Some(_)) => problems.push(SpeciesDataProblem::DataAndBase(mapping.id)),
+            (None, None) => problems.push(SpeciesDataProblem::NoData(mapping.id)),
+            (Some(data), None) => { resolved.insert(mapping.id, data); },
+            (None, Some(base)) => forms.push((mapping.id, base, mapping.overrides))
+        }
+    }
+
+    // Forms are resolved in passes, so a form can be based on another form listed after it
+    loop {
+        let before = forms.len();
+        forms.retain(|(id, base, overrides)| {
+            match resolved.get(base).map(|b| overrides.apply(b)) {
+                Some(data) => {
+                    resolved.insert(*id, data);
+                    false
+                },
+                None => true
+            }
+        });
+        if forms.len() == before {
+            break;
+        }
+    }
+    problems.extend(forms.into_iter().map(|(id, base, _)| SpeciesDataProblem::MissingBase(id, base)));
+
+    (resolved, problems)
+}
+
+/// Parse, resolve, and check a species data file, collecting every problem instead of stopping at the first.
+/// Entries which cannot be read are skipped, so the rest of the file can still be checked.
+pub fn validate_species_data(yaml: &str) -> Result<HashMap<Species, SpeciesData>, SpeciesDataError> {
+    let entries: Vec<serde_yaml::Value> = serde_yaml::from_str(yaml)
+        .map_err(|e| SpeciesDataError(vec![SpeciesDataProblem::Unreadable(e.to_string())]))?;
+
+    let mut problems = Vec::new();
+    let mut mappings = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        match serde_yaml::from_value::<SpeciesDataMapping>(entry.clone()) {
+            Ok(mapping) => mappings.push(mapping),
+            Err(e) => {
+                let name = serde_yaml::from_value::<Species>(entry["id"].clone())
+                    .map_or_else(|_| format!("Entry {}", index + 1), |s| format!("{:?}", s));
+                let unknown = unknown_moves(&entry);
+                if unknown.is_empty() {
+                    problems.push(SpeciesDataProblem::InvalidEntry(name, e.to_string()));
+                } else {
+                    problems.extend(unknown.into_iter().map(|m| SpeciesDataProblem::UnknownMove(name.clone(), m)));
+                }
+            }
+        }
+    }
+
+    let (resolved, resolve_problems) = resolve_species_data(mappings);
+    problems.extend(resolve_problems);
+    problems.extend(check_species_data(&resolved));
+
+    if problems.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(SpeciesDataError(problems))
+    }
+}
+
+/// Find the names of any moves in an entry which do not exist
+fn unknown_moves(entry: &serde_yaml::Value) -> Vec<String> {
+    let mut lists = Vec::new();
+    for section in ["data", "overrides"] {
+        if let Some(levels) = entry[section]["level_up_moves"].as_mapping() {
+            lists.extend(levels.iter().map(|(_, moves)| moves));
+        }
+        lists.push(&entry[section]["egg_moves"]);
+        lists.push(&entry[section]["tutor_moves"]);
+    }
+    lists.into_iter()
+        .filter_map(|moves| moves.as_sequence())
+        .flatten()
+        .filter(|m| serde_yaml::from_value::<Move>((*m).clone()).is_err())
+        .map(|m| m.as_str().map_or_else(|| format!("{:?}", m), String::from))
+        .collect()
+}
+
+/// Species which cannot learn any move from a TM, HM, or Move Tutor
+const NO_MACHINE_MOVES: [SpeciesDiscriminants; 18] = [
+    SpeciesDiscriminants::Caterpie, SpeciesDiscriminants::Metapod, SpeciesDiscriminants::Weedle,
+    SpeciesDiscriminants::Kakuna, SpeciesDiscriminants::Magikarp, SpeciesDiscriminants::Ditto,
+    SpeciesDiscriminants::Unown, SpeciesDiscriminants::Wobbuffet, SpeciesDiscriminants::Wynaut,
+    SpeciesDiscriminants::Smeargle, SpeciesDiscriminants::Wurmple, SpeciesDiscriminants::Silcoon,
+    SpeciesDiscriminants::Cascoon, SpeciesDiscriminants::Beldum, SpeciesDiscriminants::Kricketot,
+    SpeciesDiscriminants::Burmy, SpeciesDiscriminants::Combee, SpeciesDiscriminants::Tynamo
+];
+
+/// Check resolved species data for missing species, and values which could not appear in the games
+pub fn check_species_data(lookup: &HashMap<Species, SpeciesData>) -> Vec<SpeciesDataProblem> {
+    let mut problems = Vec::new();
+    for species in SpeciesDiscriminants::iter() {
+        match lookup.keys().find(|s| SpeciesDiscriminants::from(*s) == species) {
+            None => problems.push(SpeciesDataProblem::MissingSpecies(species)),
+            Some(s) if !lookup.contains_key(&s.base_form()) => problems.push(SpeciesDataProblem::MissingBaseForm(s.base_form())),
+            Some(_) => {}
+        }
+    }
+
+    let mut species = lookup.keys().copied().collect::<Vec<_>>();
+    species.sort_by_key(|s| format!("{:?}", s));
+    for species in species {
+        let data = &lookup[&species];
+        if data.stats.base_stats().contains(&0) {
+            problems.push(SpeciesDataProblem::ZeroBaseStat(species));
+        }
+        let evs = data.stats.ev_yields();
+        let total = evs.iter().sum::<u8>();
+        if evs.iter().any(|ev| *ev > 3) || !(1..=3).contains(&total) {
+            problems.push(SpeciesDataProblem::InvalidEvYield(species, evs));
+        }
+        if let GenderRatio::Proportion(..) = data.gender_ratio {
+            if !GenderRatio::ALL.contains(&data.gender_ratio) {
+                problems.push(SpeciesDataProblem::InvalidGenderRatio(species, data.gender_ratio));
+            }
+            if data.egg_group.as_set().contains(&EggGroup::Ditto) {
+                problems.push(SpeciesDataProblem::GenderedDitto(species));
+            }
+        }
+        for (field, value) in [("catch rate", u16::from(data.catch_rate)), ("height", u16::from(data.height)), ("weight", data.weight)] {
+            if value == 0 {
+                problems.push(SpeciesDataProblem::ZeroField(species, field));
+            }
+        }
+        match data.level_up_moves.keys().next_back() {
+            None => problems.push(SpeciesDataProblem::NoLevelUpMoves(species)),
+            Some(level) if *level > 100 => problems.push(SpeciesDataProblem::InvalidLevel(species, *level)),
+            Some(_) => {}
+        }
+        if data.tms.is_empty() && data.hms.is_empty() && data.tutor_moves.is_empty()
+            && !NO_MACHINE_MOVES.contains(&SpeciesDiscriminants::from(species)) {
+            problems.push(SpeciesDataProblem::NoMachineMoves(species));
+        }
+    }
+    problems
+}
+
+/// Look up a species' data. Forms without their own entry use the data of their base form.
+pub fn species_data(lookup: &HashMap<Species, SpeciesData>, species: Species) -> Option<&SpeciesData> {
+    lookup.get(&species)
+        .or_else(|| lookup.get(&species.base_form()))
+}
+
+impl Stats {
+    /// Get the base stats, in order: HP, Attack, Defense, Special Attack, Special Defense, Speed
+    pub fn base_stats(&self) -> [u8; 6] {
+        [self.0.base_stat, self.1.base_stat, self.2.base_stat, self.3.base_stat, self.4.base_stat, self.5.base_stat]
+    }
+
+    /// Get the EVs gained for defeating this species, in the same order as the base stats
+    pub fn ev_yields(&self) -> [u8; 6] {
+        [self.0.ev, self.1.ev, self.2.ev, self.3.ev, self.4.ev, self.5.ev]
+    }
+}
+
 impl SpeciesData {
+    /// Get the ability a Pokemon of this species has, given its ability slot.
+    /// If the slot is empty for this species, the first ability is used.
+    pub fn get_ability(&self, slot: AbilitySlot) -> Ability {
+        let first = match self.ability {
+            OneOrTwo::One(a) | OneOrTwo::Two(a, _) => a
+        };
+        match (slot, self.ability, self.hidden_ability) {
+            (AbilitySlot::SlotTwo, OneOrTwo::Two(_, a), _) => a,
+            (AbilitySlot::Hidden, _, Some(a)) => a,
+            _ => first
+        }
+    }
+
+    /// Get every move this species can learn by leveling up, from a TM or HM, or from a Move Tutor
     pub fn get_all_knowable_moves(&self) -> HashSet<Move> {
         let mut set = HashSet::new();
         for (_, pool) in &self.level_up_moves {
             set.extend(pool);
         }
+        set.extend(self.tms.iter().map(|tm| *tm.get_move()));
+        set.extend(self.hms.iter().map(|hm| *hm.get_move()));
+        set.extend(&self.tutor_moves);
         set
     }
 
+    /// Check if this species can learn a TM
+    pub fn can_learn_tm(&self, tm: TM) -> bool {
+        self.tms.contains(&tm)
+    }
+
+    /// Check if this species can learn an HM
+    pub fn can_learn_hm(&self, hm: HM) -> bool {
+        self.hms.contains(&hm)
+    }
+
+    /// Check if this species can learn a move from a Move Tutor
+    pub fn can_learn_from_tutor(&self, attack: Move) -> bool {
+        self.tutor_moves.contains(&attack)
+    }
+
     /// Get a list of all moves this Pokemon can know at the specified level.
     /// The order of the list is from latest learned to earliest. As an example
     /// if this method was called with 40, a level 35 move would show up before a level
@@ -228,7 +588,7 @@ impl SpeciesData {
 }
 
 /// Represents an individual member of a Pokemon species
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pokemon {
     pub species: Species,
     pub gender: Gender,
@@ -267,7 +627,7 @@ pub struct Pokemon {
 }
 
 /// Represents the Contest Stats and Winnings of this Pokemon
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct PokemonContestStats {
     pub coolness: u8,
     pub beauty: u8,
@@ -342,7 +702,7 @@ impl Default for Ribbons {
 }
 
 /// Represents the status conditions of this Pokemon
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct PokemonStatusCondition {
     pub sleep: u8,
     pub poison: bool,
@@ -409,6 +769,19 @@ impl StatSlot {
         }
     }
 
+    /// Get the effort values in this stat
+    pub fn ev(&self) -> u8 {
+        self.ev
+    }
+
+    /// Add effort values to this stat, without going over a limit.
+    /// Returns how many were added. The stat's value must be recalculated afterwards.
+    pub fn add_evs(&mut self, amount: u8, limit: u8) -> u8 {
+        let added = amount.min(limit.saturating_sub(self.ev));
+        self.ev += added;
+        added
+    }
+
     /// Recalculate (in-place) the HP of this Pokemon
     pub fn recalculate_hp(&mut self, base: u8, level: u8) {
         let recalc = StatSlot::hp(base, level, self.iv, self.ev);
@@ -458,12 +831,29 @@ impl Distribution<AbilitySlot> for Standard {
 }
 
 /// Represents the stage of Pokerus the Pokemon is at
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PokemonPokerusStatus {
+    #[default]
     None,
-    Infected(u8),
+    /// The Pokemon is infected with a strain of Pokerus, and will be cured after some days
+    Infected {
+        strain: u8,
+        days: u8
+    },
+    /// The Pokemon was cured, and can never be infected again
     Cured
 }
+impl PokemonPokerusStatus {
+    /// Check if the Pokemon is currently infected, and can spread Pokerus
+    pub fn is_infected(&self) -> bool {
+        matches!(self, PokemonPokerusStatus::Infected { .. })
+    }
+
+    /// Check if the Pokemon has ever had Pokerus. These Pokemon gain double effort values.
+    pub fn doubles_evs(&self) -> bool {
+        *self != PokemonPokerusStatus::None
+    }
+}
 
 /// Represents one of the 25 Natures of a Pokemon
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -569,6 +959,55 @@ impl Pokemon {
         self.current_hp = self.hp.value;
     }
 
+    /// Recalculate every stat from the species data, such as after a form change.
+    /// Any damage the Pokemon has taken is kept.
+    pub fn recalculate_stats(&mut self, data: &SpeciesData) {
+        let damage = self.hp.value.saturating_sub(self.current_hp);
+        let level = self.level;
+        self.hp.recalculate_hp(data.stats.0.base_stat, level);
+        self.attack.recalculate(data.stats.1.base_stat, level, self.nature.get_attack_boost());
+        self.defense.recalculate(data.stats.2.base_stat, level, self.nature.get_defense_boost());
+        self.special_attack.recalculate(data.stats.3.base_stat, level, self.nature.get_special_attack_boost());
+        self.special_defense.recalculate(data.stats.4.base_stat, level, self.nature.get_special_defense_boost());
+        self.speed.recalculate(data.stats.5.base_stat, level, self.nature.get_speed_boost());
+        if !self.is_fainted() {
+            self.current_hp = self.hp.value.saturating_sub(damage).max(1);
+        }
+    }
+
+    /// Get the total effort values across every stat
+    pub fn total_evs(&self) -> u16 {
+        [&self.hp, &self.attack, &self.defense, &self.special_attack, &self.special_defense, &self.speed]
+            .iter()
+            .map(|s| u16::from(s.ev))
+            .sum()
+    }
+
+    /// Gain effort values from defeating a Pokemon, given its effort value yields.
+    /// A Power item adds to its stat's yield, then Macho Brace and Pokerus each double every yield.
+    /// Stats are not recalculated until the next level up. Returns the effort values actually gained.
+    pub fn gain_evs(&mut self, yields: [u8; 6]) -> [u8; 6] {
+        let power_item = [Item::PowerWeight, Item::PowerBracer, Item::PowerBelt, Item::PowerLens, Item::PowerBand, Item::PowerAnklet];
+        let multiplier = if self.is_holding(&Item::MachoBrace) { 2 } else { 1 } *
+            if self.pokerus.doubles_evs() { 2 } else { 1 };
+        let mut remaining = MAX_TOTAL_EVS.saturating_sub(self.total_evs());
+        let mut gained = [0; 6];
+        let stats = [&mut self.hp, &mut self.attack, &mut self.defense, &mut self.special_attack, &mut self.special_defense, &mut self.speed];
+        for (idx, stat) in IntoIterator::into_iter(stats).enumerate() {
+            let bonus = if self.held_item.as_ref() == Some(&power_item[idx]) { 4 } else { 0 };
+            let amount = (u16::from(yields[idx]) + bonus) * multiplier;
+            gained[idx] = stat.add_evs(amount.min(remaining).min(255) as u8, MAX_STAT_EVS);
+            remaining -= u16::from(gained[idx]);
+        }
+        gained
+    }
+
+    /// Change this Pokemon into another form, recalculating its stats using that form's data
+    pub fn change_form(&mut self, form: Species, data: &SpeciesData) {
+        self.species = form;
+        self.recalculate_stats(data);
+    }
+
     pub fn get_hidden_power_type(&self) -> Type {
         let bit = (self.hp.iv & 1) |
             (self.attack.iv & 1) << 1 |
@@ -707,7 +1146,7 @@ impl Pokemon {
 /// Represents the three important pieces of trainer data in a Pokemon.
 /// Typically all three of these are set, or all unset, so it is logical to
 /// have them in a separate class.
-#[derive(Debug)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct TemplateTrainer {
     pub trainer_id: u16,
     pub secret_id: u16,
@@ -719,7 +1158,10 @@ pub struct TemplateTrainer {
 /// will be specified, as well as a level for non-eggs. 
 /// 
 /// In addition, some fields allow for more dynamic or user-friendly generation.
-#[derive(Debug, Default)]
+///
+/// Templates can also be loaded from YAML. There, the species and level must always be given
+/// (a level of 0 makes an egg). Any other field left out uses the same default as above.
+#[derive(Debug, Default, Clone, Deserialize)]
 pub struct PokemonTemplate {
     pub species: SpeciesTemplate,
     pub gender: Option<Gender>,
@@ -728,30 +1170,40 @@ pub struct PokemonTemplate {
     pub ability: Option<AbilitySlot>,
     pub poke_ball: Option<Pokeball>,
     pub held_item: Option<Item>,
+    #[serde(default)]
     pub move_1: MoveTemplate,
+    #[serde(default)]
     pub move_2: MoveTemplate,
+    #[serde(default)]
     pub move_3: MoveTemplate,
+    #[serde(default)]
     pub move_4: MoveTemplate,
     pub personality: Option<u32>,
     pub friendship: Option<u8>,
     pub original_trainer: Option<TemplateTrainer>,
     pub nickname: Option<String>,
     pub level: u8,
+    #[serde(default)]
     pub markings: Markings,
     pub status: Option<PokemonStatusCondition>,
     pub pokerus: Option<PokemonPokerusStatus>,
     pub current_hp: Option<u16>,
+    #[serde(default)]
     pub ivs: IVTemplate,
+    #[serde(default)]
     pub evs: EVTemplate,
     pub contest: Option<PokemonContestStats>,
+    #[serde(default)]
     pub fateful_encounter: bool,
     pub date_caught: Option<i64>,
     pub location_caught: Option<Location>,
+    #[serde(default)]
     pub force_shiny: bool
 }
 
-/// A template which allows for specifying moves
-#[derive(Debug)]
+/// A template which allows for specifying moves.
+/// In YAML, a hard-coded move is written as just the move itself.
+#[derive(Debug, Clone)]
 pub enum MoveTemplate {
     /// Forces this move to be present in this slot.
     HardCoded(Move),
@@ -765,9 +1217,48 @@ impl Default for MoveTemplate {
         MoveTemplate::NaturalMove
     }
 }
+impl<'de> Deserialize<'de> for MoveTemplate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        #[derive(Deserialize)]
+        enum Keyword { NaturalMove, None }
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw { Keyword(Keyword), Move(Move) }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Keyword(Keyword::NaturalMove) => MoveTemplate::NaturalMove,
+            Raw::Keyword(Keyword::None) => MoveTemplate::None,
+            Raw::Move(m) => MoveTemplate::HardCoded(m)
+        })
+    }
+}
+impl MoveTemplate {
+    // Calculate the move for the given move template.
+    // If the move is hard-coded, it is immediately added, provided the Pokemon does not already have it.
+    // If the move is natural, the next non-known level-up move is added. Does nothing if there are no other moves
+    // No move does nothing.
+    fn resolve<T: Iterator<Item = Move>>(self, final_moves: &mut Vec<Move>, available_pool: &mut T) {
+        match self {
+            MoveTemplate::HardCoded(m) => {
+                if !final_moves.contains(&m) {
+                    final_moves.push(m);
+                }
+            }
+            MoveTemplate::NaturalMove => {
+                for m in available_pool.by_ref() {
+                    if !final_moves.contains(&m) {
+                        final_moves.push(m);
+                        break;
+                    }
+                }
+            }
+            MoveTemplate::None => {}
+        }
+    }
+}
 
 /// A template which allows for specifying IVs
-#[derive(Debug)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum IVTemplate {
     /// IVs are randomly generated
     Random,
@@ -785,7 +1276,7 @@ impl Default for IVTemplate {
 }
 
 /// A template which allows for specifying EVs
-#[derive(Debug)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum EVTemplate {
     /// Set all stats individually to a specific value
     HardCoded(u8, u8, u8, u8, u8, u8),
@@ -798,8 +1289,9 @@ impl Default for EVTemplate {
     }
 }
 
-/// A template which allows for dynamic species generation
-#[derive(Debug)]
+/// A template which allows for dynamic species generation.
+/// In YAML, a hard-coded species is written as just the species itself.
+#[derive(Debug, Clone)]
 pub enum SpeciesTemplate {
     /// No dynamic generation
     HardCoded(Species),
@@ -811,6 +1303,29 @@ impl Default for SpeciesTemplate {
         Self::HardCoded(Species::default())
     }
 }
+impl<'de> Deserialize<'de> for SpeciesTemplate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        #[derive(Deserialize)]
+        enum Keyword { RandomUnown }
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw { Keyword(Keyword), Species(Species) }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Keyword(Keyword::RandomUnown) => SpeciesTemplate::RandomUnown,
+            Raw::Species(s) => SpeciesTemplate::HardCoded(s)
+        })
+    }
+}
+impl SpeciesTemplate {
+    /// Determine the species this template creates
+    pub fn resolve<R: Rng + ?Sized>(&self, rng: &mut R) -> Species {
+        match self {
+            SpeciesTemplate::HardCoded(s) => *s,
+            SpeciesTemplate::RandomUnown => Species::Unown(rng.gen())
+        }
+    }
+}
 impl From<Species> for SpeciesTemplate {
     fn from(s: Species) -> Self {
         Self::HardCoded(s)
@@ -921,4 +1436,334 @@ impl PokemonTemplate {
         func(&mut self);
         self
     }
-}
\ No newline at end of file
+
+    /// Create the Pokemon this template describes.
+    /// The species should be resolved first, via SpeciesTemplate::resolve, so the matching species data
+    /// can be provided. If no original trainer was specified, the provided trainer is used instead.
+    /// Each move starts with full PP, as looked up in the move data. Moves without data have no PP.
+    pub fn resolve<R: Rng + ?Sized>(self, species: Species, data: &SpeciesData, moves: &MoveDataLookup, trainer: &TemplateTrainer, rng: &mut R) -> Pokemon {
+        // Calculate the movepool.
+        let mut available_pool = data.get_knowable_moves_for_level(self.level).into_iter();
+        let mut final_moves = Vec::new();
+        self.move_1.resolve(&mut final_moves, &mut available_pool);
+        self.move_2.resolve(&mut final_moves, &mut available_pool);
+        self.move_3.resolve(&mut final_moves, &mut available_pool);
+        self.move_4.resolve(&mut final_moves, &mut available_pool);
+        let mut final_moves = final_moves.into_iter()
+            .map(|m| MoveSlot {
+                attack: m,
+                pp: moves.get(&m).map_or(0, |d| d.pp),
+                pp_bonus: 0,
+            });
+
+        // Use the provided trainer's data if none is provided otherwise.
+        let (trainer_id, trainer_secret, trainer_name) = match self.original_trainer {
+            Some(t) => (t.trainer_id, t.secret_id, t.name),
+            None => (trainer.trainer_id, trainer.secret_id, trainer.name.clone())
+        };
+
+        // Calculate nature now, so we can calculate stats in the next step.
+        let nature = self.nature.unwrap_or_else(|| rng.gen());
+        // Calculate stats
+        let (hp, atk, def, spa, spd, spe) = Self::create_stats(data, self.level, nature, self.ivs, self.evs, rng);
+
+        // Calculate personality value, taking a force shiny into account.
+        let personality = match self.personality {
+            Some(p) => p,
+            None if self.force_shiny => {
+                // The simplest way to generate a Pokemon is to create a personality value which, when
+                // the first half is xor'ed with the second, is equal to trainer_portion. This will cause the
+                // full expression to equal 0, which matches as a shiny
+                let personality_hb = rng.gen::<u16>();
+                let personality_lb = trainer_id ^ trainer_secret ^ personality_hb;
+
+                let personality_hb = personality_hb as u32;
+                let personality_lb = personality_lb as u32;
+
+                (personality_hb << 16) | personality_lb
+            }
+            _ => rng.gen()
+        };
+
+        let gender = match self.gender {
+            Some(g) => g,
+            None => Self::create_gender(data, rng)
+        };
+
+        let poke_ball = self.poke_ball.unwrap_or(Pokeball::PokeBall);
+
+        // Construct the entire Pokemon finally
+        Pokemon {
+            species,
+            gender,
+            egg: self.level == 0,
+            level_met: self.level_met.unwrap_or(self.level),
+            nature,
+            ability: self.ability.unwrap_or_else(|| rng.gen()),
+            poke_ball,
+            held_item: self.held_item,
+            move_1: final_moves.next(),
+            move_2: final_moves.next(),
+            move_3: final_moves.next(),
+            move_4: final_moves.next(),
+            experience: data.level_rate.experience_for_level(self.level),
+            personality,
+            friendship: self.friendship.unwrap_or_else(|| friendship::starting_friendship(poke_ball, data.base_friendship)),
+            original_trainer_id: trainer_id,
+            original_trainer_secret_id: trainer_secret,
+            original_trainer_name: trainer_name,
+            nickname: self.nickname,
+            level: self.level,
+            markings: self.markings,
+            status: self.status.unwrap_or_default(),
+            pokerus: self.pokerus.unwrap_or(PokemonPokerusStatus::None),
+            current_hp: hp.value,
+            hp,
+            attack: atk,
+            defense: def,
+            special_attack: spa,
+            special_defense: spd,
+            speed: spe,
+            contest: self.contest.unwrap_or_default(),
+            fateful_encounter: self.fateful_encounter,
+            date_caught: self.date_caught.unwrap_or(0), // <- Insert Timestamp
+            location_caught: self.location_caught.unwrap_or(Location::FarawayPlace), // <- Retrieve actual location
+        }
+    }
+
+    // Construct a random gender this Pokemon can be.
+    fn create_gender<R: Rng + ?Sized>(data: &SpeciesData, rng: &mut R) -> Gender {
+        match data.gender_ratio {
+            GenderRatio::None | GenderRatio::Proportion(0, 0) => Gender::None,
+            GenderRatio::Proportion(0, _) => Gender::Female,
+            GenderRatio::Proportion(_, 0) => Gender::Male,
+            GenderRatio::Proportion(m, f) => {
+                let ratio = (m as f64) / ((m + f) as f64);
+                if rng.gen_bool(ratio) { Gender::Male } else { Gender::Female }
+            }
+        }
+    }
+
+    /// Calculate the stat block of a Pokemon.
+    /// Also resolves IV and EV templates.
+    fn create_stats<R: Rng + ?Sized>(data: &SpeciesData, level: u8, nature: Nature, ivs: IVTemplate, evs: EVTemplate, rng: &mut R) -> (StatSlot, StatSlot, StatSlot, StatSlot, StatSlot, StatSlot) {
+        let ivs = match ivs {
+            IVTemplate::Random => [
+                rng.gen_range(0..=31),
+                rng.gen_range(0..=31),
+                rng.gen_range(0..=31),
+                rng.gen_range(0..=31),
+                rng.gen_range(0..=31),
+                rng.gen_range(0..=31)
+            ],
+            IVTemplate::HardCoded(hp, atk, def, spa, spd, spe) => [hp, atk, def, spa, spd, spe],
+            IVTemplate::All(val) => [val; 6],
+            IVTemplate::Rare => {
+                let mut lucky_slots = [false; 6];
+                let mut counter = 0;
+                while counter < 3 {
+                    let slot = rng.gen_range(0..6);
+                    if !lucky_slots[slot] {
+                        lucky_slots[slot] = true;
+                        counter += 1;
+                    }
+                }
+                let mut stats = [0u8; 6];
+                for (idx, slot) in stats.iter_mut().enumerate() {
+                    if lucky_slots[idx] {
+                        *slot = 31u8;
+                    } else {
+                        *slot = rng.gen_range(0u8..=31u8);
+                    }
+                }
+                stats
+            }
+        };
+        let evs = match evs {
+            EVTemplate::HardCoded(a, b, c, d, e, f) => [a, b, c, d, e, f],
+            EVTemplate::All(v) => [v; 6]
+        };
+        (
+            StatSlot::hp(data.stats.0.base_stat, level, ivs[0], evs[0]),
+            StatSlot::stat(data.stats.1.base_stat, level, ivs[1], evs[1], nature.get_attack_boost()),
+            StatSlot::stat(data.stats.2.base_stat, level, ivs[2], evs[2], nature.get_defense_boost()),
+            StatSlot::stat(data.stats.3.base_stat, level, ivs[3], evs[3], nature.get_special_attack_boost()),
+            StatSlot::stat(data.stats.4.base_stat, level, ivs[4], evs[4], nature.get_special_defense_boost()),
+            StatSlot::stat(data.stats.5.base_stat, level, ivs[5], evs[5], nature.get_speed_boost()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::shipped_species;
+    use crate::species::{DeoxysForm, GiratinaForm};
+
+    use super::*;
+
+    #[test]
+    fn template_defaults() {
+        let template: PokemonTemplate = serde_yaml::from_str("species: Pikachu\nlevel: 5").unwrap();
+        assert!(matches!(template.species, SpeciesTemplate::HardCoded(Species::Pikachu)));
+        assert_eq!(template.level, 5);
+        assert!(matches!(template.move_1, MoveTemplate::NaturalMove));
+        assert!(matches!(template.move_4, MoveTemplate::NaturalMove));
+        assert!(matches!(template.ivs, IVTemplate::Random));
+        assert!(matches!(template.evs, EVTemplate::All(0)));
+        assert!(template.nature.is_none());
+        assert!(template.original_trainer.is_none());
+        assert!(template.current_hp.is_none());
+        assert_eq!(template.markings, Markings::empty());
+        assert!(!template.force_shiny);
+        assert!(!template.fateful_encounter);
+    }
+
+    #[test]
+    fn template_requires_species_and_level() {
+        assert!(serde_yaml::from_str::<PokemonTemplate>("level: 5").is_err());
+        assert!(serde_yaml::from_str::<PokemonTemplate>("species: Pikachu").is_err());
+        let egg: PokemonTemplate = serde_yaml::from_str("species: Pikachu\nlevel: 0").unwrap();
+        assert_eq!(egg.level, 0);
+    }
+
+    #[test]
+    fn template_keywords() {
+        let template: PokemonTemplate = serde_yaml::from_str("
+species: RandomUnown
+level: 10
+move_1: Tackle
+move_2: None
+ivs: Rare
+evs:
+  All: 4
+").unwrap();
+        assert!(matches!(template.species, SpeciesTemplate::RandomUnown));
+        assert!(matches!(template.move_1, MoveTemplate::HardCoded(Move::Tackle)));
+        assert!(matches!(template.move_2, MoveTemplate::None));
+        assert!(matches!(template.move_3, MoveTemplate::NaturalMove));
+        assert!(matches!(template.ivs, IVTemplate::Rare));
+        assert!(matches!(template.evs, EVTemplate::All(4)));
+    }
+
+    #[test]
+    fn form_overrides() {
+        let mappings: Vec<SpeciesDataMapping> = serde_yaml::from_str("
+- id:
+    Giratina: Altered
+  data:
+    type: [Ghost, Dragon]
+    ability: [Pressure]
+    hidden_ability: Telepathy
+    gender_ratio: None
+    catch_rate: 3
+    egg_group: ~
+    egg_cycles: 120
+    height: 45
+    weight: 7500
+    base_exp_yield: 306
+    level_rate: Slow
+    stats: [{base_stat: 150, ev: 3}, {base_stat: 100}, {base_stat: 120}, {base_stat: 100}, {base_stat: 120}, {base_stat: 90}]
+    base_friendship: 0
+    level_up_moves: {1: [DragonBreath]}
+    egg_moves: ~
+    color: Black
+- id:
+    Giratina: Origin
+  form_of:
+    Giratina: Altered
+  overrides:
+    ability: [Levitate]
+    hidden_ability: ~
+- id:
+    Deoxys: Attack
+  form_of:
+    Deoxys: Normal
+").unwrap();
+        let (mut lookup, problems) = resolve_species_data(mappings);
+        assert_eq!(problems, vec![SpeciesDataProblem::MissingBase(Species::Deoxys(DeoxysForm::Attack), Species::Deoxys(DeoxysForm::Normal))]);
+
+        let origin = species_data(&lookup, Species::Giratina(GiratinaForm::Origin)).unwrap();
+        assert!(matches!(origin.ability, OneOrTwo::One(Ability::Levitate)));
+        assert_eq!(origin.hidden_ability, None);
+        assert_eq!(origin.stats.base_stats(), [150, 100, 120, 100, 120, 90]);
+        assert_eq!(origin.color, Color::Black);
+
+        // A form without its own entry falls back to the base form
+        let origin = Species::Giratina(GiratinaForm::Origin);
+        lookup.remove(&origin);
+        assert_eq!(species_data(&lookup, origin).and_then(|d| d.hidden_ability), Some(Ability::Telepathy));
+    }
+
+    #[test]
+    fn resolved_moves_have_full_pp() {
+        use rand::SeedableRng;
+        use crate::attack::MoveData;
+        use crate::battle::replay::BattleRng;
+
+        let species = shipped_species();
+        let moves = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../glazed-dx/assets/pkmn/moves.attacks")).unwrap();
+        let moves = serde_yaml::from_str::<Vec<MoveData>>(&moves).unwrap()
+            .into_iter()
+            .map(|m| (m.id, m))
+            .collect::<MoveDataLookup>();
+        let trainer = TemplateTrainer { trainer_id: 0, secret_id: 0, name: String::from("Test") };
+
+        let template = PokemonTemplate::pokemon(Species::Pikachu, 20).moves(vec![Move::ThunderShock, Move::Thunderbolt, Move::VoltTackle]);
+        let pokemon = template.resolve(Species::Pikachu, &species[&Species::Pikachu], &moves, &trainer, &mut BattleRng::seed_from_u64(0));
+        let pp = |slot: &Option<MoveSlot>| slot.as_ref().map(|s| s.pp);
+        assert_eq!(pp(&pokemon.move_1), Some(30));
+        assert_eq!(pp(&pokemon.move_2), Some(15));
+        assert_eq!(pp(&pokemon.move_3), Some(0));
+        assert_eq!(pp(&pokemon.move_4), None);
+    }
+
+    /// Validates the species data the game ships with, listing every problem if there are any
+    #[test]
+    fn shipped_species_data_is_valid() {
+        let yaml = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../glazed-dx/assets/pkmn/data.pkmn")).unwrap();
+        if let Err(SpeciesDataError(problems)) = validate_species_data(&yaml) {
+            let problems = problems.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+            panic!("{} problems in data.pkmn:\n{}", problems.len(), problems.join("\n"));
+        }
+    }
+
+    #[test]
+    fn species_data_problems() {
+        let entry = "
+- id: Bulbasaur
+  data:
+    type: [Grass, Poison]
+    ability: [Overgrow]
+    hidden_ability: Chlorophyll
+    gender_ratio: {Proportion: [2, 1]}
+    catch_rate: 45
+    egg_group: [Monster, Ditto]
+    egg_cycles: 20
+    height: 7
+    weight: 0
+    base_exp_yield: 64
+    level_rate: MediumSlow
+    stats: [{base_stat: 45}, {base_stat: 49}, {base_stat: 49}, {base_stat: 65, ev: 4}, {base_stat: 65}, {base_stat: 45}]
+    base_friendship: 70
+    level_up_moves: {1: [Tackle], 3: [Growl]}
+    egg_moves: ~
+    color: Green
+";
+        let (lookup, _) = resolve_species_data(serde_yaml::from_str(entry).unwrap());
+        let problems = check_species_data(&lookup);
+        assert!(problems.contains(&SpeciesDataProblem::MissingSpecies(SpeciesDiscriminants::Venusaur)));
+        assert!(problems.contains(&SpeciesDataProblem::InvalidEvYield(Species::Bulbasaur, [0, 0, 0, 4, 0, 0])));
+        assert!(problems.contains(&SpeciesDataProblem::InvalidGenderRatio(Species::Bulbasaur, GenderRatio::Proportion(2, 1))));
+        assert!(problems.contains(&SpeciesDataProblem::GenderedDitto(Species::Bulbasaur)));
+        assert!(problems.contains(&SpeciesDataProblem::ZeroField(Species::Bulbasaur, "weight")));
+        assert!(problems.contains(&SpeciesDataProblem::NoMachineMoves(Species::Bulbasaur)));
+
+        // Unreadable entries are reported by name, alongside every other problem
+        let typos = entry.replace("Tackle", "Tackel").replace("Growl", "Grwol") + "- id: Ivysaur\n  form_of: Bulbasaur\n";
+        let SpeciesDataError(problems) = validate_species_data(&typos).unwrap_err();
+        assert_eq!(problems[..3], [SpeciesDataProblem::UnknownMove(String::from("Bulbasaur"), String::from("Tackel")),
+                                   SpeciesDataProblem::UnknownMove(String::from("Bulbasaur"), String::from("Grwol")),
+                                   SpeciesDataProblem::MissingBase(Species::Ivysaur, Species::Bulbasaur)]);
+        assert!(problems.contains(&SpeciesDataProblem::MissingSpecies(SpeciesDiscriminants::Bulbasaur)));
+    }
+}
@@ -0,0 +1,193 @@
+use chrono::{Datelike, NaiveDate};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::pokemon::{Pokemon, PokemonPokerusStatus};
+
+/// The chance, out of 65536, that a party Pokemon catches Pokerus after a battle
+pub const INFECTION_CHANCE: u16 = 3;
+/// The chance, out of this, that an infected Pokemon spreads Pokerus to each neighbor after a battle
+pub const SPREAD_CHANCE: u8 = 3;
+/// The number of strains of Pokerus
+pub const STRAINS: u8 = 15;
+
+/// Get how many days a strain of Pokerus lasts before the Pokemon is cured
+pub fn days_for_strain(strain: u8) -> u8 {
+    strain % 4 + 1
+}
+
+/// Infect a Pokemon with a strain of Pokerus.
+/// Returns false if the Pokemon already has, or has had, Pokerus.
+pub fn infect(pokemon: &mut Pokemon, strain: u8) -> bool {
+    if pokemon.pokerus != PokemonPokerusStatus::None {
+        return false;
+    }
+    pokemon.pokerus = PokemonPokerusStatus::Infected { strain, days: days_for_strain(strain) };
+    true
+}
+
+/// Roll the rare chance for a random party Pokemon to catch a random strain of Pokerus.
+/// Returns the index of the Pokemon that caught it.
+pub fn roll_infection<R: Rng + ?Sized>(party: &mut [Pokemon], rng: &mut R) -> Option<usize> {
+    if party.is_empty() || rng.gen::<u16>() >= INFECTION_CHANCE {
+        return None;
+    }
+    let idx = rng.gen_range(0..party.len());
+    let strain = rng.gen_range(1..=STRAINS);
+    infect(&mut party[idx], strain).then_some(idx)
+}
+
+/// Give each infected Pokemon the chance to spread Pokerus to the Pokemon next to it in the party.
+/// Pokemon infected this way catch the same strain, and do not spread it further until the next battle.
+/// Returns the index of each Pokemon newly infected.
+pub fn spread<R: Rng + ?Sized>(party: &mut [Pokemon], rng: &mut R) -> Vec<usize> {
+    let carriers = party.iter()
+        .enumerate()
+        .filter_map(|(idx, p)| match p.pokerus {
+            PokemonPokerusStatus::Infected { strain, .. } => Some((idx, strain)),
+            _ => None
+        })
+        .collect::<Vec<_>>();
+    let size = party.len();
+    let mut infected = Vec::new();
+    for (idx, strain) in carriers {
+        let neighbors = [idx.checked_sub(1), Some(idx + 1)];
+        for neighbor in IntoIterator::into_iter(neighbors).flatten().filter(|n| *n < size) {
+            if rng.gen_range(0..SPREAD_CHANCE) == 0 && infect(&mut party[neighbor], strain) {
+                infected.push(neighbor);
+            }
+        }
+    }
+    infected
+}
+
+/// Roll for Pokerus infection and spread after a battle.
+/// Returns the index of each Pokemon newly infected.
+pub fn after_battle<R: Rng + ?Sized>(party: &mut [Pokemon], rng: &mut R) -> Vec<usize> {
+    let mut infected = spread(party, rng);
+    infected.extend(roll_infection(party, rng));
+    infected
+}
+
+/// Count down each infected party Pokemon's days, curing those which run out.
+/// Pokemon stored in boxes are not counted down.
+/// Returns the index of each Pokemon cured.
+pub fn pass_days(party: &mut [Pokemon], elapsed: u32) -> Vec<usize> {
+    let mut cured = Vec::new();
+    for (idx, pokemon) in party.iter_mut().enumerate() {
+        if let PokemonPokerusStatus::Infected { strain, days } = pokemon.pokerus {
+            let days = u32::from(days).saturating_sub(elapsed) as u8;
+            pokemon.pokerus = if days == 0 {
+                cured.push(idx);
+                PokemonPokerusStatus::Cured
+            } else {
+                PokemonPokerusStatus::Infected { strain, days }
+            };
+        }
+    }
+    cured
+}
+
+/// Remembers the last day Pokerus was counted down, so each new day counts down the party once
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PokerusClock {
+    /// The last day seen, as days since the common era
+    last_day: Option<i32>
+}
+impl PokerusClock {
+    /// Count down Pokerus for every day that has passed since the last update.
+    /// Setting the clock backwards does not count down, or cure, anything, and the last day is kept,
+    /// so days are not counted twice once the clock catches up.
+    /// Returns the index of each Pokemon cured.
+    pub fn update(&mut self, today: NaiveDate, party: &mut [Pokemon]) -> Vec<usize> {
+        let today = today.num_days_from_ce();
+        let elapsed = match self.last_day {
+            Some(last) if today < last => return Vec::new(),
+            Some(last) => today - last,
+            None => 0
+        };
+        self.last_day = Some(today);
+        if elapsed > 0 { pass_days(party, elapsed as u32) } else { Vec::new() }
+    }
+
+    /// Count down Pokerus using the system's current date
+    pub fn update_today(&mut self, party: &mut [Pokemon]) -> Vec<usize> {
+        self.update(chrono::Local::now().date_naive(), party)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256StarStar;
+
+    use crate::battle::testing::pikachu;
+    use crate::item::Item;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn party(size: usize) -> Vec<Pokemon> {
+        (0..size)
+            .map(|_| pikachu(20, &[]).pokemon)
+            .collect()
+    }
+
+    #[test]
+    fn lifecycle() {
+        let mut party = party(3);
+        let mut rng = Xoshiro256StarStar::seed_from_u64(0);
+        assert!(infect(&mut party[1], 2));
+        assert_eq!(party[1].pokerus, PokemonPokerusStatus::Infected { strain: 2, days: 3 });
+
+        let mut infected = Vec::new();
+        for _ in 0..50 {
+            infected.extend(spread(&mut party, &mut rng));
+        }
+        infected.sort_unstable();
+        assert_eq!(infected, vec![0, 2]);
+        assert!(party.iter().all(|p| p.pokerus == PokemonPokerusStatus::Infected { strain: 2, days: 3 }));
+
+        let mut clock = PokerusClock::default();
+        let day = date(2022, 6, 1);
+        assert!(clock.update(day, &mut party).is_empty());
+        assert!(clock.update(date(2022, 6, 2), &mut party).is_empty());
+        assert!(clock.update(day, &mut party).is_empty());
+        assert!(party.iter().all(|p| p.pokerus == PokemonPokerusStatus::Infected { strain: 2, days: 2 }));
+        // Only 6/3 and 6/4 are new days, as 6/2 was already counted before the clock went back
+        assert!(clock.update(date(2022, 6, 3), &mut party).is_empty());
+        assert_eq!(clock.update(date(2022, 6, 4), &mut party), vec![0, 1, 2]);
+        assert_eq!(party[0].pokerus, PokemonPokerusStatus::Cured);
+        assert!(!infect(&mut party[0], 5));
+
+        let yaml = serde_yaml::to_string(&(&clock, &party[0].pokerus)).unwrap();
+        let (loaded, status): (PokerusClock, PokemonPokerusStatus) = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!((loaded.last_day, status), (clock.last_day, PokemonPokerusStatus::Cured));
+    }
+
+    #[test]
+    fn infection_is_rare() {
+        let mut rng = Xoshiro256StarStar::seed_from_u64(1);
+        let infections = (0..100_000)
+            .filter(|_| roll_infection(&mut party(1), &mut rng).is_some())
+            .count();
+        assert!(infections < 30);
+    }
+
+    #[test]
+    fn doubles_evs() {
+        let mut party = party(1);
+        let pokemon = &mut party[0];
+        assert_eq!(pokemon.gain_evs([0, 0, 0, 0, 0, 2]), [0, 0, 0, 0, 0, 2]);
+        infect(pokemon, 1);
+        assert_eq!(pokemon.gain_evs([0, 0, 0, 0, 0, 2]), [0, 0, 0, 0, 0, 4]);
+        pokemon.held_item = Some(Item::PowerBracer);
+        assert_eq!(pokemon.gain_evs([0, 0, 0, 0, 0, 2]), [0, 8, 0, 0, 0, 4]);
+        pokemon.held_item = Some(Item::MachoBrace);
+        assert_eq!(pokemon.gain_evs([1, 0, 0, 0, 0, 0]), [4, 0, 0, 0, 0, 0]);
+        assert_eq!(pokemon.total_evs(), 22);
+    }
+}
@@ -0,0 +1,182 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::friendship::MAX_FRIENDSHIP;
+use crate::pokemon::{MAX_TOTAL_EVS, Pokemon, Ribbons};
+
+/// Every known ribbon, in the order they are shown in the ribbon case, with the key used to translate it
+pub const RIBBON_KEYS: [(Ribbons, &str); 29] = [
+    (Ribbons::LEAGUE_RIBBON, "league-ribbon"),
+    (Ribbons::COOL_RIBBON, "cool-ribbon"),
+    (Ribbons::COOL_SUPER_RIBBON, "cool-super-ribbon"),
+    (Ribbons::COOL_HYPER_RIBBON, "cool-hyper-ribbon"),
+    (Ribbons::COOL_MASTER_RIBBON, "cool-master-ribbon"),
+    (Ribbons::BEAUTY_RIBBON, "beauty-ribbon"),
+    (Ribbons::BEAUTY_SUPER_RIBBON, "beauty-super-ribbon"),
+    (Ribbons::BEAUTY_HYPER_RIBBON, "beauty-hyper-ribbon"),
+    (Ribbons::BEAUTY_MASTER_RIBBON, "beauty-master-ribbon"),
+    (Ribbons::CUTE_RIBBON, "cute-ribbon"),
+    (Ribbons::CUTE_SUPER_RIBBON, "cute-super-ribbon"),
+    (Ribbons::CUTE_HYPER_RIBBON, "cute-hyper-ribbon"),
+    (Ribbons::CUTE_MASTER_RIBBON, "cute-master-ribbon"),
+    (Ribbons::SMART_RIBBON, "smart-ribbon"),
+    (Ribbons::SMART_SUPER_RIBBON, "smart-super-ribbon"),
+    (Ribbons::SMART_HYPER_RIBBON, "smart-hyper-ribbon"),
+    (Ribbons::SMART_MASTER_RIBBON, "smart-master-ribbon"),
+    (Ribbons::TOUGH_RIBBON, "tough-ribbon"),
+    (Ribbons::TOUGH_SUPER_RIBBON, "tough-super-ribbon"),
+    (Ribbons::TOUGH_HYPER_RIBBON, "tough-hyper-ribbon"),
+    (Ribbons::TOUGH_MASTER_RIBBON, "tough-master-ribbon"),
+    (Ribbons::WINNING_RIBBON, "winning-ribbon"),
+    (Ribbons::VICTORY_RIBBON, "victory-ribbon"),
+    (Ribbons::ARTIST_RIBBON, "artist-ribbon"),
+    (Ribbons::EFFORT_RIBBON, "effort-ribbon"),
+    (Ribbons::GORGEOUS_RIBBON, "gorgeous-ribbon"),
+    (Ribbons::ROYAL_RIBBON, "royal-ribbon"),
+    (Ribbons::GORGEOUS_ROYAL_RIBBON, "gorgeous-royal-ribbon"),
+    (Ribbons::FOOTPRINT_RIBBON, "footprint-ribbon")
+];
+
+impl Ribbons {
+    /// Get the key used to translate a single ribbon's name and description
+    pub fn key(&self) -> Option<&'static str> {
+        RIBBON_KEYS.iter()
+            .find(|(ribbon, _)| ribbon == self)
+            .map(|(_, key)| *key)
+    }
+
+    /// List each ribbon held, in ribbon case order
+    pub fn list(&self) -> Vec<Ribbons> {
+        RIBBON_KEYS.iter()
+            .map(|(ribbon, _)| *ribbon)
+            .filter(|ribbon| self.contains(*ribbon))
+            .collect()
+    }
+}
+
+/// Reasons a ribbon could not be bought
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RibbonError {
+    /// Only the Gorgeous, Royal and Gorgeous Royal ribbons are sold
+    NotForSale,
+    AlreadyHas,
+    /// Eggs can't be given ribbons
+    Egg,
+    /// The player needs this much money to buy the ribbon
+    NotEnoughMoney(u32)
+}
+impl Display for RibbonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RibbonError::NotForSale => write!(f, "That ribbon is not for sale"),
+            RibbonError::AlreadyHas => write!(f, "The Pokemon already has that ribbon"),
+            RibbonError::Egg => write!(f, "An egg can't be given a ribbon"),
+            RibbonError::NotEnoughMoney(m) => write!(f, "The ribbon costs ${}", m)
+        }
+    }
+}
+impl Error for RibbonError {}
+
+/// Give a ribbon to a Pokemon, if it does not have it already.
+/// Returns true if the ribbon was given.
+fn award(pokemon: &mut Pokemon, ribbon: Ribbons) -> bool {
+    if pokemon.egg || pokemon.contest.ribbons.contains(ribbon) {
+        return false;
+    }
+    pokemon.contest.ribbons.insert(ribbon);
+    true
+}
+
+/// Give the Effort Ribbon to a Pokemon that has earned the most EVs possible
+pub fn award_effort(pokemon: &mut Pokemon) -> bool {
+    pokemon.total_evs() >= MAX_TOTAL_EVS && award(pokemon, Ribbons::EFFORT_RIBBON)
+}
+
+/// Give the Footprint Ribbon to a Pokemon with the most friendship possible
+pub fn award_footprint(pokemon: &mut Pokemon) -> bool {
+    pokemon.friendship == MAX_FRIENDSHIP && award(pokemon, Ribbons::FOOTPRINT_RIBBON)
+}
+
+/// Give every ribbon a Pokemon has earned by its own stats, such as the Effort and Footprint Ribbons.
+/// Returns the ribbons newly given.
+pub fn award_earned(pokemon: &mut Pokemon) -> Ribbons {
+    let mut awarded = Ribbons::empty();
+    awarded.set(Ribbons::EFFORT_RIBBON, award_effort(pokemon));
+    awarded.set(Ribbons::FOOTPRINT_RIBBON, award_footprint(pokemon));
+    awarded
+}
+
+/// Give the League Ribbon to every Pokemon in the party, when they are entered into the Hall of Fame.
+/// Returns the index of each Pokemon newly given the ribbon.
+pub fn award_league(party: &mut [Pokemon]) -> Vec<usize> {
+    party.iter_mut()
+        .enumerate()
+        .filter_map(|(idx, pokemon)| award(pokemon, Ribbons::LEAGUE_RIBBON).then_some(idx))
+        .collect()
+}
+
+/// Get the price of a ribbon that can be bought
+pub fn ribbon_price(ribbon: Ribbons) -> Option<u32> {
+    match ribbon {
+        Ribbons::GORGEOUS_RIBBON => Some(10_000),
+        Ribbons::ROYAL_RIBBON => Some(100_000),
+        Ribbons::GORGEOUS_ROYAL_RIBBON => Some(999_999),
+        _ => None
+    }
+}
+
+/// Buy a ribbon for a Pokemon, taking its price from the player's money
+pub fn buy_ribbon(pokemon: &mut Pokemon, ribbon: Ribbons, money: &mut u32) -> Result<(), RibbonError> {
+    let price = ribbon_price(ribbon).ok_or(RibbonError::NotForSale)?;
+    if pokemon.egg {
+        return Err(RibbonError::Egg);
+    }
+    if pokemon.contest.ribbons.contains(ribbon) {
+        return Err(RibbonError::AlreadyHas);
+    }
+    if *money < price {
+        return Err(RibbonError::NotEnoughMoney(price));
+    }
+    *money -= price;
+    award(pokemon, ribbon);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::battle::testing::pikachu;
+
+    use super::*;
+
+    #[test]
+    fn earned_ribbons() {
+        let mut pokemon = pikachu(20, &[]).pokemon;
+        pokemon.friendship = MAX_FRIENDSHIP - 1;
+        pokemon.gain_evs([252, 0, 0, 0, 0, 252]);
+        assert_eq!(award_earned(&mut pokemon), Ribbons::empty());
+
+        pokemon.friendship = MAX_FRIENDSHIP;
+        pokemon.gain_evs([0, 6, 0, 0, 0, 0]);
+        assert_eq!(award_earned(&mut pokemon), Ribbons::EFFORT_RIBBON | Ribbons::FOOTPRINT_RIBBON);
+        assert_eq!(award_earned(&mut pokemon), Ribbons::empty());
+
+        let mut party = vec![pikachu(20, &[]).pokemon, pokemon];
+        party[0].egg = true;
+        assert_eq!(award_league(&mut party), vec![1]);
+        assert_eq!(party[1].contest.ribbons.list(), vec![Ribbons::LEAGUE_RIBBON, Ribbons::EFFORT_RIBBON, Ribbons::FOOTPRINT_RIBBON]);
+    }
+
+    #[test]
+    fn bought_ribbons() {
+        let mut pokemon = pikachu(20, &[]).pokemon;
+        let mut money = 110_000;
+        assert_eq!(buy_ribbon(&mut pokemon, Ribbons::LEAGUE_RIBBON, &mut money), Err(RibbonError::NotForSale));
+        buy_ribbon(&mut pokemon, Ribbons::ROYAL_RIBBON, &mut money).unwrap();
+        assert_eq!(buy_ribbon(&mut pokemon, Ribbons::ROYAL_RIBBON, &mut money), Err(RibbonError::AlreadyHas));
+        assert_eq!(buy_ribbon(&mut pokemon, Ribbons::GORGEOUS_ROYAL_RIBBON, &mut money), Err(RibbonError::NotEnoughMoney(999_999)));
+        buy_ribbon(&mut pokemon, Ribbons::GORGEOUS_RIBBON, &mut money).unwrap();
+        assert_eq!(money, 0);
+        assert_eq!(pokemon.contest.ribbons.list().iter().map(|r| r.key()).collect::<Vec<_>>(), vec![Some("gorgeous-ribbon"), Some("royal-ribbon")]);
+        assert_eq!((Ribbons::COOL_RIBBON | Ribbons::CUTE_RIBBON).key(), None);
+    }
+}
@@ -3,15 +3,15 @@
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
 use rand::Rng;
-use serde::Deserialize;
-use strum_macros::{EnumDiscriminants, IntoStaticStr};
+use serde::{Deserialize, Serialize};
+use strum_macros::{EnumDiscriminants, EnumIter, IntoStaticStr};
 
 use crate::time::Season;
 use crate::types::Type;
 
 //region Pokemon Species Enums
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash, EnumDiscriminants)]
-#[strum_discriminants(derive(IntoStaticStr))]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash, EnumDiscriminants)]
+#[strum_discriminants(derive(IntoStaticStr, EnumIter, Hash, Serialize, Deserialize))]
 pub enum Species {
     Bulbasaur,
     Ivysaur,
@@ -668,8 +668,40 @@ impl Default for Species {
         Species::Bulbasaur
     }
 }
+impl Species {
+    /// Get the form whose species data other forms of this species inherit from.
+    /// Species without forms are their own base form.
+    pub fn base_form(&self) -> Species {
+        match self {
+            Species::Unown(_) => Species::Unown(UnownForm::A),
+            Species::Castform(_) => Species::Castform(CastformForm::Normal),
+            Species::Deoxys(_) => Species::Deoxys(DeoxysForm::Normal),
+            Species::Burmy(_) => Species::Burmy(BurmyWormadamForm::Plant),
+            Species::Wormadam(_) => Species::Wormadam(BurmyWormadamForm::Plant),
+            Species::Cherrim(_) => Species::Cherrim(CherrimForm::Overcast),
+            Species::Shellos(_) => Species::Shellos(ShellosGastrodonForm::WestSea),
+            Species::Gastrodon(_) => Species::Gastrodon(ShellosGastrodonForm::WestSea),
+            Species::Rotom(_) => Species::Rotom(RotomForm::Normal),
+            Species::Giratina(_) => Species::Giratina(GiratinaForm::Altered),
+            Species::Shaymin(_) => Species::Shaymin(ShayminForm::Land),
+            Species::Arceus(_) => Species::Arceus(Type::Normal),
+            Species::Basculin(_) => Species::Basculin(BasculinForm::RedStriped),
+            Species::Darmanitan(_) => Species::Darmanitan(DarmanitanForm::Standard),
+            Species::Deerling(_) => Species::Deerling(Season::Spring),
+            Species::Sawsbuck(_) => Species::Sawsbuck(Season::Spring),
+            Species::Tornadus(_) => Species::Tornadus(ForcesOfNatureForm::Incarnate),
+            Species::Thundurus(_) => Species::Thundurus(ForcesOfNatureForm::Incarnate),
+            Species::Landorus(_) => Species::Landorus(ForcesOfNatureForm::Incarnate),
+            Species::Kyurem(_) => Species::Kyurem(KyuremForm::Normal),
+            Species::Keldeo(_) => Species::Keldeo(KeldeoForm::Ordinary),
+            Species::Meloetta(_) => Species::Meloetta(MeloettaForm::Aria),
+            Species::Genesect(_) => Species::Genesect(GenesectForm::Normal),
+            s => *s
+        }
+    }
+}
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash, IntoStaticStr)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash, IntoStaticStr)]
 pub enum UnownForm {
     A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
     ExclamationMark,
@@ -711,7 +743,7 @@ impl Distribution<UnownForm> for Standard {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum CastformForm {
     Normal, Sunny, Rainy, Snowy
 }
@@ -719,7 +751,7 @@ impl Default for CastformForm {
     fn default() -> Self { CastformForm::Normal }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum DeoxysForm {
     Normal, Attack, Defense, Speed
 }
@@ -727,7 +759,7 @@ impl Default for DeoxysForm {
     fn default() -> Self { DeoxysForm::Normal }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum BurmyWormadamForm {
     Plant, Sandy, Trash
 }
@@ -735,7 +767,7 @@ impl Default for BurmyWormadamForm {
     fn default() -> Self { BurmyWormadamForm::Plant }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum CherrimForm {
     Overcast, Sunshine
 }
@@ -743,12 +775,12 @@ impl Default for CherrimForm {
     fn default() -> Self { CherrimForm::Overcast }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum ShellosGastrodonForm {
     WestSea, EastSea
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum RotomForm {
     Normal, Heat, Wash, Frost, Fan, Mow
 }
@@ -756,7 +788,7 @@ impl Default for RotomForm {
     fn default() -> Self { RotomForm::Normal }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum GiratinaForm {
     Altered, Origin
 }
@@ -764,7 +796,7 @@ impl Default for GiratinaForm {
     fn default() -> Self { GiratinaForm::Altered }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum ShayminForm {
     Land, Sky
 }
@@ -772,7 +804,7 @@ impl Default for ShayminForm {
     fn default() -> Self { ShayminForm::Sky }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum BasculinForm {
     RedStriped, BlueStriped
 }
@@ -786,7 +818,7 @@ impl Distribution<BasculinForm> for Standard {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum DarmanitanForm {
     Standard, Zen
 }
@@ -794,7 +826,7 @@ impl Default for DarmanitanForm {
     fn default() -> Self { DarmanitanForm::Standard }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum ForcesOfNatureForm {
     Incarnate, Therian
 }
@@ -802,7 +834,7 @@ impl Default for ForcesOfNatureForm {
     fn default() -> Self { ForcesOfNatureForm::Incarnate }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum KyuremForm {
     Normal, White, Black
 }
@@ -810,7 +842,7 @@ impl Default for KyuremForm {
     fn default() -> Self { KyuremForm::Normal }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum KeldeoForm {
     Ordinary, Resolute
 }
@@ -818,7 +850,7 @@ impl Default for KeldeoForm {
     fn default() -> Self { KeldeoForm::Ordinary }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum MeloettaForm {
     Aria, Pirouette
 }
@@ -826,7 +858,7 @@ impl Default for MeloettaForm {
     fn default() -> Self { MeloettaForm::Aria }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum GenesectForm {
     Normal, Shock, Burn, Chill, Douse
 }
@@ -0,0 +1,192 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::IntoStaticStr;
+
+use crate::battle::ai::AiTier;
+use crate::item::Item;
+use crate::pokemon::{PokemonTemplate, TemplateTrainer};
+
+/// The most money the player can hold
+pub const MAX_MONEY: u32 = 999_999;
+
+/// The base amount of money lost on a whiteout, indexed by number of badges
+const WHITEOUT_BASE_PAYOUT: [u32; 9] = [8, 16, 24, 36, 48, 64, 80, 100, 120];
+
+/// Represents the class of a trainer.
+/// Aside from flavor, this determines the default prize money for beating them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, IntoStaticStr)]
+pub enum TrainerClass {
+    Youngster, Lass, BugCatcher, Schoolkid, Preschooler, Twins,
+    Camper, Picnicker, Hiker, Fisherman, Sailor, Swimmer, Breeder,
+    BlackBelt, Battlegirl, Psychic, Gentleman, RichBoy, Lady, Socialite, Beauty,
+    Scientist, AceTrainer, Veteran,
+    Rival, GymLeader, EliteFour, Champion
+}
+impl TrainerClass {
+    /// The prize money for each level of the trainer's last Pokemon
+    pub fn base_payout(&self) -> u32 {
+        match self {
+            TrainerClass::Preschooler => 4,
+            TrainerClass::Swimmer => 8,
+            TrainerClass::Twins | TrainerClass::Youngster | TrainerClass::Lass | TrainerClass::BugCatcher |
+            TrainerClass::Camper | TrainerClass::Picnicker => 16,
+            TrainerClass::Schoolkid | TrainerClass::BlackBelt | TrainerClass::Battlegirl | TrainerClass::Psychic => 24,
+            TrainerClass::Hiker | TrainerClass::Fisherman | TrainerClass::Sailor => 32,
+            TrainerClass::Breeder | TrainerClass::Scientist | TrainerClass::Rival => 48,
+            TrainerClass::Beauty => 56,
+            TrainerClass::AceTrainer => 60,
+            TrainerClass::Veteran => 80,
+            TrainerClass::GymLeader => 100,
+            TrainerClass::EliteFour => 120,
+            TrainerClass::Gentleman | TrainerClass::RichBoy | TrainerClass::Lady |
+            TrainerClass::Socialite | TrainerClass::Champion => 200
+        }
+    }
+
+    /// Check if this is a Gym Leader, Elite Four member, or Champion.
+    /// Battling them raises the friendship of the player's party.
+    pub fn is_boss(&self) -> bool {
+        matches!(self, TrainerClass::GymLeader | TrainerClass::EliteFour | TrainerClass::Champion)
+    }
+}
+
+/// Represents an NPC trainer, who can be battled
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trainer {
+    /// Uniquely identifies this trainer, so they can be recorded as defeated
+    pub id: String,
+    pub class: TrainerClass,
+    pub name: String,
+    pub team: Vec<PokemonTemplate>,
+    #[serde(default)]
+    pub ai: AiTier,
+    /// Overrides the prize money normally calculated from the trainer's class
+    #[serde(default)]
+    pub prize_money: Option<u32>,
+    /// Items the trainer can use during battle
+    #[serde(default)]
+    pub items: Vec<Item>,
+    /// Fluent key of the dialogue said before battle
+    #[serde(default)]
+    pub before_battle: Option<String>,
+    /// Fluent key of the dialogue said after being defeated
+    #[serde(default)]
+    pub after_battle: Option<String>
+}
+impl Trainer {
+    /// Get the money the player earns by defeating this trainer.
+    /// Unless overridden, this is the class's base payout multiplied by the level of the last Pokemon on the team.
+    pub fn prize_money(&self) -> u32 {
+        self.prize_money.unwrap_or_else(|| {
+            let level = self.team.last().map_or(0, |t| t.level);
+            self.class.base_payout() * u32::from(level)
+        })
+    }
+
+    /// Get the original trainer info for this trainer's Pokemon
+    pub fn original_trainer(&self) -> TemplateTrainer {
+        TemplateTrainer {
+            trainer_id: 0,
+            secret_id: 0,
+            name: self.name.clone()
+        }
+    }
+}
+
+/// Get the money the player loses when all their Pokemon faint.
+/// This is a base amount depending on the number of badges, multiplied by the level of their highest-level Pokemon.
+/// The player can never lose more money than they have.
+pub fn whiteout_penalty(money: u32, badges: u8, highest_level: u8) -> u32 {
+    let base = WHITEOUT_BASE_PAYOUT[usize::from(badges).min(WHITEOUT_BASE_PAYOUT.len() - 1)];
+    (base * u32::from(highest_level)).min(money)
+}
+
+/// The trainers the player has defeated.
+/// Defeated trainers will not battle the player again.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DefeatedTrainers(HashSet<String>);
+impl DefeatedTrainers {
+    pub fn record<S: Into<String>>(&mut self, id: S) {
+        self.0.insert(id.into());
+    }
+
+    pub fn has_defeated(&self, id: &str) -> bool {
+        self.0.contains(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::species::Species;
+
+    use super::*;
+
+    fn trainer(class: TrainerClass, levels: &[u8]) -> Trainer {
+        Trainer {
+            id: String::from("test"),
+            class,
+            name: String::from("Test"),
+            team: levels.iter().map(|l| PokemonTemplate::pokemon(Species::Rattata, *l)).collect(),
+            ai: AiTier::default(),
+            prize_money: None,
+            items: Vec::new(),
+            before_battle: None,
+            after_battle: None
+        }
+    }
+
+    #[test]
+    fn prize_money() {
+        let table = [
+            (TrainerClass::Preschooler, 4), (TrainerClass::Swimmer, 8),
+            (TrainerClass::Youngster, 16), (TrainerClass::Lass, 16), (TrainerClass::BugCatcher, 16),
+            (TrainerClass::Twins, 16), (TrainerClass::Camper, 16), (TrainerClass::Picnicker, 16),
+            (TrainerClass::Schoolkid, 24), (TrainerClass::BlackBelt, 24), (TrainerClass::Battlegirl, 24), (TrainerClass::Psychic, 24),
+            (TrainerClass::Hiker, 32), (TrainerClass::Fisherman, 32), (TrainerClass::Sailor, 32),
+            (TrainerClass::Breeder, 48), (TrainerClass::Scientist, 48), (TrainerClass::Rival, 48),
+            (TrainerClass::Beauty, 56), (TrainerClass::AceTrainer, 60), (TrainerClass::Veteran, 80),
+            (TrainerClass::GymLeader, 100), (TrainerClass::EliteFour, 120),
+            (TrainerClass::Gentleman, 200), (TrainerClass::RichBoy, 200), (TrainerClass::Lady, 200),
+            (TrainerClass::Socialite, 200), (TrainerClass::Champion, 200)
+        ];
+        for (class, base) in IntoIterator::into_iter(table) {
+            assert_eq!(class.base_payout(), base, "{:?}", class);
+            // Only the last Pokemon's level counts
+            assert_eq!(trainer(class, &[50, 10]).prize_money(), base * 10, "{:?}", class);
+        }
+
+        let mut overridden = trainer(TrainerClass::Youngster, &[5]);
+        overridden.prize_money = Some(1000);
+        assert_eq!(overridden.prize_money(), 1000);
+        assert_eq!(trainer(TrainerClass::Youngster, &[]).prize_money(), 0);
+    }
+
+    #[test]
+    fn whiteout() {
+        let table = [8, 16, 24, 36, 48, 64, 80, 100, 120];
+        for (badges, base) in IntoIterator::into_iter(table).enumerate() {
+            assert_eq!(whiteout_penalty(MAX_MONEY, badges as u8, 10), base * 10, "{} badges", badges);
+        }
+        // More badges than the table lists use the last entry
+        assert_eq!(whiteout_penalty(MAX_MONEY, 16, 10), 1200);
+        // The player can't lose money they don't have
+        assert_eq!(whiteout_penalty(500, 8, 100), 500);
+        assert_eq!(whiteout_penalty(0, 0, 5), 0);
+    }
+
+    #[test]
+    fn defeated_trainers() {
+        let mut defeated = DefeatedTrainers::default();
+        assert!(!defeated.has_defeated("route-1-youngster-joey"));
+        defeated.record("route-1-youngster-joey");
+        defeated.record(String::from("route-1-youngster-joey"));
+        assert!(defeated.has_defeated("route-1-youngster-joey"));
+        assert!(!defeated.has_defeated("route-1-lass-janice"));
+
+        let yaml = serde_yaml::to_string(&defeated).unwrap();
+        let loaded: DefeatedTrainers = serde_yaml::from_str(&yaml).unwrap();
+        assert!(loaded.has_defeated("route-1-youngster-joey"));
+        assert!(!loaded.has_defeated("route-1-lass-janice"));
+    }
+}
@@ -1,11 +1,18 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::ops::Mul;
+use std::sync::RwLock;
 
-use serde::Deserialize;
-use strum_macros::{EnumIter, IntoStaticStr};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, IntoEnumIterator};
+use strum_macros::{EnumCount as EnumCountMacro, EnumIter, IntoStaticStr};
 use crate::core::OneOrTwo;
 
 /// Represents the Type of a Pokemon
-#[derive(Debug, Copy, Clone, PartialEq, EnumIter, Deserialize, Eq, Hash, IntoStaticStr)]
+#[derive(Debug, Copy, Clone, PartialEq, EnumIter, EnumCountMacro, Serialize, Deserialize, Eq, Hash, IntoStaticStr)]
 pub enum Type {
     Normal,
     Fighting,
@@ -28,189 +35,141 @@ pub enum Type {
 }
 
 impl Type {
-    /// Determine the effectiveness of this type trying to hit another type
+    /// Determine the effectiveness of this type trying to hit another type, using the active type chart
     pub fn attacking(&self, defending_type: &Type) -> Effectiveness {
+        ACTIVE_CHART.read()
+            .map(|chart| chart.get(*self, *defending_type))
+            .unwrap_or_else(|poisoned| poisoned.into_inner().get(*self, *defending_type))
+    }
+}
+
+/// The chart used unless another is made active. The game ships a copy of this file as pkmn/types.chart.
+const DEFAULT_CHART: &str = include_str!("../data/types.chart");
+
+lazy_static! {
+    /// The chart every effectiveness check uses. This is global, so swapping charts is meant to be
+    /// a one-time step at startup, done before any battle begins.
+    static ref ACTIVE_CHART: RwLock<TypeChart> = RwLock::new(TypeChart::default());
+}
+
+/// One row of a type chart, as written in a chart file
+#[derive(Debug, Clone, Deserialize)]
+pub struct TypeChartEntry {
+    pub attacking: Type,
+    /// The damage multiplier against each defending type
+    pub against: HashMap<Type, f32>
+}
+
+/// A problem found while validating a type chart
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeChartProblem {
+    /// The attacking type has no row
+    MissingRow(Type),
+    /// The attacking type has more than one row
+    DuplicateRow(Type),
+    /// The row for the attacking type does not list the defending type
+    MissingPair(Type, Type),
+    /// The multiplier is not one of 0, 0.5, 1, or 2
+    InvalidMultiplier(Type, Type, f32)
+}
+impl Display for TypeChartProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Type::Normal => match defending_type {
-                Type::Rock => Effectiveness::HALF,
-                Type::Ghost => Effectiveness::Immune,
-                Type::Steel => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Fighting => match defending_type {
-                Type::Normal => Effectiveness::DOUBLE,
-                Type::Flying => Effectiveness::HALF,
-                Type::Poison => Effectiveness::HALF,
-                Type::Rock => Effectiveness::DOUBLE,
-                Type::Bug => Effectiveness::HALF,
-                Type::Ghost => Effectiveness::Immune,
-                Type::Steel => Effectiveness::DOUBLE,
-                Type::Psychic => Effectiveness::HALF,
-                Type::Ice => Effectiveness::DOUBLE,
-                Type::Dark => Effectiveness::DOUBLE,
-                Type::Fairy => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Flying => match defending_type {
-                Type::Fighting => Effectiveness::DOUBLE,
-                Type::Rock => Effectiveness::HALF,
-                Type::Bug => Effectiveness::DOUBLE,
-                Type::Steel => Effectiveness::HALF,
-                Type::Grass => Effectiveness::DOUBLE,
-                Type::Electric => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Poison => match defending_type {
-                Type::Poison => Effectiveness::HALF,
-                Type::Ground => Effectiveness::HALF,
-                Type::Rock => Effectiveness::HALF,
-                Type::Ghost => Effectiveness::HALF,
-                Type::Steel => Effectiveness::Immune,
-                Type::Grass => Effectiveness::DOUBLE,
-                Type::Fairy => Effectiveness::DOUBLE,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Ground => match defending_type {
-                Type::Flying => Effectiveness::Immune,
-                Type::Poison => Effectiveness::DOUBLE,
-                Type::Rock => Effectiveness::DOUBLE,
-                Type::Bug => Effectiveness::HALF,
-                Type::Steel => Effectiveness::DOUBLE,
-                Type::Fire => Effectiveness::DOUBLE,
-                Type::Grass => Effectiveness::HALF,
-                Type::Electric => Effectiveness::DOUBLE,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Rock => match defending_type {
-                Type::Fighting => Effectiveness::HALF,
-                Type::Flying => Effectiveness::DOUBLE,
-                Type::Ground => Effectiveness::HALF,
-                Type::Bug => Effectiveness::DOUBLE,
-                Type::Steel => Effectiveness::HALF,
-                Type::Fire => Effectiveness::DOUBLE,
-                Type::Ice => Effectiveness::DOUBLE,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Bug => match defending_type {
-                Type::Fighting => Effectiveness::HALF,
-                Type::Flying => Effectiveness::HALF,
-                Type::Poison => Effectiveness::HALF,
-                Type::Ghost => Effectiveness::HALF,
-                Type::Steel => Effectiveness::HALF,
-                Type::Fire => Effectiveness::HALF,
-                Type::Grass => Effectiveness::DOUBLE,
-                Type::Psychic => Effectiveness::DOUBLE,
-                Type::Dark => Effectiveness::DOUBLE,
-                Type::Fairy => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Ghost => match defending_type {
-                Type::Normal => Effectiveness::Immune,
-                Type::Ghost => Effectiveness::DOUBLE,
-                Type::Psychic => Effectiveness::DOUBLE,
-                Type::Dark => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Steel => match defending_type {
-                Type::Rock => Effectiveness::DOUBLE,
-                Type::Steel => Effectiveness::HALF,
-                Type::Fire => Effectiveness::HALF,
-                Type::Water => Effectiveness::HALF,
-                Type::Electric => Effectiveness::HALF,
-                Type::Ice => Effectiveness::DOUBLE,
-                Type::Fairy => Effectiveness::DOUBLE,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Fire => match defending_type {
-                Type::Rock => Effectiveness::HALF,
-                Type::Bug => Effectiveness::DOUBLE,
-                Type::Steel => Effectiveness::DOUBLE,
-                Type::Fire => Effectiveness::HALF,
-                Type::Water => Effectiveness::HALF,
-                Type::Grass => Effectiveness::DOUBLE,
-                Type::Ice => Effectiveness::DOUBLE,
-                Type::Dragon => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Water => match defending_type {
-                Type::Ground => Effectiveness::DOUBLE,
-                Type::Rock => Effectiveness::DOUBLE,
-                Type::Fire => Effectiveness::DOUBLE,
-                Type::Water => Effectiveness::HALF,
-                Type::Grass => Effectiveness::HALF,
-                Type::Dragon => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Grass => match defending_type {
-                Type::Flying => Effectiveness::HALF,
-                Type::Poison => Effectiveness::HALF,
-                Type::Ground => Effectiveness::DOUBLE,
-                Type::Rock => Effectiveness::DOUBLE,
-                Type::Bug => Effectiveness::HALF,
-                Type::Steel => Effectiveness::HALF,
-                Type::Fire => Effectiveness::HALF,
-                Type::Water => Effectiveness::DOUBLE,
-                Type::Grass => Effectiveness::HALF,
-                Type::Dragon => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Electric => match defending_type {
-                Type::Flying => Effectiveness::DOUBLE,
-                Type::Ground => Effectiveness::Immune,
-                Type::Water => Effectiveness::DOUBLE,
-                Type::Grass => Effectiveness::HALF,
-                Type::Electric => Effectiveness::HALF,
-                Type::Dragon => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Psychic => match defending_type {
-                Type::Fighting => Effectiveness::DOUBLE,
-                Type::Poison => Effectiveness::DOUBLE,
-                Type::Steel => Effectiveness::HALF,
-                Type::Psychic => Effectiveness::HALF,
-                Type::Dark => Effectiveness::Immune,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Ice => match defending_type {
-                Type::Flying => Effectiveness::DOUBLE,
-                Type::Ground => Effectiveness::DOUBLE,
-                Type::Steel => Effectiveness::HALF,
-                Type::Fire => Effectiveness::HALF,
-                Type::Water => Effectiveness::HALF,
-                Type::Grass => Effectiveness::DOUBLE,
-                Type::Ice => Effectiveness::HALF,
-                Type::Dragon => Effectiveness::DOUBLE,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Dragon => match defending_type {
-                Type::Steel => Effectiveness::HALF,
-                Type::Dragon => Effectiveness::DOUBLE,
-                Type::Fairy => Effectiveness::Immune,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Dark => match defending_type {
-                Type::Fighting => Effectiveness::HALF,
-                Type::Ghost => Effectiveness::DOUBLE,
-                Type::Psychic => Effectiveness::DOUBLE,
-                Type::Dark => Effectiveness::HALF,
-                Type::Fairy => Effectiveness::HALF,
-                _ => Effectiveness::NORMAL
-            },
-            Type::Fairy => match defending_type {
-                Type::Fighting => Effectiveness::DOUBLE,
-                Type::Poison => Effectiveness::HALF,
-                Type::Steel => Effectiveness::HALF,
-                Type::Fire => Effectiveness::HALF,
-                Type::Dragon => Effectiveness::DOUBLE,
-                Type::Dark => Effectiveness::DOUBLE,
-                _ => Effectiveness::NORMAL
+            TypeChartProblem::MissingRow(a) => write!(f, "{:?} has no row", a),
+            TypeChartProblem::DuplicateRow(a) => write!(f, "{:?} has more than one row", a),
+            TypeChartProblem::MissingPair(a, d) => write!(f, "{:?} is missing a multiplier against {:?}", a, d),
+            TypeChartProblem::InvalidMultiplier(a, d, m) => write!(f, "{:?} has an invalid multiplier of {} against {:?}", a, m, d)
+        }
+    }
+}
+
+/// Every problem found while validating a type chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeChartError(pub Vec<TypeChartProblem>);
+impl Display for TypeChartError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let problems = self.0.iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+        write!(f, "Invalid type chart: {}", problems)
+    }
+}
+impl Error for TypeChartError {}
+
+/// The effectiveness of every attacking type against every defending type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeChart([[Effectiveness; Type::COUNT]; Type::COUNT]);
+impl TypeChart {
+    /// Parse and validate a chart file
+    pub fn from_yaml(yaml: &str) -> Result<TypeChart, Box<dyn Error>> {
+        let entries: Vec<TypeChartEntry> = serde_yaml::from_str(yaml)?;
+        Ok(TypeChart::try_from(entries)?)
+    }
+
+    /// Get the effectiveness of an attacking type against a defending type
+    pub fn get(&self, attacking: Type, defending: Type) -> Effectiveness {
+        self.0[attacking as usize][defending as usize]
+    }
+
+    /// Use this chart for all effectiveness checks, such as `Type::attacking`.
+    /// This should be done once, when the game or simulator starts. Any battle already in progress
+    /// would immediately start using the new chart.
+    pub fn make_active(self) {
+        match ACTIVE_CHART.write() {
+            Ok(mut chart) => *chart = self,
+            Err(poisoned) => *poisoned.into_inner() = self
+        }
+    }
+}
+impl Default for TypeChart {
+    fn default() -> Self {
+        TypeChart::from_yaml(DEFAULT_CHART)
+            .expect("Default type chart is invalid")
+    }
+}
+impl TryFrom<Vec<TypeChartEntry>> for TypeChart {
+    type Error = TypeChartError;
+
+    fn try_from(entries: Vec<TypeChartEntry>) -> Result<Self, Self::Error> {
+        let mut chart = [[Effectiveness::NORMAL; Type::COUNT]; Type::COUNT];
+        let mut seen = Vec::new();
+        let mut problems = Vec::new();
+        for entry in entries {
+            if seen.contains(&entry.attacking) {
+                problems.push(TypeChartProblem::DuplicateRow(entry.attacking));
+                continue;
+            }
+            seen.push(entry.attacking);
+            for defending in Type::iter() {
+                let effectiveness = match entry.against.get(&defending) {
+                    None => Err(TypeChartProblem::MissingPair(entry.attacking, defending)),
+                    Some(m) if *m == 0.0 => Ok(Effectiveness::Immune),
+                    Some(m) if *m == 0.5 => Ok(Effectiveness::HALF),
+                    Some(m) if *m == 1.0 => Ok(Effectiveness::NORMAL),
+                    Some(m) if *m == 2.0 => Ok(Effectiveness::DOUBLE),
+                    Some(m) => Err(TypeChartProblem::InvalidMultiplier(entry.attacking, defending, *m))
+                };
+                match effectiveness {
+                    Ok(e) => chart[entry.attacking as usize][defending as usize] = e,
+                    Err(p) => problems.push(p)
+                }
             }
         }
+        problems.extend(Type::iter()
+            .filter(|t| !seen.contains(t))
+            .map(TypeChartProblem::MissingRow));
+
+        if problems.is_empty() {
+            Ok(TypeChart(chart))
+        } else {
+            Err(TypeChartError(problems))
+        }
     }
 }
 
 /// Represents the effectiveness of a Move matchup
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Effectiveness {
     Immune,
     Effect(i8)
@@ -235,6 +194,23 @@ impl Effectiveness {
         }
     }
 
+    /// Get the power of two this effectiveness multiplies damage by.
+    /// Immunity is treated as lower than any other effectiveness.
+    pub fn power(&self) -> i8 {
+        match self {
+            Effectiveness::Immune => i8::MIN,
+            Effectiveness::Effect(p) => *p
+        }
+    }
+
+    /// Get the multiplier this effectiveness applies to damage
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            Effectiveness::Immune => 0.0,
+            Effectiveness::Effect(p) => 2f32.powi(i32::from(*p))
+        }
+    }
+
     pub fn is_super_effective(&self) -> bool {
         if let Effectiveness::Effect(i) = self {
             *i > 0
@@ -288,4 +264,47 @@ impl OneOrTwo<Type> {
     pub fn has_type(&self, attack_type: &Type) -> bool {
         self.is_stab(attack_type)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_chart() {
+        let chart = TypeChart::default();
+        assert_eq!(chart.get(Type::Fire, Type::Grass), Effectiveness::DOUBLE);
+        assert_eq!(chart.get(Type::Water, Type::Fire), Effectiveness::DOUBLE);
+        assert_eq!(chart.get(Type::Fighting, Type::Dark), Effectiveness::DOUBLE);
+        assert_eq!(chart.get(Type::Fairy, Type::Dragon), Effectiveness::DOUBLE);
+        assert_eq!(chart.get(Type::Fire, Type::Water), Effectiveness::HALF);
+        assert_eq!(chart.get(Type::Water, Type::Water), Effectiveness::HALF);
+        assert_eq!(chart.get(Type::Steel, Type::Steel), Effectiveness::HALF);
+        assert_eq!(chart.get(Type::Normal, Type::Normal), Effectiveness::NORMAL);
+        assert_eq!(chart.get(Type::Ghost, Type::Normal), Effectiveness::Immune);
+        assert_eq!(chart.get(Type::Normal, Type::Ghost), Effectiveness::Immune);
+        assert_eq!(chart.get(Type::Electric, Type::Ground), Effectiveness::Immune);
+        assert_eq!(chart.get(Type::Dragon, Type::Fairy), Effectiveness::Immune);
+    }
+
+    #[test]
+    fn shipped_chart_is_in_sync() {
+        let shipped = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../glazed-dx/assets/pkmn/types.chart")).unwrap();
+        assert_eq!(shipped, DEFAULT_CHART, "pkmn/types.chart should be a copy of data/types.chart");
+    }
+
+    #[test]
+    fn chart_validation() {
+        let mut entries: Vec<TypeChartEntry> = serde_yaml::from_str(DEFAULT_CHART).unwrap();
+        entries[0].against.remove(&Type::Fairy);
+        entries[1].against.insert(Type::Normal, 3.0);
+        entries.pop();
+
+        let error = TypeChart::try_from(entries).unwrap_err();
+        assert_eq!(error.0, vec![
+            TypeChartProblem::MissingPair(Type::Normal, Type::Fairy),
+            TypeChartProblem::InvalidMultiplier(Type::Fighting, Type::Normal, 3.0),
+            TypeChartProblem::MissingRow(Type::Fairy)
+        ]);
+    }
+}
@@ -0,0 +1,43 @@
+pokedex-height = { $height } m
+pokedex-weight = { $weight } kg
+
+bulbasaur-dex-category = Seed Pokémon
+bulbasaur-dex-entry = It carries a seed on its back from birth. The seed soaks up sunlight and slowly grows along with it.
+ivysaur-dex-category = Seed Pokémon
+ivysaur-dex-entry = The bud on its back has grown heavy, so its legs have grown strong to carry it. It basks in the sun to help the bud bloom.
+venusaur-dex-category = Seed Pokémon
+venusaur-dex-entry = Its great flower releases a soothing scent after a rain. It gathers sunlight into its petals and turns it into energy.
+charmander-dex-category = Lizard Pokémon
+charmander-dex-entry = The flame on its tail shows how it is feeling. It flickers weakly when the Pokémon is tired, and blazes when it is happy.
+charmeleon-dex-category = Flame Pokémon
+charmeleon-dex-entry = It swipes at foes with its sharp claws. When it gets excited, the flame on its tail turns a bluish white.
+charizard-dex-category = Flame Pokémon
+charizard-dex-entry = It flies high into the sky in search of strong opponents. Its fiery breath can melt almost anything.
+squirtle-dex-category = Tiny Turtle Pokémon
+squirtle-dex-entry = It withdraws into its shell when threatened, then sprays water back at its attacker from the openings.
+wartortle-dex-category = Turtle Pokémon
+wartortle-dex-entry = Its furry tail is thought to be a sign of long life. It uses its tail and ears to keep its balance while swimming.
+blastoise-dex-category = Shellfish Pokémon
+blastoise-dex-entry = The cannons on its shell fire jets of water strong enough to punch through thick steel.
+sentret-dex-category = Scout Pokémon
+sentret-dex-entry = It stands on its tail to see farther. Sentret take turns keeping watch, and cry out loudly when danger is near.
+furret-dex-category = Long Body Pokémon
+furret-dex-entry = Its long, slender body lets it slip into narrow burrows. It raises its young deep inside these tunnels.
+pidgey-dex-category = Tiny Bird Pokémon
+pidgey-dex-entry = A gentle Pokémon that would rather kick up sand to hide than fight. It is common in grassy fields.
+pidgeotto-dex-category = Bird Pokémon
+pidgeotto-dex-entry = It patrols a wide territory from the air. Anything that intrudes is chased off with its sharp talons.
+pidgeot-dex-category = Bird Pokémon
+pidgeot-dex-entry = It skims the surface of the water at great speed to snatch up unsuspecting fish.
+rattata-dex-category = Mouse Pokémon
+rattata-dex-entry = Its teeth never stop growing, so it gnaws on anything hard to keep them short. It lives wherever food can be found.
+raticate-dex-category = Mouse Pokémon
+raticate-dex-entry = Its whiskers help it keep its balance. It swims across rivers to reach new places to forage.
+buizel-dex-category = Sea Weasel Pokémon
+buizel-dex-entry = It spins its two tails like a propeller to swim. The flotation sac around its neck keeps its head above water.
+floatzel-dex-category = Sea Weasel Pokémon
+floatzel-dex-entry = It inflates its flotation sac to rescue people who are drowning, and carries them to shore on its back.
+pikachu-dex-category = Mouse Pokémon
+pikachu-dex-entry = It stores electricity in the pouches on its cheeks. Groups of Pikachu can cause lightning storms.
+raichu-dex-category = Mouse Pokémon
+raichu-dex-entry = Its long tail acts as a ground, so it can release huge amounts of electricity without harming itself.
@@ -0,0 +1,58 @@
+league-ribbon-name = League Ribbon
+league-ribbon-description = Awarded for beating the Pokémon League Champion and entering the Hall of Fame.
+cool-ribbon-name = Cool Ribbon
+cool-ribbon-description = Cool Contest Normal Rank winner!
+cool-super-ribbon-name = Cool Ribbon Super
+cool-super-ribbon-description = Cool Contest Super Rank winner!
+cool-hyper-ribbon-name = Cool Ribbon Hyper
+cool-hyper-ribbon-description = Cool Contest Hyper Rank winner!
+cool-master-ribbon-name = Cool Ribbon Master
+cool-master-ribbon-description = Cool Contest Master Rank winner!
+beauty-ribbon-name = Beauty Ribbon
+beauty-ribbon-description = Beauty Contest Normal Rank winner!
+beauty-super-ribbon-name = Beauty Ribbon Super
+beauty-super-ribbon-description = Beauty Contest Super Rank winner!
+beauty-hyper-ribbon-name = Beauty Ribbon Hyper
+beauty-hyper-ribbon-description = Beauty Contest Hyper Rank winner!
+beauty-master-ribbon-name = Beauty Ribbon Master
+beauty-master-ribbon-description = Beauty Contest Master Rank winner!
+cute-ribbon-name = Cute Ribbon
+cute-ribbon-description = Cute Contest Normal Rank winner!
+cute-super-ribbon-name = Cute Ribbon Super
+cute-super-ribbon-description = Cute Contest Super Rank winner!
+cute-hyper-ribbon-name = Cute Ribbon Hyper
+cute-hyper-ribbon-description = Cute Contest Hyper Rank winner!
+cute-master-ribbon-name = Cute Ribbon Master
+cute-master-ribbon-description = Cute Contest Master Rank winner!
+smart-ribbon-name = Smart Ribbon
+smart-ribbon-description = Smart Contest Normal Rank winner!
+smart-super-ribbon-name = Smart Ribbon Super
+smart-super-ribbon-description = Smart Contest Super Rank winner!
+smart-hyper-ribbon-name = Smart Ribbon Hyper
+smart-hyper-ribbon-description = Smart Contest Hyper Rank winner!
+smart-master-ribbon-name = Smart Ribbon Master
+smart-master-ribbon-description = Smart Contest Master Rank winner!
+tough-ribbon-name = Tough Ribbon
+tough-ribbon-description = Tough Contest Normal Rank winner!
+tough-super-ribbon-name = Tough Ribbon Super
+tough-super-ribbon-description = Tough Contest Super Rank winner!
+tough-hyper-ribbon-name = Tough Ribbon Hyper
+tough-hyper-ribbon-description = Tough Contest Hyper Rank winner!
+tough-master-ribbon-name = Tough Ribbon Master
+tough-master-ribbon-description = Tough Contest Master Rank winner!
+winning-ribbon-name = Winning Ribbon
+winning-ribbon-description = Ribbon for clearing the Battle Tower's Lv. 50 challenge.
+victory-ribbon-name = Victory Ribbon
+victory-ribbon-description = Ribbon for clearing the Battle Tower's Lv. 100 challenge.
+artist-ribbon-name = Artist Ribbon
+artist-ribbon-description = Ribbon for being chosen as a super sketch model.
+effort-ribbon-name = Effort Ribbon
+effort-ribbon-description = Ribbon awarded for being an exceptionally hard worker.
+gorgeous-ribbon-name = Gorgeous Ribbon
+gorgeous-ribbon-description = An extraordinarily gorgeous and extravagant Ribbon.
+royal-ribbon-name = Royal Ribbon
+royal-ribbon-description = An incredibly regal Ribbon with an air of nobility.
+gorgeous-royal-ribbon-name = Gorgeous Royal Ribbon
+gorgeous-royal-ribbon-description = A gorgeous and regal Ribbon that is the peak of fabulous.
+footprint-ribbon-name = Footprint Ribbon
+footprint-ribbon-description = A Ribbon awarded to a Pokémon deemed a beneficial partner.
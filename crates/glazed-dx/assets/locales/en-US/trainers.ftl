@@ -0,0 +1,38 @@
+trainer-battle-start = You are challenged by { $trainer-class } { $trainer-name }!
+trainer-battle-won = You defeated { $trainer-class } { $trainer-name }!
+trainer-battle-prize = You got ${ $prize-money } for winning!
+trainer-battle-whiteout = You have no more Pokémon that can fight! You panicked and dropped ${ $whiteout-money }...
+
+youngster-trainer-class = Youngster
+lass-trainer-class = Lass
+bugcatcher-trainer-class = Bug Catcher
+schoolkid-trainer-class = Schoolkid
+preschooler-trainer-class = Preschooler
+twins-trainer-class = Twins
+camper-trainer-class = Camper
+picnicker-trainer-class = Picnicker
+hiker-trainer-class = Hiker
+fisherman-trainer-class = Fisherman
+sailor-trainer-class = Sailor
+swimmer-trainer-class = Swimmer
+breeder-trainer-class = Pokémon Breeder
+blackbelt-trainer-class = Black Belt
+battlegirl-trainer-class = Battle Girl
+psychic-trainer-class = Psychic
+gentleman-trainer-class = Gentleman
+richboy-trainer-class = Rich Boy
+lady-trainer-class = Lady
+socialite-trainer-class = Socialite
+beauty-trainer-class = Beauty
+scientist-trainer-class = Scientist
+acetrainer-trainer-class = Ace Trainer
+veteran-trainer-class = Veteran
+rival-trainer-class = Rival
+gymleader-trainer-class = Gym Leader
+elitefour-trainer-class = Elite Four
+champion-trainer-class = Champion
+
+route-1-youngster-joey-before = Hey! You're a trainer, right? My Rattata is in the top percentage of Rattata!
+route-1-youngster-joey-after = Aww, my Rattata isn't as strong as I thought...
+route-1-lass-janice-before = Our eyes met, so that means we have to battle!
+route-1-lass-janice-after = I'll raise my Pokémon to be even cuter next time!
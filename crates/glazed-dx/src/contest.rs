@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::reflect::TypeUuid;
+
+use glazed_data::contest::BerryPokeblockData;
+use glazed_data::contest::blender::BerryPokeblockLookup;
+use glazed_data::contest::engine::{ContestMove, ContestMoveLookup};
+
+use crate::util::YamlLoader;
+
+pub struct ContestPlugin;
+impl Plugin for ContestPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset_loader(YamlLoader::<ContestMove, ContestMoveAsset>::new("appeals"))
+            .add_asset::<ContestMoveAsset>()
+            .add_asset_loader(YamlLoader::<BerryPokeblockData, BerryFlavorAsset>::new("flavors"))
+            .add_asset::<BerryFlavorAsset>()
+        ;
+    }
+}
+
+/// The contest data of every move
+#[derive(TypeUuid, Deref, DerefMut)]
+#[uuid = "3d7a9e15-c2b4-4f08-a6e1-5b9c0d48f273"]
+pub struct ContestMoveAsset(ContestMoveLookup);
+impl From<Vec<ContestMove>> for ContestMoveAsset {
+    fn from(vec: Vec<ContestMove>) -> Self {
+        let d = vec.into_iter()
+            .map(|m| (m.id, m))
+            .collect::<ContestMoveLookup>();
+        ContestMoveAsset(d)
+    }
+}
+
+/// The flavors of every berry that can go in the Berry Blender
+#[derive(TypeUuid, Deref, DerefMut)]
+#[uuid = "a41f6c08-97d3-4e2b-8b5a-e2c7f1093d6e"]
+pub struct BerryFlavorAsset(BerryPokeblockLookup);
+impl From<Vec<BerryPokeblockData>> for BerryFlavorAsset {
+    fn from(vec: Vec<BerryPokeblockData>) -> Self {
+        let d = vec.into_iter()
+            .map(|b| (b.id, b))
+            .collect::<BerryPokeblockLookup>();
+        BerryFlavorAsset(d)
+    }
+}
+
+/// Hold all Contest files
+pub struct ContestDataFiles {
+    pub moves: Handle<ContestMoveAsset>,
+    pub berries: Handle<BerryFlavorAsset>
+}
+
+#[derive(SystemParam)]
+pub struct ContestLookupService<'w, 's> {
+    handles: Res<'w, ContestDataFiles>,
+    move_assets: Res<'w, Assets<ContestMoveAsset>>,
+    berry_assets: Res<'w, Assets<BerryFlavorAsset>>,
+    #[allow(dead_code)]
+    marker: Commands<'w, 's>,
+}
+impl<'w, 's> ContestLookupService<'w, 's> {
+    /// Get the contest data of every move, once it is loaded
+    pub fn moves(&self) -> Option<&ContestMoveLookup> {
+        self.move_assets.get(&self.handles.moves).map(|l| &l.0)
+    }
+
+    /// Get the flavors of every berry, once they are loaded
+    pub fn berries(&self) -> Option<&BerryPokeblockLookup> {
+        self.berry_assets.get(&self.handles.berries).map(|l| &l.0)
+    }
+}
@@ -8,8 +8,14 @@ mod scenes;
 mod actions;
 mod locale;
 mod player;
+mod trainer;
+mod pokedex;
+mod ribbons;
+mod contest;
 
 use bevy::prelude::*;
+use glazed_data::bag::Bag;
+use glazed_data::contest::case::PokeblockCase;
 use glazed_data::pokemon::PokemonTemplate;
 use glazed_data::species::Species;
 use iyes_loopless::prelude::*;
@@ -22,12 +28,15 @@ use locale::FluentData;
 use player::{Player, PlayerService, Boxes};
 use util::Rng;
 use crate::anim::GlazedAnimator;
+use crate::contest::{ContestDataFiles, ContestPlugin};
 use crate::pkmn::{CryService, PkmnPlugin, PokemonDataFiles};
+use crate::pokedex::{PokedexDataFiles, PokedexPlugin};
 use crate::controls::Actions;
 use crate::scenes::intro::Title;
 use crate::scenes::lecture::Lecture;
 use crate::text::TextPlugin;
-use crate::state::GlobalOptions;
+use crate::trainer::{TrainerDataFiles, TrainerPlugin};
+use crate::state::{GlobalOptions, LoadGame, SaveGame};
 use crate::util::TransitionPlugin;
 
 pub const SCREEN_WIDTH: f32 = 400.0;
@@ -49,6 +58,8 @@ fn main() {
         .insert_resource(ClearColor(Color::BLACK))
         .init_resource::<Player>()
         .init_resource::<Boxes>()
+        .init_resource::<Bag>()
+        .init_resource::<PokeblockCase>()
         .add_plugins(DefaultPlugins)
         .add_plugin(GlazedAnimator)
         .add_plugin(TweeningPlugin)
@@ -57,9 +68,18 @@ fn main() {
         .add_startup_system(setup)
         .add_startup_system(GlobalOptions::load)
         .add_system(exit_on_esc_system)
+        .add_event::<SaveGame>()
+        .add_event::<LoadGame>()
+        .add_system(state::save_game)
+        .add_system(state::load_game)
+        .add_system(player::update_pokerus)
+        .add_system(player::update_berries)
 
         // Random Plugins
         .add_plugin(PkmnPlugin)
+        .add_plugin(TrainerPlugin)
+        .add_plugin(PokedexPlugin)
+        .add_plugin(ContestPlugin)
         .add_plugin(TransitionPlugin)
         .add_plugin(TextPlugin)
         .add_plugin(actions::ActionsPlugin)
@@ -130,8 +150,41 @@ fn init_load(mut commands: Commands, ass: Res<AssetServer>, locales: Res<FluentD
     let species_data = ass.load("pkmn/data.pkmn");
     loading.add(species_data.clone());
 
+    let type_chart = ass.load("pkmn/types.chart");
+    loading.add(type_chart.clone());
+
+    let moves = ass.load("pkmn/moves.attacks");
+    loading.add(moves.clone());
+
     commands.insert_resource(PokemonDataFiles {
-        species_data
+        species_data,
+        type_chart,
+        moves
+    });
+
+    let trainers = ass.load("trainers/trainers.trainers");
+    loading.add(trainers.clone());
+
+    commands.insert_resource(TrainerDataFiles {
+        trainers
+    });
+
+    let regional = ass.load("pkmn/regional.dex");
+    loading.add(regional.clone());
+
+    commands.insert_resource(PokedexDataFiles {
+        regional
+    });
+
+    let contest_moves = ass.load("pkmn/contest.appeals");
+    loading.add(contest_moves.clone());
+
+    let berries = ass.load("pkmn/berries.flavors");
+    loading.add(berries.clone());
+
+    commands.insert_resource(ContestDataFiles {
+        moves: contest_moves,
+        berries
     });
 }
 
@@ -1,7 +1,16 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy::ecs::system::SystemParam;
+use glazed_data::farming::BerryFarm;
+use glazed_data::locations::Location;
+use glazed_data::pokedex::Pokedex;
 use glazed_data::pokemon::*;
+use glazed_data::pokerus::PokerusClock;
+use glazed_data::ribbons;
+use glazed_data::trainer::DefeatedTrainers;
 use rand::Rng as o;
+use serde::{Deserialize, Serialize};
 
 use crate::{locale::Fluent, pkmn::PokemonLookupService, util::Rng};
 
@@ -14,7 +23,7 @@ pub const MAX_POKEMON_IN_BOX: usize = 30;
 pub const STARTING_BOX_COUNT: usize = 8;
 
 /// Represents the player
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     /// The player's name
     pub name: String,
@@ -23,15 +32,82 @@ pub struct Player {
     /// The player's secret ID
     pub secret_id: u16,
     /// The player's current party
-    pub party: Party
+    pub party: Party,
+    /// The player's money
+    pub money: u32,
+    /// The number of gym badges the player has earned
+    pub badges: u8,
+    /// The trainers the player has defeated
+    pub defeated_trainers: DefeatedTrainers,
+    /// The species the player has seen and caught
+    pub pokedex: Pokedex,
+    /// Counts down Pokerus in the party as days pass
+    pub pokerus: PokerusClock,
+    /// The plots of soil where the player has planted berries
+    pub berries: BerryFarm,
+    /// Where the player is
+    pub location: Location,
+    /// The Pokemon Center the player last healed at, which they return to after whiting out
+    pub last_pokemon_center: Location
+}
+impl Player {
+    /// Send the player back to the last Pokemon Center they healed at, and heal their party there
+    pub fn return_to_pokemon_center(&mut self) {
+        self.location = self.last_pokemon_center;
+        self.party.iter_mut().for_each(|p| p.heal());
+    }
+}
+
+/// How often systems that depend on the real-world clock check it.
+/// Nothing they track changes more than once an hour, so checking every frame would be wasted work.
+#[derive(Deref, DerefMut)]
+pub struct ClockTimer(Timer);
+impl Default for ClockTimer {
+    fn default() -> Self {
+        Self(Timer::new(Duration::from_secs(60), true))
+    }
+}
+
+/// Count down Pokerus in the party once a new day begins
+pub fn update_pokerus(mut player: ResMut<Player>, time: Res<Time>, mut timer: Local<ClockTimer>) {
+    timer.tick(time.delta());
+    if !timer.just_finished() {
+        return;
+    }
+    let Player { party, pokerus, .. } = &mut *player;
+    pokerus.update_today(party.as_mut_slice());
+}
+
+/// Grow the player's berries as time passes
+pub fn update_berries(mut player: ResMut<Player>, time: Res<Time>, mut timer: Local<ClockTimer>) {
+    timer.tick(time.delta());
+    if !timer.just_finished() {
+        return;
+    }
+    player.berries.update_now();
 }
 
 /// Represents a party of Pokemon
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Party {
     slots: Vec<Pokemon>
 }
 impl Party {
+    /// Iterate through the Pokemon in the party, in order
+    pub fn iter(&self) -> impl Iterator<Item = &Pokemon> {
+        self.slots.iter()
+    }
+
+    /// Iterate mutably through the Pokemon in the party, in order
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Pokemon> {
+        self.slots.iter_mut()
+    }
+
+    /// Get the Pokemon in the party mutably, in order
+    pub fn as_mut_slice(&mut self) -> &mut [Pokemon] {
+        &mut self.slots
+    }
+
     /// Add a Pokemon to the party. 
     /// If the party is full, this acts as a no-op.
     fn add_pokemon<T: Into<Pokemon>>(&mut self, pkmn: T) {
@@ -44,7 +120,7 @@ impl Party {
 /// Represents boxes of Pokemon the player does not currently use.
 /// This structure maintains a cursor, which represents the active box.
 /// The active box will be where caught Pokemon are placed.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Boxes {
     cursor: usize,
     boxes: Vec<Box>
@@ -82,7 +158,7 @@ impl Boxes {
 }
 
 /// Represents a single box of Pokemon.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Box {
     name: String,
     slots: [Option<Pokemon>; MAX_POKEMON_IN_BOX]
@@ -146,8 +222,13 @@ impl<'w, 's> PlayerService<'w, 's> {
     /// 2. If the party is full, add the Pokemon to the active box
     /// 3. If the active box is full, advance to the next non-full box
     /// 4. If no non-full boxes are found, create a new set of boxes
-    /// Note that we need to also do Pokedex information here I think. 
+    /// The Pokemon is also registered as caught in the Pokedex.
     pub fn add_pokemon<T: Into<Pokemon>>(&mut self, pkmn: T) -> AddPokemonResult {
+        let pkmn = pkmn.into();
+        if !pkmn.egg {
+            self.player.pokedex.catch(pkmn.species, pkmn.gender);
+        }
+
         if MAX_POKEMON_IN_PARTY < self.player.party.slots.len() {
             self.player.party.add_pokemon(pkmn);
             AddPokemonResult::InParty
@@ -181,6 +262,12 @@ impl<'w, 's> PlayerService<'w, 's> {
         }
     }
 
+    /// Enter the party into the Hall of Fame, giving each Pokemon the League Ribbon.
+    /// Returns the index of each Pokemon newly given the ribbon.
+    pub fn enter_hall_of_fame(&mut self) -> Vec<usize> {
+        ribbons::award_league(self.player.party.as_mut_slice())
+    }
+
     /// Create STARTING_BOX_COUNT boxes, and move the cursor to the first one
     fn create_more_boxes(&mut self) {
         self.boxes.cursor = self.boxes.boxes.len();
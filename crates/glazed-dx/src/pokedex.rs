@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::reflect::TypeUuid;
+
+use glazed_data::pokedex::{self, PokedexSearch, RegionalDex};
+use glazed_data::species::{Species, SpeciesDiscriminants};
+
+use crate::locale::Fluent;
+use crate::pkmn::PokemonLookupService;
+use crate::player::Player;
+use crate::util::YamlLoader;
+
+pub struct PokedexPlugin;
+impl Plugin for PokedexPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset_loader(YamlLoader::<Species, RegionalDexLookup>::new("dex"))
+            .add_asset::<RegionalDexLookup>()
+        ;
+    }
+}
+
+#[derive(TypeUuid, Deref, DerefMut)]
+#[uuid = "9c3e5d21-6f0a-4b7e-8d12-4a7b3c9e0f56"]
+pub struct RegionalDexLookup(RegionalDex);
+impl From<Vec<Species>> for RegionalDexLookup {
+    fn from(vec: Vec<Species>) -> Self {
+        RegionalDexLookup(RegionalDex::from(vec))
+    }
+}
+
+/// Hold all Pokedex files
+pub struct PokedexDataFiles {
+    pub regional: Handle<RegionalDexLookup>
+}
+
+/// A Pokedex entry, translated into the player's language.
+/// The details are only known once the species has been caught.
+#[derive(Debug, Clone)]
+pub struct PokedexEntry {
+    pub species: Species,
+    pub national_number: u16,
+    /// The species' number in the regional Pokedex, if it is part of it
+    pub regional_number: Option<u16>,
+    pub name: String,
+    pub caught: bool,
+    /// The species' category, such as "Seed Pokemon"
+    pub category: Option<String>,
+    /// The species' height, formatted in meters
+    pub height: Option<String>,
+    /// The species' weight, formatted in kilograms
+    pub weight: Option<String>,
+    pub flavor_text: Option<String>
+}
+
+#[derive(SystemParam)]
+pub struct PokedexService<'w, 's> {
+    handles: Res<'w, PokedexDataFiles>,
+    assets: Res<'w, Assets<RegionalDexLookup>>,
+    player: Res<'w, Player>,
+    pkmn_lookup: PokemonLookupService<'w, 's>,
+    fluent: Fluent<'w, 's>
+}
+impl<'w, 's> PokedexService<'w, 's> {
+    /// Get the regional Pokedex, if it has loaded
+    pub fn regional_dex(&self) -> Option<&RegionalDex> {
+        self.assets.get(&self.handles.regional).map(|l| &l.0)
+    }
+
+    /// Build the entry for a species, as the player currently knows it.
+    /// Returns None if the player has never seen the species.
+    pub fn entry(&self, species: Species) -> Option<PokedexEntry> {
+        let dex = &self.player.pokedex;
+        if !dex.has_seen(species) {
+            return None;
+        }
+        let caught = dex.has_caught(species);
+        let key = {
+            let raw: SpeciesDiscriminants = species.into();
+            let raw: &str = raw.into();
+            raw.to_ascii_lowercase()
+        };
+        let data = self.pkmn_lookup.lookup(species).filter(|_| caught);
+
+        Some(PokedexEntry {
+            species,
+            national_number: pokedex::national_number(species),
+            regional_number: self.regional_dex().and_then(|d| d.number(species)),
+            name: self.fluent.get_pokemon_species_name(species),
+            caught,
+            category: caught.then(|| self.fluent.translate(&format!("{}-dex-category", key))).flatten(),
+            height: data.and_then(|d| self.fluent.translate_with_arg("pokedex-height", "height", f64::from(d.height) / 10.0)),
+            weight: data.and_then(|d| self.fluent.translate_with_arg("pokedex-weight", "weight", f64::from(d.weight) / 10.0)),
+            flavor_text: caught.then(|| self.fluent.translate(&format!("{}-dex-entry", key))).flatten()
+        })
+    }
+
+    /// Search the regional Pokedex for seen species which match the filters, in regional order
+    pub fn search(&self, search: &PokedexSearch) -> Vec<Species> {
+        match self.regional_dex() {
+            Some(regional) => self.player.pokedex.search(regional, search, |s| self.pkmn_lookup.lookup(s)),
+            None => Vec::new()
+        }
+    }
+}
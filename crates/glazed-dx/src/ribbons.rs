@@ -0,0 +1,33 @@
+use glazed_data::pokemon::{Pokemon, Ribbons};
+
+use crate::locale::Fluent;
+
+/// A ribbon in a Pokemon's ribbon case, translated into the player's language
+#[derive(Debug, Clone)]
+pub struct RibbonEntry {
+    pub ribbon: Ribbons,
+    pub name: String,
+    pub description: String
+}
+
+impl<'w, 's> Fluent<'w, 's> {
+    /// Get the translated name and description of a single ribbon
+    pub fn get_ribbon(&self, ribbon: Ribbons) -> Option<RibbonEntry> {
+        let key = ribbon.key()?;
+        let name_key = format!("{}-name", key);
+        let description_key = format!("{}-description", key);
+        Some(RibbonEntry {
+            ribbon,
+            name: self.translate(&name_key).expect(&name_key),
+            description: self.translate(&description_key).expect(&description_key)
+        })
+    }
+
+    /// List every ribbon a Pokemon holds, in ribbon case order
+    pub fn get_ribbon_case(&self, pokemon: &Pokemon) -> Vec<RibbonEntry> {
+        pokemon.contest.ribbons.list()
+            .into_iter()
+            .filter_map(|r| self.get_ribbon(r))
+            .collect()
+    }
+}
@@ -1,13 +1,18 @@
+use std::error::Error;
 use std::fs::File;
+use std::path::PathBuf;
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use bevy::reflect::TypeUuid;
+use glazed_data::bag::Bag;
+use glazed_data::contest::case::PokeblockCase;
 use glazed_data::species::Species;
 use unic_langid::LanguageIdentifier;
 use crate::controls::Actions;
 use crate::PlayerData;
 use crate::locale::Locale;
+use crate::player::{Boxes, Player};
 use crate::util::RootRng;
 
 #[derive(Deserialize, Default, TypeUuid, Debug)]
@@ -153,6 +158,24 @@ pub enum SaveGameState {
 
 pub struct Save;
 impl Save {
+    const DIRECTORY: &'static str = "./saves";
+
+    fn path(name: &str) -> PathBuf {
+        PathBuf::from(Self::DIRECTORY).join(format!("{}.yml", name))
+    }
+
+    /// Write a save file, replacing any save with the same name
+    pub fn write(name: &str, save: &SaveFile) -> Result<(), Box<dyn Error>> {
+        std::fs::create_dir_all(Self::DIRECTORY)?;
+        serde_yaml::to_writer(File::create(Self::path(name))?, save)?;
+        Ok(())
+    }
+
+    /// Read a save file
+    pub fn read(name: &str) -> Result<SaveFile, Box<dyn Error>> {
+        Ok(serde_yaml::from_reader(File::open(Self::path(name))?)?)
+    }
+
     pub fn check_for_saves() -> Result<bool, ()> {
         use std::fs;
         use std::path::Path;
@@ -175,4 +198,48 @@ impl Save {
             }
         }
     }
-}
\ No newline at end of file
+}
+/// Everything about the player's progress which is kept between sessions
+#[derive(Serialize, Deserialize)]
+pub struct SaveFile {
+    pub player: Player,
+    pub boxes: Boxes,
+    pub bag: Bag,
+    pub pokeblocks: PokeblockCase
+}
+
+/// Event to write the player's progress to the save file with this name
+pub struct SaveGame(pub String);
+
+/// Event to replace the player's progress with the save file with this name
+pub struct LoadGame(pub String);
+
+pub fn save_game(mut events: EventReader<SaveGame>, player: Res<Player>, boxes: Res<Boxes>, bag: Res<Bag>, pokeblocks: Res<PokeblockCase>) {
+    for SaveGame(name) in events.iter() {
+        let save = SaveFile {
+            player: player.clone(),
+            boxes: boxes.clone(),
+            bag: bag.clone(),
+            pokeblocks: pokeblocks.clone()
+        };
+        match Save::write(name, &save) {
+            Ok(()) => info!("Saved game {}", name),
+            Err(e) => error!("Error saving game {}: {}", name, e)
+        }
+    }
+}
+
+pub fn load_game(mut commands: Commands, mut events: EventReader<LoadGame>) {
+    for LoadGame(name) in events.iter() {
+        match Save::read(name) {
+            Ok(SaveFile { player, boxes, bag, pokeblocks }) => {
+                info!("Loaded game {}", name);
+                commands.insert_resource(player);
+                commands.insert_resource(boxes);
+                commands.insert_resource(bag);
+                commands.insert_resource(pokeblocks);
+            },
+            Err(e) => error!("Error loading game {}: {}", name, e)
+        }
+    }
+}
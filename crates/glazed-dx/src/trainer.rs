@@ -0,0 +1,326 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashMap;
+use bevy_sequential_actions::*;
+use rand::Rng as o;
+use rand::SeedableRng;
+
+use glazed_data::bag::Bag;
+use glazed_data::battle::ai::AiTier;
+use glazed_data::battle::engine::BattleEvent;
+use glazed_data::battle::replay::BattleRng;
+use glazed_data::battle::{Action, Battle, BattleOutcome, Battler, Side, SideId};
+use glazed_data::forms::FormTrigger;
+use glazed_data::friendship::{self, FriendshipEvent};
+use glazed_data::item::Item;
+use glazed_data::item_use::{self, ItemUseContext};
+use glazed_data::pokerus;
+use glazed_data::trainer::{self, Trainer, TrainerClass, MAX_MONEY};
+
+use crate::actions::text::ShowTextAction;
+use crate::locale::Fluent;
+use crate::pkmn::PokemonLookupService;
+use crate::player::Player;
+use crate::text::TextBoxOptions;
+use crate::util::{Rng, YamlLoader};
+
+const BATTLE_START: &str = "trainer-battle-start";
+const BATTLE_WON: &str = "trainer-battle-won";
+const BATTLE_PRIZE: &str = "trainer-battle-prize";
+const BATTLE_WHITEOUT: &str = "trainer-battle-whiteout";
+
+pub struct TrainerPlugin;
+impl Plugin for TrainerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset_loader(YamlLoader::<Trainer, TrainerLookup>::new("trainers"))
+            .add_asset::<TrainerLookup>()
+            .add_event::<StartTrainerBattle>()
+            .add_event::<TrainerBattleEnded>()
+            .add_event::<ChooseBattleAction>()
+            .add_system(start_trainer_battle)
+            .add_system(play_trainer_turn)
+            .add_system(end_trainer_battle)
+        ;
+    }
+}
+
+#[derive(TypeUuid, Deref, DerefMut)]
+#[uuid = "d8813986-18e9-4108-9969-675c703bb8d4"]
+pub struct TrainerLookup(HashMap<String, Trainer>);
+impl From<Vec<Trainer>> for TrainerLookup {
+    fn from(vec: Vec<Trainer>) -> Self {
+        let d = vec.into_iter()
+            .map(|t| (t.id.clone(), t))
+            .collect::<HashMap<String, Trainer>>();
+        TrainerLookup(d)
+    }
+}
+
+/// Hold all Trainer files
+pub struct TrainerDataFiles {
+    pub trainers: Handle<TrainerLookup>
+}
+
+#[derive(SystemParam)]
+pub struct TrainerLookupService<'w, 's> {
+    handles: Res<'w, TrainerDataFiles>,
+    assets: Res<'w, Assets<TrainerLookup>>,
+    #[allow(dead_code)]
+    marker: Commands<'w, 's>,
+}
+impl<'w, 's> TrainerLookupService<'w, 's> {
+    pub fn lookup(&self, id: &str) -> Option<&Trainer> {
+        let handle = &self.handles.trainers;
+        let asset = self.assets.get(handle);
+
+        asset.and_then(|l| l.0.get(id))
+    }
+}
+
+/// Event to challenge the player to a battle with a trainer, by their ID.
+/// Trainers the player has already defeated are ignored.
+pub struct StartTrainerBattle(pub String);
+
+/// Event fired once the current trainer battle is over, to pay out or white out
+pub struct TrainerBattleEnded;
+
+/// Event carrying what the player chose to do in the current trainer battle, from the battle menu.
+/// If the player's Pokemon fainted, this must be a switch to the party member sent out in its place.
+pub struct ChooseBattleAction(pub Action);
+
+/// The trainer battle currently in progress
+pub struct TrainerBattle {
+    pub trainer: String,
+    pub battle: Battle,
+    pub ai: AiTier,
+    /// Items the trainer has left to use
+    pub items: Vec<Item>,
+    pub rng: BattleRng
+}
+
+impl<'w, 's> Fluent<'w, 's> {
+    /// Get the translated name of a trainer class
+    pub fn get_trainer_class_name(&self, class: TrainerClass) -> String {
+        let class: &str = class.into();
+        let class_key = format!("{}-trainer-class", class.to_ascii_lowercase());
+
+        self.translate(class_key.as_str())
+            .expect(class_key.as_str())
+    }
+}
+
+/// Set up the battle against a challenging trainer, and display their pre-battle dialogue
+fn start_trainer_battle(mut commands: Commands, mut events: EventReader<StartTrainerBattle>, current: Option<Res<TrainerBattle>>,
+                        trainers: TrainerLookupService, pkmn_lookup: PokemonLookupService, player: Res<Player>,
+                        mut fluent: Fluent, mut rng: Local<Rng>) {
+    let StartTrainerBattle(id) = match events.iter().last() {
+        Some(e) => e,
+        None => return
+    };
+    if current.is_some() {
+        warn!("Trainer {} challenged the player mid-battle", id);
+        return;
+    }
+    if player.defeated_trainers.has_defeated(id) {
+        return;
+    }
+    let trainer = match trainers.lookup(id) {
+        Some(t) => t,
+        None => {
+            warn!("No trainer with ID {}", id);
+            return;
+        }
+    };
+
+    let original_trainer = trainer.original_trainer();
+    let moves = pkmn_lookup.moves()
+        .expect("Missing move data");
+    let mut opponent = Vec::new();
+    for template in trainer.team.iter().cloned() {
+        let species = template.species.resolve(&mut **rng);
+        let data = pkmn_lookup.lookup(species)
+            .expect("Missing Pokemon Species data");
+        let pokemon = template.resolve(species, data, moves, &original_trainer, &mut **rng);
+        opponent.push(Battler::new(pokemon, data));
+    }
+
+    let party = player.party.iter()
+        .filter(|p| !p.egg)
+        .map(|p| {
+            let data = pkmn_lookup.lookup(p.species)
+                .expect("Missing Pokemon Species data");
+            Battler::new(p.clone(), data)
+        })
+        .collect();
+
+    commands.insert_resource(TrainerBattle {
+        trainer: trainer.id.clone(),
+        battle: Battle::new(Side::new(party), Side::new(opponent)),
+        ai: trainer.ai,
+        items: trainer.items.clone(),
+        rng: BattleRng::seed_from_u64(rng.gen())
+    });
+
+    let class = fluent.get_trainer_class_name(trainer.class);
+    fluent.buffer_string("trainer-class", class);
+    fluent.buffer_string("trainer-name", &trainer.name);
+
+    let timeline = commands.spawn_bundle(ActionsBundle::default()).id();
+    let mut actions = commands.action(timeline);
+    if let Some(key) = &trainer.before_battle {
+        actions = actions.add(ShowTextAction(TextBoxOptions::new(key).with_max_lines(2)));
+    }
+    actions.add(ShowTextAction(TextBoxOptions::new(BATTLE_START).with_max_lines(2)));
+}
+
+/// Play a turn of the current trainer battle once the player chooses their action.
+/// The trainer's AI chooses theirs, possibly using one of the trainer's items, and sends out a
+/// replacement if their Pokemon faints. Once either side has no Pokemon left, the battle ends.
+fn play_trainer_turn(mut events: EventReader<ChooseBattleAction>, current: Option<ResMut<TrainerBattle>>,
+                     pkmn_lookup: PokemonLookupService, mut bag: ResMut<Bag>,
+                     mut ended: EventWriter<TrainerBattleEnded>, mut rng: Local<Rng>) {
+    let ChooseBattleAction(action) = match events.iter().last() {
+        Some(e) => e,
+        None => return
+    };
+    let mut current = match current {
+        Some(c) => c,
+        None => return
+    };
+    let TrainerBattle { battle, ai, items, rng: battle_rng, .. } = &mut *current;
+    if battle.is_over() {
+        return;
+    }
+    let moves = pkmn_lookup.moves()
+        .expect("Missing move data");
+    let species = pkmn_lookup.species()
+        .expect("Missing Pokemon Species data");
+
+    if battle.needs_replacement(SideId::Player) {
+        match action {
+            Action::Switch(slot) if battle.player.can_switch_to(*slot) => { battle.replace(SideId::Player, *slot); },
+            _ => warn!("The player must send out a Pokemon, not {:?}", action)
+        }
+        return;
+    }
+    match action {
+        Action::Run => {
+            warn!("The player can't run from a trainer battle");
+            return;
+        },
+        Action::UseItem(item, _) if !bag.contains(item) => {
+            warn!("The player has no {:?} to use", item);
+            return;
+        },
+        _ => {}
+    }
+
+    // The AI draws from its own RNG, so the battle's RNG only depends on the actions taken
+    let opponent = ai.choose_trainer_action(battle, SideId::Opponent, items, moves, &mut **rng);
+    let turn = battle.play_turn(action.clone(), opponent, moves, battle_rng);
+    battle.update_forms(&turn, species);
+
+    let context = ItemUseContext { moves, species, evolutions: &[] };
+    for event in turn {
+        if let BattleEvent::ItemUsed { side, item, slot } = event {
+            let pokemon = match battle.side_mut(side).party.get_mut(slot) {
+                Some(b) => &mut b.pokemon,
+                None => continue
+            };
+            let result = match side {
+                SideId::Player => item_use::use_from_bag(&mut bag, &item, pokemon, None, &context),
+                // The trainer's item was already taken out of their items when it was chosen
+                SideId::Opponent => item_use::use_item(&item, pokemon, None, &context)
+            };
+            if let Err(e) = result {
+                warn!("{:?} had no effect: {}", item, e);
+            }
+        }
+    }
+
+    if battle.needs_replacement(SideId::Opponent) {
+        if let Some(slot) = ai.choose_replacement(battle, SideId::Opponent, moves, &mut **rng) {
+            battle.replace(SideId::Opponent, slot);
+        }
+    }
+    if battle.outcome().is_some() {
+        ended.send(TrainerBattleEnded);
+    }
+}
+
+/// Resolve a finished trainer battle.
+/// On a win, the player is paid and the trainer is recorded as defeated. On a loss, the player
+/// whites out, losing money and returning to the last Pokemon Center, where their party is healed.
+fn end_trainer_battle(mut commands: Commands, mut events: EventReader<TrainerBattleEnded>, current: Option<Res<TrainerBattle>>,
+                      trainers: TrainerLookupService, pkmn_lookup: PokemonLookupService, mut player: ResMut<Player>,
+                      mut fluent: Fluent, mut rng: Local<Rng>) {
+    if events.iter().last().is_none() {
+        return;
+    }
+    let current = match current {
+        Some(c) => c,
+        None => return
+    };
+    commands.remove_resource::<TrainerBattle>();
+
+    // Carry HP, PP, and status back over to the party, reverting any forms that only last in battle.
+    // Fainting lowers friendship, more so against a much stronger opponent.
+    let opponent = &current.battle.opponent;
+    let strongest_foe = opponent.party.iter().map(|b| b.pokemon.level).max().unwrap_or_default();
+    let boss = trainers.lookup(&current.trainer).map_or(false, |t| t.class.is_boss());
+    let battlers = current.battle.player.party.iter();
+    for (pokemon, battler) in player.party.iter_mut().filter(|p| !p.egg).zip(battlers) {
+        let fainted = pokemon.has_health() && battler.pokemon.is_fainted();
+        *pokemon = battler.pokemon.clone();
+        pkmn_lookup.apply_form_trigger(pokemon, FormTrigger::BattleEnded);
+        if fainted && strongest_foe >= pokemon.level.saturating_add(30) {
+            friendship::apply(pokemon, FriendshipEvent::FaintedToStrongerFoe);
+        } else if fainted {
+            friendship::apply(pokemon, FriendshipEvent::Fainted);
+        }
+        if boss {
+            friendship::apply(pokemon, FriendshipEvent::BossBattle);
+        }
+    }
+    pokerus::after_battle(player.party.as_mut_slice(), &mut **rng);
+
+    // Every opposing Pokemon that was sent out has been seen
+    for (idx, battler) in opponent.party.iter().enumerate() {
+        if idx == opponent.active || battler.is_fainted() {
+            player.pokedex.see(battler.pokemon.species, battler.pokemon.gender);
+        }
+    }
+
+    let timeline = commands.spawn_bundle(ActionsBundle::default()).id();
+    let mut actions = commands.action(timeline);
+    match current.battle.outcome() {
+        Some(BattleOutcome::Victory(SideId::Player)) => {
+            player.defeated_trainers.record(current.trainer.as_str());
+            let trainer = trainers.lookup(&current.trainer);
+            let prize = trainer.map_or(0, |t| t.prize_money());
+            player.money = player.money.saturating_add(prize).min(MAX_MONEY);
+
+            fluent.buffer_string("prize-money", prize);
+            actions = actions.add(ShowTextAction(TextBoxOptions::new(BATTLE_WON).with_max_lines(2)));
+            if let Some(key) = trainer.and_then(|t| t.after_battle.as_ref()) {
+                actions = actions.add(ShowTextAction(TextBoxOptions::new(key).with_max_lines(2)));
+            }
+            actions.add(ShowTextAction(TextBoxOptions::new(BATTLE_PRIZE).with_max_lines(2)));
+        },
+        Some(BattleOutcome::Victory(SideId::Opponent)) => {
+            let highest_level = player.party.iter()
+                .map(|p| p.level)
+                .max()
+                .unwrap_or(0);
+            let penalty = trainer::whiteout_penalty(player.money, player.badges, highest_level);
+            player.money -= penalty;
+            player.return_to_pokemon_center();
+
+            fluent.buffer_string("whiteout-money", penalty);
+            actions.add(ShowTextAction(TextBoxOptions::new(BATTLE_WHITEOUT).with_max_lines(2)));
+        },
+        _ => {}
+    }
+}
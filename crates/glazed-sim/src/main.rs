@@ -0,0 +1,138 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use glazed_data::battle::ai::AiTier;
+use glazed_data::battle::SideId;
+use glazed_data::pokemon::{PokemonTemplate, SpeciesTemplate};
+use glazed_data::types::TypeChart;
+
+use crate::sim::{SimConfig, SimData};
+
+mod sim;
+
+const USAGE: &str = "\
+Usage: glazed_sim <player team> <opponent team> [options]
+
+Runs battles between two teams, with both sides controlled by the AI.
+
+Options:
+    --battles <n>        Number of battles to run (default: 100)
+    --seed <n>           Seed for the first battle (default: 0)
+    --player-ai <tier>   Random, Greedy, or Lookahead (default: Greedy)
+    --opponent-ai <tier> Random, Greedy, or Lookahead (default: Greedy)
+    --turn-limit <n>     Stop battles which go on this long (default: 500)
+    --data <path>        Species data file (default: glazed-dx/assets/pkmn/data.pkmn)
+    --moves <path>       Move data file (default: glazed-dx/assets/pkmn/moves.attacks)
+    --types <path>       Type chart file (default: glazed-dx/assets/pkmn/types.chart)";
+
+/// Everything passed in on the command line
+struct Args {
+    player: PathBuf,
+    opponent: PathBuf,
+    data: PathBuf,
+    moves: PathBuf,
+    types: PathBuf,
+    config: SimConfig
+}
+impl Args {
+    fn parse() -> Result<Args, String> {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../glazed-dx/assets/pkmn");
+        let mut teams = Vec::new();
+        let mut data = assets.join("data.pkmn");
+        let mut moves = assets.join("moves.attacks");
+        let mut types = assets.join("types.chart");
+        let mut config = SimConfig {
+            battles: 100,
+            seed: 0,
+            player_ai: AiTier::Greedy,
+            opponent_ai: AiTier::Greedy,
+            turn_limit: 500
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--battles" => config.battles = parse_number(&value()?)?,
+                "--seed" => config.seed = parse_number(&value()?)?,
+                "--player-ai" => config.player_ai = parse_tier(&value()?)?,
+                "--opponent-ai" => config.opponent_ai = parse_tier(&value()?)?,
+                "--turn-limit" => config.turn_limit = parse_number(&value()?)?,
+                "--data" => data = PathBuf::from(value()?),
+                "--moves" => moves = PathBuf::from(value()?),
+                "--types" => types = PathBuf::from(value()?),
+                "--help" | "-h" => return Err(String::new()),
+                _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+                _ => teams.push(PathBuf::from(arg))
+            }
+        }
+
+        let mut teams = teams.into_iter();
+        match (teams.next(), teams.next(), teams.next()) {
+            (Some(player), Some(opponent), None) => Ok(Args { player, opponent, data, moves, types, config }),
+            _ => Err(String::from("Expected exactly two team files"))
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{} is not a valid number", value))
+}
+
+fn parse_tier(value: &str) -> Result<AiTier, String> {
+    serde_yaml::from_str(value).map_err(|_| format!("{} is not a valid AI tier", value))
+}
+
+fn load_team(path: &Path) -> Result<Vec<PokemonTemplate>, Box<dyn Error>> {
+    let team: Vec<PokemonTemplate> = serde_yaml::from_reader(File::open(path)?)?;
+    if team.is_empty() || team.len() > 6 {
+        return Err(format!("{} should contain between 1 and 6 Pokemon", path.display()).into());
+    }
+    Ok(team)
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    TypeChart::from_yaml(&fs::read_to_string(&args.types)?)?.make_active();
+    let data = SimData::load(&args.data, &args.moves)?;
+    let player = load_team(&args.player)?;
+    let opponent = load_team(&args.opponent)?;
+
+    let report = sim::run(&data, &player, &opponent, &args.config)?;
+
+    let names = |team: &[PokemonTemplate]| team.iter()
+        .map(|t| match t.species {
+            SpeciesTemplate::HardCoded(s) => format!("{:?} Lv. {}", s, t.level),
+            SpeciesTemplate::RandomUnown => format!("Unown Lv. {}", t.level)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("Player ({:?}):   {}", args.config.player_ai, names(&player));
+    println!("Opponent ({:?}): {}", args.config.opponent_ai, names(&opponent));
+    println!();
+    println!("Battles:       {}", report.battles);
+    println!("Player wins:   {} ({:.1}%)", report.player_wins, report.win_rate(SideId::Player));
+    println!("Opponent wins: {} ({:.1}%)", report.opponent_wins, report.win_rate(SideId::Opponent));
+    println!("Unfinished:    {} ({:.1}%)", report.unfinished, report.unfinished_rate());
+    println!("Average turns: {:.1}", report.average_turns());
+    Ok(())
+}
+
+fn main() {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("{}\n", e);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = run(args) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
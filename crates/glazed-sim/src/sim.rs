@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::path::Path;
+
+use rand::SeedableRng;
+
+use glazed_data::attack::MoveData;
+use glazed_data::battle::ai::AiTier;
+use glazed_data::battle::replay::BattleRng;
+use glazed_data::battle::{Battle, BattleOutcome, Battler, MoveDataLookup, Side, SideId};
+use glazed_data::pokemon::{self, PokemonTemplate, SpeciesData, TemplateTrainer};
+use glazed_data::species::Species;
+
+/// Offsets the seed used for AI decisions, so they do not mirror the battle's own rolls
+const AI_SEED_OFFSET: u64 = 0x5EED;
+/// Offsets the seed used to generate teams
+const TEAM_SEED_OFFSET: u64 = 0x7EA3;
+
+/// All the data a battle needs to look up
+pub struct SimData {
+    pub species: HashMap<Species, SpeciesData>,
+    pub moves: MoveDataLookup
+}
+impl SimData {
+    pub fn load(species: &Path, moves: &Path) -> Result<SimData, Box<dyn Error>> {
+        let species = pokemon::validate_species_data(&fs::read_to_string(species)?)?;
+        let moves: Vec<MoveData> = serde_yaml::from_reader(File::open(moves)?)?;
+        Ok(SimData {
+            species,
+            moves: moves.into_iter().map(|m| (m.id, m)).collect()
+        })
+    }
+
+    /// Create the battlers for one team.
+    /// Fails if a Pokemon knows a move without move data.
+    fn create_team(&self, team: &[PokemonTemplate], trainer: &TemplateTrainer, rng: &mut BattleRng) -> Result<Vec<Battler>, String> {
+        team.iter()
+            .map(|template| {
+                let template = template.clone();
+                let species = template.species.resolve(rng);
+                let data = pokemon::species_data(&self.species, species)
+                    .ok_or_else(|| format!("Missing species data for {:?}", species))?;
+                let pokemon = template.resolve(species, data, &self.moves, trainer, rng);
+                if let Some(attack) = pokemon.get_moves().into_iter().find(|m| !self.moves.contains_key(m)) {
+                    return Err(format!("Missing move data for {:?}", attack));
+                }
+                Ok(Battler::new(pokemon, data))
+            })
+            .collect()
+    }
+}
+
+/// Settings for a run of simulated battles
+pub struct SimConfig {
+    pub battles: u32,
+    pub seed: u64,
+    pub player_ai: AiTier,
+    pub opponent_ai: AiTier,
+    /// Battles which reach this many turns are stopped, and counted as unfinished
+    pub turn_limit: u16
+}
+
+/// The combined results of every simulated battle
+#[derive(Debug, Default)]
+pub struct SimReport {
+    pub battles: u32,
+    pub player_wins: u32,
+    pub opponent_wins: u32,
+    pub unfinished: u32,
+    pub total_turns: u64
+}
+impl SimReport {
+    pub fn win_rate(&self, side: SideId) -> f64 {
+        let wins = match side {
+            SideId::Player => self.player_wins,
+            SideId::Opponent => self.opponent_wins
+        };
+        percentage(wins, self.battles)
+    }
+
+    pub fn unfinished_rate(&self) -> f64 {
+        percentage(self.unfinished, self.battles)
+    }
+
+    pub fn average_turns(&self) -> f64 {
+        if self.battles == 0 { 0.0 } else { self.total_turns as f64 / f64::from(self.battles) }
+    }
+}
+
+fn percentage(count: u32, total: u32) -> f64 {
+    if total == 0 { 0.0 } else { f64::from(count) * 100.0 / f64::from(total) }
+}
+
+/// Run every battle, and combine the results.
+/// Battle N uses seed + N, so any single battle can be reproduced on its own.
+pub fn run(data: &SimData, player: &[PokemonTemplate], opponent: &[PokemonTemplate], config: &SimConfig) -> Result<SimReport, String> {
+    let player_trainer = TemplateTrainer { trainer_id: 0, secret_id: 0, name: String::from("Player") };
+    let opponent_trainer = TemplateTrainer { trainer_id: 1, secret_id: 1, name: String::from("Opponent") };
+
+    let mut report = SimReport::default();
+    for n in 0..config.battles {
+        let seed = config.seed.wrapping_add(u64::from(n));
+        let mut team_rng = BattleRng::seed_from_u64(seed.wrapping_add(TEAM_SEED_OFFSET));
+        let battle = Battle::new(
+            Side::new(data.create_team(player, &player_trainer, &mut team_rng)?),
+            Side::new(data.create_team(opponent, &opponent_trainer, &mut team_rng)?)
+        );
+
+        let battle = simulate(battle, data, config, seed);
+        report.battles += 1;
+        report.total_turns += u64::from(battle.turn);
+        match battle.outcome() {
+            Some(BattleOutcome::Victory(SideId::Player)) | Some(BattleOutcome::Fled(SideId::Opponent)) => report.player_wins += 1,
+            Some(BattleOutcome::Victory(SideId::Opponent)) | Some(BattleOutcome::Fled(SideId::Player)) => report.opponent_wins += 1,
+            None => report.unfinished += 1
+        }
+    }
+    Ok(report)
+}
+
+/// Play a single battle to completion, with both sides controlled by the AI
+fn simulate(mut battle: Battle, data: &SimData, config: &SimConfig, seed: u64) -> Battle {
+    let moves = &data.moves;
+    let mut rng = BattleRng::seed_from_u64(seed);
+    let mut ai_rng = BattleRng::seed_from_u64(seed.wrapping_add(AI_SEED_OFFSET));
+    let tier = |side: SideId| match side {
+        SideId::Player => config.player_ai,
+        SideId::Opponent => config.opponent_ai
+    };
+
+    while !battle.is_over() && battle.turn < config.turn_limit {
+        let player = tier(SideId::Player).choose_action(&battle, SideId::Player, moves, &mut ai_rng);
+        let opponent = tier(SideId::Opponent).choose_action(&battle, SideId::Opponent, moves, &mut ai_rng);
+        let events = battle.play_turn(player, opponent, moves, &mut rng);
+        battle.update_forms(&events, &data.species);
+
+        for side in [SideId::Player, SideId::Opponent] {
+            if battle.needs_replacement(side) {
+                if let Some(slot) = tier(side).choose_replacement(&battle, side, moves, &mut ai_rng) {
+                    battle.replace(side, slot);
+                }
+            }
+        }
+    }
+    battle
+}